@@ -7,6 +7,13 @@ use std::process::{Command, Stdio};
 
 use crate::models::*;
 use crate::auth::UserTokens;
+use crate::uri::SpotifyUri;
+
+/// Most track IDs Spotify accepts in one library (liked songs) request.
+const LIBRARY_BATCH: usize = 50;
+
+/// Most track URIs Spotify accepts in one playlist add/remove request.
+const PLAYLIST_BATCH: usize = 100;
 
 #[derive(Debug, Clone)]
 pub struct SpotifyClient {
@@ -189,6 +196,18 @@ impl SpotifyClient {
         self.user_tokens = Some(tokens);
     }
 
+    /// Scopes the saved user token was granted.
+    pub fn granted_scopes(&self) -> Vec<String> {
+        self.user_tokens
+            .as_ref()
+            .map(|tokens| tokens.scope.split_whitespace().map(str::to_string).collect())
+            .unwrap_or_default()
+    }
+
+    pub async fn get_current_user(&self) -> Result<UserProfile> {
+        self.make_user_request("GET", "me", None).await
+    }
+
     async fn make_user_request<T>(&self, method: &str, endpoint: &str, body: Option<serde_json::Value>) -> Result<T>
     where
         T: for<'de> Deserialize<'de>,
@@ -245,17 +264,29 @@ impl SpotifyClient {
         }
     }
 
-    pub async fn play_track(&self, track_uri: &str) -> Result<()> {
+    /// Like `get_current_playback`, but only "nothing playing" (204) is
+    /// `None`; request and token errors come back as errors.
+    pub async fn get_playback_state(&self) -> Result<Option<CurrentPlayback>> {
+        let value: serde_json::Value = self.make_user_request("GET", "me/player", None).await?;
+        if value.as_object().is_some_and(|o| o.is_empty()) {
+            return Ok(None);
+        }
+        Ok(Some(serde_json::from_value(value)?))
+    }
+
+    /// Plays a single track or episode.
+    pub async fn play_track(&self, track_uri: &SpotifyUri) -> Result<()> {
         let body = serde_json::json!({
-            "uris": [track_uri]
+            "uris": [track_uri.to_string()]
         });
         self.make_user_request_no_response("PUT", "me/player/play", Some(body)).await?;
         Ok(())
     }
 
-    pub async fn play_tracks_with_offset(&self, track_uris: &[String], offset: usize) -> Result<()> {
+    pub async fn play_tracks_with_offset(&self, track_uris: &[SpotifyUri], offset: usize) -> Result<()> {
+        let uris: Vec<String> = track_uris.iter().map(|uri| uri.to_string()).collect();
         let body = serde_json::json!({
-            "uris": track_uris,
+            "uris": uris,
             "offset": {
                 "position": offset
             }
@@ -264,9 +295,10 @@ impl SpotifyClient {
         Ok(())
     }
 
-    pub async fn play_playlist_with_offset(&self, playlist_uri: &str, offset: usize) -> Result<()> {
+    /// Plays an album or playlist starting at track `offset`.
+    pub async fn play_context_with_offset(&self, context_uri: &SpotifyUri, offset: usize) -> Result<()> {
         let body = serde_json::json!({
-            "context_uri": playlist_uri,
+            "context_uri": context_uri.to_string(),
             "offset": {
                 "position": offset
             }
@@ -275,6 +307,15 @@ impl SpotifyClient {
         Ok(())
     }
 
+    /// Plays an album, artist, playlist or show from the start.
+    pub async fn play_context(&self, context_uri: &SpotifyUri) -> Result<()> {
+        let body = serde_json::json!({
+            "context_uri": context_uri.to_string()
+        });
+        self.make_user_request_no_response("PUT", "me/player/play", Some(body)).await?;
+        Ok(())
+    }
+
     pub async fn pause_playback(&self) -> Result<()> {
         self.make_user_request_no_response("PUT", "me/player/pause", None).await?;
         Ok(())
@@ -295,6 +336,13 @@ impl SpotifyClient {
         Ok(())
     }
 
+    pub async fn seek(&self, position_ms: u64) -> Result<()> {
+        let endpoint = format!("me/player/seek?position_ms={}", position_ms);
+        let empty_body = serde_json::json!({});
+        self.make_user_request_no_response("PUT", &endpoint, Some(empty_body)).await?;
+        Ok(())
+    }
+
     pub async fn set_volume(&self, volume_percent: u8) -> Result<()> {
         let endpoint = format!("me/player/volume?volume_percent={}", volume_percent.min(100));
         let empty_body = serde_json::json!({});
@@ -336,6 +384,15 @@ impl SpotifyClient {
         self.make_user_request("GET", "me/player/devices", None).await
     }
 
+    /// Moves playback to another device and keeps it playing.
+    pub async fn transfer_playback(&self, device_id: &str) -> Result<()> {
+        let body = serde_json::json!({
+            "device_ids": [device_id],
+            "play": true
+        });
+        self.make_user_request_no_response("PUT", "me/player", Some(body)).await
+    }
+
     pub async fn get_recently_played(&self, limit: u32) -> Result<RecentlyPlayedResponse> {
         let endpoint = format!("me/player/recently-played?limit={}", limit.min(50));
         self.make_user_request("GET", &endpoint, None).await
@@ -417,8 +474,155 @@ impl SpotifyClient {
         Ok(all_tracks)
     }
 
-    pub async fn add_to_queue(&self, track_uri: &str) -> Result<()> {
-        let endpoint = format!("me/player/queue?uri={}", urlencoding::encode(track_uri));
+    pub async fn get_saved_albums(&self, limit: u32, offset: u32) -> Result<SavedAlbumsResponse> {
+        let endpoint = format!("me/albums?limit={}&offset={}", limit.min(50), offset);
+        self.make_user_request("GET", &endpoint, None).await
+    }
+
+    pub async fn get_all_saved_albums(&self) -> Result<Vec<Album>> {
+        let mut all_albums = Vec::new();
+        let mut offset = 0;
+        let limit = 50;
+
+        loop {
+            match self.get_saved_albums(limit, offset).await {
+                Ok(response) => {
+                    if response.items.is_empty() {
+                        break; // No more albums
+                    }
+
+                    for saved in response.items {
+                        all_albums.push(saved.album);
+                    }
+
+                    if response.next.is_none() {
+                        break;
+                    }
+                    offset += limit;
+
+                    // Small delay to avoid rate limiting
+                    tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+                }
+                Err(e) => {
+                    if all_albums.is_empty() {
+                        return Err(e);
+                    }
+                    break;
+                }
+            }
+        }
+
+        Ok(all_albums)
+    }
+
+    /// A page of followed artists; the API pages them by the last artist ID.
+    pub async fn get_followed_artists(&self, limit: u32, after: Option<&str>) -> Result<FollowedArtistsResponse> {
+        let mut endpoint = format!("me/following?type=artist&limit={}", limit.min(50));
+        if let Some(after) = after {
+            endpoint.push_str(&format!("&after={}", after));
+        }
+        self.make_user_request("GET", &endpoint, None).await
+    }
+
+    pub async fn get_all_followed_artists(&self) -> Result<Vec<Artist>> {
+        let mut all_artists = Vec::new();
+        let mut after: Option<String> = None;
+
+        loop {
+            let response = self.get_followed_artists(50, after.as_deref()).await?.artists;
+            all_artists.extend(response.items);
+
+            after = response.cursors.and_then(|c| c.after);
+            if response.next.is_none() || after.is_none() {
+                break;
+            }
+
+            // Small delay to avoid rate limiting
+            tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+        }
+
+        Ok(all_artists)
+    }
+
+    pub async fn get_album_tracks(&self, album_id: &str, limit: u32, offset: u32) -> Result<AlbumTracksResponse> {
+        let endpoint = format!("albums/{}/tracks?limit={}&offset={}", album_id, limit.min(50), offset);
+        self.make_request(&endpoint).await
+    }
+
+    /// All tracks of an album. The API returns simplified tracks, so the album
+    /// itself is attached to each one.
+    pub async fn get_all_album_tracks(&self, album: &Album) -> Result<Vec<Track>> {
+        let mut all_tracks = Vec::new();
+        let mut offset = 0;
+        let limit = 50;
+
+        loop {
+            let response = self.get_album_tracks(&album.id, limit, offset).await?;
+            let has_more = response.next.is_some();
+
+            for mut track in response.items {
+                track.album = Some(album.clone());
+                all_tracks.push(track);
+            }
+
+            if !has_more {
+                break;
+            }
+            offset += limit;
+
+            // Small delay to avoid rate limiting
+            tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+        }
+
+        Ok(all_tracks)
+    }
+
+    pub async fn get_artist_albums(&self, artist_id: &str, limit: u32, offset: u32) -> Result<SearchAlbums> {
+        let endpoint = format!(
+            "artists/{}/albums?include_groups=album,single&limit={}&offset={}",
+            artist_id, limit.min(50), offset
+        );
+        self.make_request(&endpoint).await
+    }
+
+    /// Albums and singles by an artist, newest first as returned by the API.
+    pub async fn get_all_artist_albums(&self, artist_id: &str) -> Result<Vec<Album>> {
+        let mut all_albums = Vec::new();
+        let mut offset = 0;
+        let limit = 50;
+
+        loop {
+            let response = self.get_artist_albums(artist_id, limit, offset).await?;
+            let count = response.items.len() as u32;
+            all_albums.extend(response.items);
+
+            offset += limit;
+            if count < limit || offset >= response.total {
+                break;
+            }
+
+            // Small delay to avoid rate limiting
+            tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+        }
+
+        Ok(all_albums)
+    }
+
+    pub async fn get_related_artists(&self, artist_id: &str) -> Result<Vec<Artist>> {
+        let endpoint = format!("artists/{}/related-artists", artist_id);
+        let response: RelatedArtistsResponse = self.make_user_request("GET", &endpoint, None).await?;
+        Ok(response.artists)
+    }
+
+    /// An artist's most popular tracks in the user's market.
+    pub async fn get_artist_top_tracks(&self, artist_id: &str) -> Result<Vec<Track>> {
+        let endpoint = format!("artists/{}/top-tracks?market=from_token", artist_id);
+        let response: TopTracksResponse = self.make_user_request("GET", &endpoint, None).await?;
+        Ok(response.tracks)
+    }
+
+    pub async fn add_to_queue(&self, track_uri: &SpotifyUri) -> Result<()> {
+        let endpoint = format!("me/player/queue?uri={}", urlencoding::encode(&track_uri.to_string()));
         // POST requests need a body, even if empty, to set proper Content-Length header
         let empty_body = serde_json::json!({});
 
@@ -463,172 +667,6 @@ impl SpotifyClient {
         self.make_user_request("GET", "me/player/queue", None).await
     }
 
-    pub async fn get_recommendations(&self, track_id: &str, limit: u32) -> Result<(serde_json::Value, Vec<String>)> {
-        let mut logs = Vec::new();
-        let endpoint = format!("recommendations?seed_tracks={}&limit={}", track_id, limit.min(100));
-
-        // Debug: Check if we have user authentication
-        if self.user_tokens.is_none() {
-            let error_msg = "No user authentication tokens available for recommendations".to_string();
-            logs.push(format!("❌ {}", error_msg));
-            return Err(anyhow!(error_msg));
-        }
-
-        logs.push(format!("🔍 Requesting recommendations for track: {}", track_id));
-        logs.push(format!("🔗 API endpoint: {}", endpoint));
-
-        match self.make_user_request("GET", &endpoint, None).await {
-            Ok(response) => {
-                logs.push("✅ Recommendations API call successful".to_string());
-                Ok((response, logs))
-            }
-            Err(e) => {
-                logs.push(format!("❌ Recommendations API failed: {}", e));
-                logs.push("🔄 Trying fallback approach using artist data...".to_string());
-
-                match self.get_fallback_recommendations_with_logs(track_id, logs).await {
-                    Ok((response, final_logs)) => Ok((response, final_logs)),
-                    Err((error, final_logs)) => {
-                        // Return error but also return logs for display
-                        eprintln!("Final error: {}", error);
-                        for log in &final_logs {
-                            eprintln!("{}", log);
-                        }
-                        Err(anyhow!("Recommendations failed: {}", error))
-                    }
-                }
-            }
-        }
-    }
-
-
-    async fn get_fallback_recommendations_with_logs(&self, track_id: &str, mut logs: Vec<String>) -> Result<(serde_json::Value, Vec<String>), (String, Vec<String>)> {
-        // Get the track first to find the artist
-        match self.get_track(track_id).await {
-            Ok(track) => {
-                if let Some(artist) = track.artists.first() {
-                    logs.push(format!("🎤 Getting top tracks for artist: {}", artist.name));
-
-                    // Get artist's top tracks as recommendations
-                    let endpoint = format!("artists/{}/top-tracks?market=US", artist.id);
-                    match self.make_user_request::<serde_json::Value>("GET", &endpoint, None).await {
-                        Ok(response) => {
-                            // Format response to match recommendations format
-                            if let Some(tracks) = response.get("tracks") {
-                                let formatted_response = serde_json::json!({
-                                    "tracks": tracks
-                                });
-                                logs.push(format!("✅ Got {} artist top tracks as fallback",
-                                    tracks.as_array().map(|t| t.len()).unwrap_or(0)));
-                                return Ok((formatted_response, logs));
-                            }
-                        }
-                        Err(e) => {
-                            logs.push(format!("❌ Fallback also failed: {}", e));
-                        }
-                    }
-                }
-            }
-            Err(e) => {
-                logs.push(format!("❌ Could not get track info for fallback: {}", e));
-            }
-        }
-
-        Err(("Both recommendations and fallback failed".to_string(), logs))
-    }
-
-    pub async fn start_radio_from_track(&self, track_uri: &str) -> Result<Vec<String>> {
-        let mut radio_logs = Vec::new();
-        // Extract track ID from URI (format: spotify:track:TRACK_ID)
-        let track_id = track_uri.strip_prefix("spotify:track:")
-            .ok_or_else(|| anyhow!("Invalid track URI format"))?;
-
-        radio_logs.push("📻 Starting radio mode for track...".to_string());
-
-        // First, play the selected track
-        self.play_track(track_uri).await?;
-        radio_logs.push("▶️  Playing your selected track".to_string());
-
-        // Configure playback settings for autoplay
-        tokio::time::sleep(tokio::time::Duration::from_millis(800)).await;
-        let _ = self.enable_autoplay().await;
-        radio_logs.push("🔄 Configured playback settings for autoplay".to_string());
-
-        // Try to get recommendations, with multiple fallback approaches
-        let recommendations_result = self.get_recommendations(track_id, 20).await;
-
-        match recommendations_result {
-            Ok((recommendations, logs)) => {
-                // Add all the API activity logs
-                radio_logs.extend(logs);
-
-                if let Some(tracks) = recommendations.get("tracks").and_then(|t| t.as_array()) {
-                    radio_logs.push(format!("🎵 Found {} recommended tracks, adding to queue...", tracks.len()));
-
-                    let mut added_count = 0;
-                    for (i, track) in tracks.iter().enumerate().take(15) {
-                        if let Some(uri) = track.get("uri").and_then(|u| u.as_str()) {
-                            if let Some(name) = track.get("name").and_then(|n| n.as_str()) {
-                                // Add delay between queue additions to avoid rate limiting
-                                if i > 0 {
-                                    tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
-                                }
-
-                                match self.add_to_queue(uri).await {
-                                    Ok(_) => {
-                                        added_count += 1;
-                                        radio_logs.push(format!("  ✅ Added: {}", name));
-                                    }
-                                    Err(e) => {
-                                        radio_logs.push(format!("  ❌ Failed to add '{}': {}", name, e));
-                                        // Don't break, continue with other tracks
-                                    }
-                                }
-                            }
-                        }
-                    }
-
-                    if added_count > 0 {
-                        radio_logs.push(format!("🎶 Successfully added {} similar tracks to queue!", added_count));
-                        radio_logs.push("🔄 Radio mode active - tracks will continue playing automatically".to_string());
-
-                        // Final settings to ensure continuous playback
-                        tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
-                        let _ = self.set_repeat("off").await; // Allow queue to flow naturally
-
-                        // Debug: Check what's actually in the queue
-                        tokio::time::sleep(tokio::time::Duration::from_millis(1000)).await;
-                        match self.get_queue().await {
-                            Ok(queue_response) => {
-                                radio_logs.push(format!("📋 Current queue has {} tracks", queue_response.queue.len()));
-                                for (i, track) in queue_response.queue.iter().take(3).enumerate() {
-                                    radio_logs.push(format!("  {}. {}", i + 1, track.name));
-                                }
-                                if queue_response.queue.len() > 3 {
-                                    radio_logs.push(format!("  ... and {} more", queue_response.queue.len() - 3));
-                                }
-                            }
-                            Err(e) => {
-                                radio_logs.push(format!("❌ Could not check queue status: {}", e));
-                            }
-                        }
-                    } else {
-                        radio_logs.push("⚠️  Could not add any tracks to queue - check your Spotify device".to_string());
-                        radio_logs.push("💡 Make sure you have an active Spotify device and Premium account".to_string());
-                    }
-                } else {
-                    radio_logs.push("⚠️  No tracks found in recommendations response".to_string());
-                }
-            }
-            Err(e) => {
-                radio_logs.push(format!("❌ Failed to get recommendations: {}", e));
-                radio_logs.push("💡 Playing single track - recommendations require user authentication".to_string());
-            }
-        }
-
-        Ok(radio_logs)
-    }
-
     pub async fn like_song(&self, track_id: &str) -> Result<()> {
         let endpoint = format!("me/tracks?ids={}", track_id);
         let empty_body = serde_json::json!({});
@@ -648,6 +686,81 @@ impl SpotifyClient {
         Ok(response.first().copied().unwrap_or(false))
     }
 
+    pub async fn check_if_album_saved(&self, album_id: &str) -> Result<bool> {
+        let endpoint = format!("me/albums/contains?ids={}", album_id);
+        let response: Vec<bool> = self.make_user_request("GET", &endpoint, None).await?;
+        Ok(response.first().copied().unwrap_or(false))
+    }
+
+    pub async fn save_album(&self, album_id: &str) -> Result<()> {
+        let body = serde_json::json!({ "ids": [album_id] });
+        self.make_user_request_no_response("PUT", "me/albums", Some(body)).await
+    }
+
+    pub async fn remove_saved_album(&self, album_id: &str) -> Result<()> {
+        let body = serde_json::json!({ "ids": [album_id] });
+        self.make_user_request_no_response("DELETE", "me/albums", Some(body)).await
+    }
+
+    /// Like status for each ID, in order.
+    pub async fn check_liked_tracks(&self, track_ids: &[String]) -> Result<Vec<bool>> {
+        let mut liked = Vec::with_capacity(track_ids.len());
+        for batch in track_ids.chunks(LIBRARY_BATCH) {
+            let endpoint = format!("me/tracks/contains?ids={}", batch.join(","));
+            let response: Vec<bool> = self.make_user_request("GET", &endpoint, None).await?;
+            liked.extend(response);
+        }
+        Ok(liked)
+    }
+
+    pub async fn like_tracks(&self, track_ids: &[String]) -> Result<()> {
+        for batch in track_ids.chunks(LIBRARY_BATCH) {
+            let body = serde_json::json!({ "ids": batch });
+            self.make_user_request_no_response("PUT", "me/tracks", Some(body)).await?;
+        }
+        Ok(())
+    }
+
+    pub async fn unlike_tracks(&self, track_ids: &[String]) -> Result<()> {
+        for batch in track_ids.chunks(LIBRARY_BATCH) {
+            let body = serde_json::json!({ "ids": batch });
+            self.make_user_request_no_response("DELETE", "me/tracks", Some(body)).await?;
+        }
+        Ok(())
+    }
+
+    /// Creates a private playlist owned by `user_id`.
+    pub async fn create_playlist(&self, user_id: &str, name: &str) -> Result<Playlist> {
+        let endpoint = format!("users/{}/playlists", user_id);
+        let body = serde_json::json!({
+            "name": name,
+            "public": false
+        });
+        self.make_user_request("POST", &endpoint, Some(body)).await
+    }
+
+    /// Appends the tracks to the end of the playlist, keeping their order.
+    pub async fn add_tracks_to_playlist(&self, playlist_id: &str, uris: &[SpotifyUri]) -> Result<()> {
+        let endpoint = format!("playlists/{}/tracks", playlist_id);
+        for batch in uris.chunks(PLAYLIST_BATCH) {
+            let batch: Vec<String> = batch.iter().map(|uri| uri.to_string()).collect();
+            let body = serde_json::json!({ "uris": batch });
+            self.make_user_request_no_response("POST", &endpoint, Some(body)).await?;
+        }
+        Ok(())
+    }
+
+    /// Removes every occurrence of each track from the playlist.
+    pub async fn remove_tracks_from_playlist(&self, playlist_id: &str, uris: &[SpotifyUri]) -> Result<()> {
+        let endpoint = format!("playlists/{}/tracks", playlist_id);
+        for batch in uris.chunks(PLAYLIST_BATCH) {
+            let tracks: Vec<serde_json::Value> = batch.iter().map(|uri| serde_json::json!({ "uri": uri.to_string() })).collect();
+            let body = serde_json::json!({ "tracks": tracks });
+            self.make_user_request_no_response("DELETE", &endpoint, Some(body)).await?;
+        }
+        Ok(())
+    }
+
     pub fn launch_spotify_background() -> Result<()> {
         // First check if Spotify is already running
         let check_output = Command::new("pgrep")
@@ -106,7 +106,7 @@ impl SpotifyAuth {
                 ("code_challenge_method", "S256"),
                 ("code_challenge", &code_challenge),
                 ("state", &state),
-                ("scope", "user-read-playback-state user-modify-playback-state user-read-currently-playing streaming user-library-read playlist-read-private playlist-read-collaborative user-read-recently-played"),
+                ("scope", "user-read-playback-state user-modify-playback-state user-read-currently-playing streaming user-library-read user-library-modify user-follow-read playlist-read-private playlist-read-collaborative playlist-modify-public playlist-modify-private user-read-recently-played"),
             ]
             .iter()
             .map(|(k, v)| format!("{}={}", k, urlencoding::encode(v)))
@@ -117,7 +117,7 @@ impl SpotifyAuth {
         println!("🔐 Opening browser for Spotify authentication...");
         println!("If browser doesn't open automatically, visit: {}", auth_url);
 
-        if let Err(_) = webbrowser::open(&auth_url) {
+        if webbrowser::open(&auth_url).is_err() {
             println!("❌ Could not open browser automatically.");
             println!("Please manually open: {}", auth_url);
         }
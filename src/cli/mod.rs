@@ -0,0 +1,401 @@
+use serde_json::Value;
+use std::fmt;
+use std::io::{self, Write};
+
+use crate::commands::{parse_position, RepeatMode};
+use crate::daemon::{self, Adjust, DaemonClient, Engine, Request, SearchHit};
+use crate::models::{Device, ShuffleMode};
+use crate::uri::{ItemKind, SpotifyUri};
+
+mod session;
+mod status;
+
+pub use session::Session;
+pub use status::{Status, StatusOptions};
+
+const TOKENS_FILE: &str = ".spotify_tokens";
+
+pub const USAGE: &str = "\
+Usage: spotycli [COMMAND]
+
+Without a command, starts the terminal UI.
+
+Commands:
+  play [URI|QUERY]        Resume, play a URI or link, or play the first track matching QUERY
+  pause                   Pause
+  toggle                  Pause if playing, otherwise resume
+  next | prev             Skip forward or back
+  vol N|+N|-N             Set the volume, or change it by N
+  seek POS|+S|-S          Jump to POS (1:23 or 83) or move by S seconds
+  shuffle [on|off|smart]  Set shuffle; without an argument, toggle it
+  repeat off|track|context
+  like | unlike           Save or remove the current track from Liked Songs
+  queue URI               Add a track or episode to the queue
+  devices                 List devices: id, name, type, active, volume (tab-separated)
+  transfer NAME|ID        Move playback to a device
+  status [--json|--format T] [--follow]
+                          Print playback as JSON (default) or through the template T,
+                          e.g. '{artist} - {title} {progress}/{duration}'; --follow
+                          prints a new line whenever it changes
+  search [--type T] [--limit N] QUERY
+                          Print matching URIs and names (tab-separated);
+                          T is track (default), album, artist or playlist
+  daemon                  Keep running, polling playback and serving the commands
+                          above (and the TUI) over a Unix socket; see README
+
+Commands go through a running daemon when there is one.
+
+Exit status:
+  0  success            3  not signed in (run: cargo run --bin authenticate)
+  1  Spotify error      4  no active device
+  2  usage error        5  nothing found or nothing playing
+                        6  Spotify Premium required";
+
+/// A non-interactive command run from the shell.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Subcommand {
+    /// Resume, or play a URI, or search and play the first track.
+    Play(Option<String>),
+    Pause,
+    Toggle,
+    Next,
+    Previous,
+    Volume(Adjust<u8>),
+    /// Milliseconds, whether a position or a relative move.
+    Seek(Adjust<u64>),
+    /// `None` toggles between on and off.
+    Shuffle(Option<ShuffleMode>),
+    Repeat(RepeatMode),
+    Like,
+    Unlike,
+    Queue(SpotifyUri),
+    Devices,
+    Transfer(String),
+    Search { query: String, kind: ItemKind, limit: u32 },
+    Status(StatusOptions),
+    Daemon,
+    Help,
+}
+
+/// Why a subcommand failed; each maps to its own exit status.
+#[derive(Debug)]
+pub enum Failure {
+    Api(anyhow::Error),
+    Usage(String),
+    NotAuthenticated(String),
+    NoActiveDevice,
+    NotFound(String),
+    PremiumRequired,
+    /// Reported by a daemon, which already chose the exit status.
+    Daemon { code: i32, message: String },
+}
+
+impl Failure {
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Failure::Api(_) => 1,
+            Failure::Usage(_) => 2,
+            Failure::NotAuthenticated(_) => 3,
+            Failure::NoActiveDevice => 4,
+            Failure::NotFound(_) => 5,
+            Failure::PremiumRequired => 6,
+            Failure::Daemon { code, .. } => *code,
+        }
+    }
+
+    /// Sorts a Spotify error by the reason code in its body.
+    fn from_api(error: anyhow::Error) -> Self {
+        let text = error.to_string();
+        if text.contains("NO_ACTIVE_DEVICE") {
+            Failure::NoActiveDevice
+        } else if text.contains("PREMIUM_REQUIRED") {
+            Failure::PremiumRequired
+        } else if is_expired(&text) || text.contains("User not authenticated") {
+            Failure::NotAuthenticated(text)
+        } else {
+            Failure::Api(error)
+        }
+    }
+}
+
+impl fmt::Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Failure::Api(e) => write!(f, "{}", e),
+            Failure::Usage(message) => write!(f, "{}", message),
+            Failure::NotAuthenticated(message) => write!(f, "not signed in ({}); run: cargo run --bin authenticate", message),
+            Failure::NoActiveDevice => write!(f, "no active device; start Spotify somewhere first"),
+            Failure::NotFound(message) => write!(f, "{}", message),
+            Failure::PremiumRequired => write!(f, "Spotify Premium is required for playback control"),
+            Failure::Daemon { message, .. } => write!(f, "{}", message),
+        }
+    }
+}
+
+impl From<anyhow::Error> for Failure {
+    fn from(error: anyhow::Error) -> Self {
+        Failure::from_api(error)
+    }
+}
+
+impl From<io::Error> for Failure {
+    fn from(error: io::Error) -> Self {
+        Failure::Api(error.into())
+    }
+}
+
+fn is_expired(text: &str) -> bool {
+    text.contains("access token expired") || text.contains("Invalid access token")
+}
+
+pub(crate) fn usage(message: impl Into<String>) -> Failure {
+    Failure::Usage(message.into())
+}
+
+impl Subcommand {
+    /// Parses the arguments after the program name.
+    pub fn parse(args: &[String]) -> Result<Self, Failure> {
+        let (name, rest) = match args.split_first() {
+            Some((name, rest)) => (name.as_str(), rest),
+            None => return Ok(Subcommand::Help),
+        };
+        let joined = rest.join(" ");
+        let one = || match rest {
+            [arg] => Ok(arg.as_str()),
+            _ => Err(usage(format!("usage: spotycli {} <value>", name))),
+        };
+        let none = |command: Subcommand| {
+            if rest.is_empty() {
+                Ok(command)
+            } else {
+                Err(usage(format!("{} takes no arguments", name)))
+            }
+        };
+
+        match name {
+            "play" => Ok(Subcommand::Play(if joined.is_empty() { None } else { Some(joined) })),
+            "pause" => none(Subcommand::Pause),
+            "toggle" => none(Subcommand::Toggle),
+            "next" => none(Subcommand::Next),
+            "prev" | "previous" => none(Subcommand::Previous),
+            "vol" | "volume" => {
+                let volume = parse_adjust(one()?, |n| n.parse::<u8>().ok().filter(|v| *v <= 100))
+                    .ok_or_else(|| usage("volume must be 0-100, +N or -N"))?;
+                Ok(Subcommand::Volume(volume))
+            }
+            "seek" => {
+                let arg = one()?;
+                let seek = if arg.starts_with(['+', '-']) {
+                    parse_adjust(arg, |n| n.parse::<u64>().ok().and_then(|seconds| seconds.checked_mul(1000)))
+                } else {
+                    parse_position(arg).ok().map(Adjust::To)
+                };
+                seek.map(Subcommand::Seek).ok_or_else(|| usage("position must look like 1:23, 83, +10 or -10"))
+            }
+            "shuffle" => match rest {
+                [] => Ok(Subcommand::Shuffle(None)),
+                [mode] => match mode.as_str() {
+                    "on" => Ok(Subcommand::Shuffle(Some(ShuffleMode::On))),
+                    "off" => Ok(Subcommand::Shuffle(Some(ShuffleMode::Off))),
+                    "smart" => Ok(Subcommand::Shuffle(Some(ShuffleMode::SmartShuffle))),
+                    other => Err(usage(format!("unknown shuffle mode '{}' (on, off, smart)", other))),
+                },
+                _ => Err(usage("usage: spotycli shuffle [on|off|smart]")),
+            },
+            "repeat" => match one()? {
+                "off" => Ok(Subcommand::Repeat(RepeatMode::Off)),
+                "track" => Ok(Subcommand::Repeat(RepeatMode::Track)),
+                "context" | "playlist" | "album" => Ok(Subcommand::Repeat(RepeatMode::Context)),
+                other => Err(usage(format!("unknown repeat mode '{}' (off, track, context)", other))),
+            },
+            "like" => none(Subcommand::Like),
+            "unlike" => none(Subcommand::Unlike),
+            "queue" => {
+                let uri = SpotifyUri::parse(one()?).map_err(|e| usage(e.to_string()))?;
+                if uri.is_context() {
+                    return Err(usage("only tracks and episodes can be queued"));
+                }
+                Ok(Subcommand::Queue(uri))
+            }
+            "devices" => none(Subcommand::Devices),
+            "transfer" if !joined.is_empty() => Ok(Subcommand::Transfer(joined)),
+            "transfer" => Err(usage("usage: spotycli transfer <device name or id>")),
+            "search" => parse_search(rest),
+            "status" => parse_status(rest),
+            "daemon" => none(Subcommand::Daemon),
+            "help" | "--help" | "-h" => Ok(Subcommand::Help),
+            other => Err(usage(format!("unknown command '{}'; see spotycli help", other))),
+        }
+    }
+
+    /// The protocol request this runs as.
+    fn request(self) -> Request {
+        match self {
+            Subcommand::Play(target) => Request::Play { target },
+            Subcommand::Pause => Request::Pause,
+            Subcommand::Toggle => Request::Toggle,
+            Subcommand::Next => Request::Next,
+            Subcommand::Previous => Request::Previous,
+            Subcommand::Volume(change) => Request::Volume { change },
+            Subcommand::Seek(change) => Request::Seek { change },
+            Subcommand::Shuffle(mode) => Request::Shuffle { mode },
+            Subcommand::Repeat(mode) => Request::Repeat { mode },
+            Subcommand::Like => Request::Like,
+            Subcommand::Unlike => Request::Unlike,
+            Subcommand::Queue(uri) => Request::Queue { uri: uri.to_string() },
+            Subcommand::Devices => Request::Devices,
+            Subcommand::Transfer(device) => Request::Transfer { device },
+            Subcommand::Search { query, kind, limit } => Request::Search { query, kind, limit },
+            Subcommand::Status(_) => Request::Status,
+            Subcommand::Daemon | Subcommand::Help => unreachable!("handled before connecting"),
+        }
+    }
+}
+
+fn parse_adjust<T>(arg: &str, parse: impl Fn(&str) -> Option<T>) -> Option<Adjust<T>> {
+    if let Some(n) = arg.strip_prefix('+') {
+        parse(n).map(Adjust::Up)
+    } else if let Some(n) = arg.strip_prefix('-') {
+        parse(n).map(Adjust::Down)
+    } else {
+        parse(arg).map(Adjust::To)
+    }
+}
+
+fn parse_status(args: &[String]) -> Result<Subcommand, Failure> {
+    let mut options = StatusOptions::default();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--json" => options.format = None,
+            "--format" | "-f" => {
+                options.format = Some(args.next().cloned().ok_or_else(|| usage("--format takes a template, e.g. '{artist} - {title}'"))?);
+            }
+            "--follow" => options.follow = true,
+            other => return Err(usage(format!("unknown status option '{}'", other))),
+        }
+    }
+    Ok(Subcommand::Status(options))
+}
+
+fn parse_search(args: &[String]) -> Result<Subcommand, Failure> {
+    let mut kind = ItemKind::Track;
+    let mut limit = 10;
+    let mut words = Vec::new();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--type" | "-t" => {
+                kind = match args.next().map(String::as_str) {
+                    Some("track") => ItemKind::Track,
+                    Some("album") => ItemKind::Album,
+                    Some("artist") => ItemKind::Artist,
+                    Some("playlist") => ItemKind::Playlist,
+                    _ => return Err(usage("--type takes track, album, artist or playlist")),
+                }
+            }
+            "--limit" | "-n" => {
+                limit = args
+                    .next()
+                    .and_then(|n| n.parse().ok())
+                    .filter(|n| (1..=50).contains(n))
+                    .ok_or_else(|| usage("--limit takes a number from 1 to 50"))?;
+            }
+            word => words.push(word),
+        }
+    }
+    if words.is_empty() {
+        return Err(usage("usage: spotycli search [--type T] [--limit N] <query>"));
+    }
+    Ok(Subcommand::Search { query: words.join(" "), kind, limit })
+}
+
+/// Runs a subcommand and returns the process exit status. Output goes to
+/// stdout only for commands that list something; failures print one line
+/// to stderr.
+pub async fn run(client_id: String, client_secret: String, args: &[String]) -> i32 {
+    match run_subcommand(client_id, client_secret, args).await {
+        Ok(()) => 0,
+        Err(failure) => {
+            eprintln!("spotycli: {}", failure);
+            failure.exit_code()
+        }
+    }
+}
+
+async fn run_subcommand(client_id: String, client_secret: String, args: &[String]) -> Result<(), Failure> {
+    let command = Subcommand::parse(args)?;
+    match command {
+        Subcommand::Help => {
+            let _ = print_line(USAGE);
+            return Ok(());
+        }
+        Subcommand::Daemon => return daemon::run(Session::new(client_id, client_secret)).await,
+        _ => {}
+    }
+
+    let mut backend = Backend::connect(client_id, client_secret).await;
+    if let Subcommand::Status(options) = &command {
+        return status::run(&mut backend, options).await;
+    }
+    let devices = command == Subcommand::Devices;
+    let data = backend.request(command.request()).await?;
+    if devices {
+        print_devices(data);
+    } else if let Ok(hits) = serde_json::from_value::<Vec<SearchHit>>(data) {
+        for hit in hits {
+            let _ = print_line(&format!("{}\t{}", hit.uri, hit.name));
+        }
+    }
+    Ok(())
+}
+
+/// Where subcommands go: a running daemon, or an engine in this process
+/// that talks to Spotify itself.
+pub enum Backend {
+    Local(Box<Engine<Session>>),
+    Daemon(DaemonClient),
+}
+
+impl Backend {
+    pub async fn connect(client_id: String, client_secret: String) -> Self {
+        match DaemonClient::connect(&daemon::socket_path()).await {
+            Ok(client) => Backend::Daemon(client),
+            Err(_) => {
+                let mut session = Session::new(client_id, client_secret);
+                // Searching works without it; everything else reports it
+                let _ = session.sign_in();
+                Backend::Local(Box::new(Engine::new(session)))
+            }
+        }
+    }
+
+    pub async fn request(&mut self, request: Request) -> Result<Value, Failure> {
+        match self {
+            Backend::Local(engine) => engine.handle(request).await,
+            Backend::Daemon(client) => client.request(request).await,
+        }
+    }
+}
+
+/// One tab-separated line per device: id, name, type, active, volume.
+fn print_devices(data: Value) {
+    if let Ok(devices) = serde_json::from_value::<Vec<Device>>(data) {
+        for device in devices {
+            let _ = print_line(&format!(
+                "{}\t{}\t{}\t{}\t{}",
+                device.id.unwrap_or_default(),
+                device.name,
+                device.device_type,
+                if device.is_active { "active" } else { "inactive" },
+                device.volume_percent.map(|v| v.to_string()).unwrap_or_default(),
+            ));
+        }
+    }
+}
+
+/// Prints to stdout, quietly giving up when the reader has gone away
+/// (e.g. piped into `head`).
+fn print_line(line: &str) -> bool {
+    writeln!(io::stdout(), "{}", line).is_ok()
+}
@@ -0,0 +1,186 @@
+use std::fs;
+
+use super::{is_expired, usage, Failure, TOKENS_FILE};
+use crate::api::SpotifyClient;
+use crate::auth::{SpotifyAuth, UserTokens};
+use crate::daemon::{Control, Player, SearchHit};
+use crate::models::{CurrentPlayback, DeviceList, ShuffleMode, Track};
+use crate::uri::{ItemKind, SpotifyUri};
+
+/// A client signed in with the saved user token, which it can refresh.
+pub struct Session {
+    pub client: SpotifyClient,
+    auth: SpotifyAuth,
+    refresh_token: String,
+    signed_in: bool,
+    /// Searching goes through the app's own credentials, fetched on first use
+    /// so playback commands stay quick.
+    app_token: bool,
+}
+
+/// Makes a user request, refreshing the token once if it has expired.
+/// Saved tokens only last an hour.
+macro_rules! with_refresh {
+    ($session:ident, $call:expr) => {{
+        $session.require_user()?;
+        match $call.await.map_err(Failure::from) {
+            Err(Failure::NotAuthenticated(message)) if is_expired(&message) => {
+                $session.refresh().await?;
+                $call.await.map_err(Failure::from)
+            }
+            result => result,
+        }
+    }};
+}
+
+impl Session {
+    pub fn new(client_id: String, client_secret: String) -> Self {
+        Self {
+            client: SpotifyClient::new(client_id.clone(), client_secret.clone()),
+            auth: SpotifyAuth::new(client_id, client_secret),
+            refresh_token: String::new(),
+            signed_in: false,
+            app_token: false,
+        }
+    }
+
+    /// Loads the token `cargo run --bin authenticate` saved.
+    pub fn sign_in(&mut self) -> Result<(), Failure> {
+        let tokens = fs::read_to_string(TOKENS_FILE)
+            .ok()
+            .and_then(|data| serde_json::from_str::<UserTokens>(&data).ok())
+            .ok_or_else(|| Failure::NotAuthenticated("no saved token".to_string()))?;
+        self.refresh_token = tokens.refresh_token.clone();
+        self.client.set_user_tokens(tokens);
+        self.signed_in = true;
+        Ok(())
+    }
+
+    /// Swaps an expired access token for a new one and saves it.
+    pub async fn refresh(&mut self) -> Result<(), Failure> {
+        let tokens = self
+            .auth
+            .refresh_access_token(&self.refresh_token)
+            .await
+            .map_err(|e| Failure::NotAuthenticated(e.to_string()))?;
+        if let Ok(data) = serde_json::to_string_pretty(&tokens) {
+            let _ = fs::write(TOKENS_FILE, data);
+        }
+        self.refresh_token = tokens.refresh_token.clone();
+        self.client.set_user_tokens(tokens);
+        Ok(())
+    }
+
+    fn require_user(&self) -> Result<(), Failure> {
+        if self.signed_in {
+            Ok(())
+        } else {
+            Err(Failure::NotAuthenticated("no saved token".to_string()))
+        }
+    }
+}
+
+impl Player for Session {
+    async fn playback(&mut self) -> Result<Option<CurrentPlayback>, Failure> {
+        with_refresh!(self, self.client.get_playback_state())
+    }
+
+    async fn devices(&mut self) -> Result<DeviceList, Failure> {
+        with_refresh!(self, self.client.get_available_devices())
+    }
+
+    async fn search(&mut self, query: &str, kind: ItemKind, limit: u32) -> Result<Vec<SearchHit>, Failure> {
+        if !self.app_token {
+            self.client.authenticate().await?;
+            self.app_token = true;
+        }
+        search(&self.client, query, kind, limit).await
+    }
+
+    async fn library(&mut self, kind: ItemKind, limit: u32) -> Result<Vec<SearchHit>, Failure> {
+        let hits = match kind {
+            ItemKind::Track => {
+                let page = with_refresh!(self, self.client.get_liked_songs(limit, 0))?;
+                let items = page.get("items").and_then(|items| items.as_array()).cloned().unwrap_or_default();
+                items
+                    .into_iter()
+                    .filter_map(|item| serde_json::from_value::<Track>(item.get("track")?.clone()).ok())
+                    .filter_map(|track| track_hit(&track))
+                    .collect()
+            }
+            ItemKind::Album => with_refresh!(self, self.client.get_saved_albums(limit, 0))?
+                .items
+                .into_iter()
+                .filter_map(|saved| hit(saved.album.spotify_uri(), saved.album.name))
+                .collect(),
+            ItemKind::Playlist => with_refresh!(self, self.client.get_user_playlists(limit, 0))?
+                .items
+                .into_iter()
+                .filter_map(|playlist| hit(playlist.spotify_uri(), playlist.name))
+                .collect(),
+            other => return Err(usage(format!("there is no library of {}s", other.as_str()))),
+        };
+        Ok(hits)
+    }
+
+    async fn is_liked(&mut self, track_id: &str) -> Result<bool, Failure> {
+        with_refresh!(self, self.client.check_if_liked(track_id))
+    }
+
+    async fn queue(&mut self) -> Result<Vec<Track>, Failure> {
+        Ok(with_refresh!(self, self.client.get_queue())?.queue)
+    }
+
+    async fn control(&mut self, control: Control) -> Result<(), Failure> {
+        match control {
+            Control::Play(uri) if uri.is_context() => with_refresh!(self, self.client.play_context(&uri)),
+            Control::Play(uri) => with_refresh!(self, self.client.play_track(&uri)),
+            Control::Resume => with_refresh!(self, self.client.resume_playback()),
+            Control::Pause => with_refresh!(self, self.client.pause_playback()),
+            Control::Next => with_refresh!(self, self.client.next_track()),
+            Control::Previous => with_refresh!(self, self.client.previous_track()),
+            Control::Volume(volume) => with_refresh!(self, self.client.set_volume(volume)),
+            Control::Seek(position_ms) => with_refresh!(self, self.client.seek(position_ms)),
+            Control::Shuffle(ShuffleMode::Off) => with_refresh!(self, self.client.set_shuffle(false)),
+            Control::Shuffle(ShuffleMode::On) => with_refresh!(self, self.client.set_shuffle(true)),
+            Control::Shuffle(ShuffleMode::SmartShuffle) => with_refresh!(self, self.client.set_smart_shuffle(true)),
+            Control::Repeat(mode) => with_refresh!(self, self.client.set_repeat(mode.as_str())),
+            Control::Like(id) => with_refresh!(self, self.client.like_song(&id)),
+            Control::Unlike(id) => with_refresh!(self, self.client.unlike_song(&id)),
+            Control::Queue(uri) => with_refresh!(self, self.client.add_to_queue(&uri)),
+            Control::Transfer(id) => with_refresh!(self, self.client.transfer_playback(&id)),
+        }
+    }
+}
+
+/// Search results in Spotify's order.
+async fn search(client: &SpotifyClient, query: &str, kind: ItemKind, limit: u32) -> Result<Vec<SearchHit>, Failure> {
+    let response = client.search(query, kind.as_str(), limit).await?;
+    let results = match kind {
+        ItemKind::Track => response.tracks.map(|page| page.items.iter().filter_map(track_hit).collect()).unwrap_or_default(),
+        ItemKind::Album => response
+            .albums
+            .map(|page| page.items.into_iter().filter_map(|album| hit(album.spotify_uri(), album.name)).collect())
+            .unwrap_or_default(),
+        ItemKind::Artist => response
+            .artists
+            .map(|page| page.items.into_iter().filter_map(|artist| hit(artist.spotify_uri(), artist.name)).collect())
+            .unwrap_or_default(),
+        ItemKind::Playlist => response
+            .playlists
+            .map(|page| page.items.into_iter().filter_map(|playlist| hit(playlist.spotify_uri(), playlist.name)).collect())
+            .unwrap_or_default(),
+        ItemKind::Show | ItemKind::Episode => Vec::new(),
+    };
+    Ok(results)
+}
+
+fn hit(uri: anyhow::Result<SpotifyUri>, name: String) -> Option<SearchHit> {
+    Some(SearchHit { uri: uri.ok()?.to_string(), name })
+}
+
+/// Tracks are listed as `name\tartists`.
+fn track_hit(track: &Track) -> Option<SearchHit> {
+    let artists = track.artists.iter().map(|a| a.name.as_str()).collect::<Vec<_>>().join(", ");
+    hit(track.spotify_uri(), format!("{}\t{}", track.name, artists))
+}
@@ -0,0 +1,148 @@
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+use super::{print_line, Backend, Failure};
+use crate::daemon::Request;
+use crate::models::CurrentPlayback;
+
+/// How often `--follow` re-renders between polls, so progress keeps moving.
+const TICK: Duration = Duration::from_secs(1);
+
+/// `spotycli status` flags.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StatusOptions {
+    /// Template with `{field}` placeholders; JSON when `None`.
+    pub format: Option<String>,
+    pub follow: bool,
+}
+
+/// Playback as printed by `spotycli status`. Fields are `null` when nothing
+/// is playing.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct Status {
+    /// `playing`, `paused` or `stopped`.
+    pub state: String,
+    pub title: Option<String>,
+    pub artists: Vec<String>,
+    pub album: Option<String>,
+    pub uri: Option<String>,
+    pub progress_ms: Option<u64>,
+    pub duration_ms: Option<u64>,
+    pub device: Option<String>,
+    pub shuffle: Option<bool>,
+    pub repeat: Option<String>,
+    pub volume: Option<u8>,
+    /// `None` when it couldn't be checked, e.g. without the library scope.
+    pub liked: Option<bool>,
+}
+
+impl Status {
+    /// `position_ms` is the interpolated position, not the one in `playback`.
+    pub fn new(playback: Option<&CurrentPlayback>, position_ms: u64, liked: Option<bool>) -> Self {
+        let playback = match playback {
+            Some(playback) => playback,
+            None => return Self { state: "stopped".to_string(), ..Self::default() },
+        };
+        let track = playback.item.as_ref();
+        Self {
+            state: if playback.is_playing { "playing" } else { "paused" }.to_string(),
+            title: track.map(|t| t.name.clone()),
+            artists: track.map(|t| t.artists.iter().map(|a| a.name.clone()).collect()).unwrap_or_default(),
+            album: track.and_then(|t| t.album.as_ref()).map(|a| a.name.clone()),
+            uri: track.map(|t| t.uri.clone()),
+            progress_ms: Some(position_ms),
+            duration_ms: track.map(|t| t.duration_ms as u64),
+            device: Some(playback.device.name.clone()),
+            shuffle: Some(playback.shuffle_state),
+            repeat: Some(playback.repeat_state.clone()),
+            volume: playback.device.volume_percent,
+            liked,
+        }
+    }
+
+    /// Fills `{title}`, `{artist}`, `{album}`, `{progress}`, `{duration}`,
+    /// `{percent}`, `{state}`, `{device}`, `{shuffle}`, `{repeat}`,
+    /// `{volume}`, `{liked}`, `{uri}`, `{progress_ms}` and `{duration_ms}`.
+    /// Missing values become empty; unknown placeholders are left as typed.
+    pub fn render(&self, template: &str) -> String {
+        let on_off = |value: Option<bool>| value.map(|v| if v { "on" } else { "off" }).unwrap_or_default().to_string();
+        let time = |ms: Option<u64>| ms.map(|ms| format!("{}:{:02}", ms / 60000, ms / 1000 % 60)).unwrap_or_default();
+        let percent = match (self.progress_ms, self.duration_ms) {
+            (Some(progress), Some(duration)) if duration > 0 => (progress * 100 / duration).to_string(),
+            _ => String::new(),
+        };
+        let fields = [
+            ("title", self.title.clone().unwrap_or_default()),
+            ("artist", self.artists.join(", ")),
+            ("album", self.album.clone().unwrap_or_default()),
+            ("progress", time(self.progress_ms)),
+            ("duration", time(self.duration_ms)),
+            ("percent", percent),
+            ("state", self.state.clone()),
+            ("device", self.device.clone().unwrap_or_default()),
+            ("shuffle", on_off(self.shuffle)),
+            ("repeat", self.repeat.clone().unwrap_or_default()),
+            ("volume", self.volume.map(|v| v.to_string()).unwrap_or_default()),
+            ("liked", on_off(self.liked)),
+            ("uri", self.uri.clone().unwrap_or_default()),
+            ("progress_ms", self.progress_ms.map(|v| v.to_string()).unwrap_or_default()),
+            ("duration_ms", self.duration_ms.map(|v| v.to_string()).unwrap_or_default()),
+        ];
+        // One pass, so values that happen to look like placeholders stay as they are
+        let mut line = String::with_capacity(template.len());
+        let mut rest = template;
+        while let Some(start) = rest.find('{') {
+            line.push_str(&rest[..start]);
+            let after = &rest[start + 1..];
+            let field = after.find('}').and_then(|end| fields.iter().find(|(name, _)| *name == &after[..end]));
+            match field {
+                Some((name, value)) => {
+                    line.push_str(value);
+                    rest = &after[name.len() + 1..];
+                }
+                None => {
+                    line.push('{');
+                    rest = after;
+                }
+            }
+        }
+        line.push_str(rest);
+        line
+    }
+
+    fn line(&self, options: &StatusOptions) -> String {
+        match options.format {
+            Some(ref template) => self.render(template),
+            None => serde_json::to_string(self).unwrap_or_default(),
+        }
+    }
+}
+
+/// Prints the status once, or with `--follow` keeps printing a line each
+/// time it changes until stdout closes. The backend decides when to ask
+/// Spotify; between polls the position is interpolated.
+pub async fn run(backend: &mut Backend, options: &StatusOptions) -> Result<(), Failure> {
+    let mut last_line = fetch(backend).await?.line(options);
+    if !print_line(&last_line) || !options.follow {
+        return Ok(());
+    }
+    loop {
+        tokio::time::sleep(TICK).await;
+        // A failed poll keeps showing the last state; the bar shouldn't flicker
+        let Ok(status) = fetch(backend).await else {
+            continue;
+        };
+        let line = status.line(options);
+        if line != last_line {
+            if !print_line(&line) {
+                return Ok(());
+            }
+            last_line = line;
+        }
+    }
+}
+
+async fn fetch(backend: &mut Backend) -> Result<Status, Failure> {
+    let data = backend.request(Request::Status).await?;
+    Ok(serde_json::from_value(data).map_err(anyhow::Error::from)?)
+}
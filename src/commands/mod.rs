@@ -0,0 +1,199 @@
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::models::ShuffleMode;
+use crate::uri::SpotifyUri;
+
+/// Command names, in the order they are offered for completion.
+pub const COMMAND_NAMES: [&str; 16] = [
+    "vol", "seek", "play", "pause", "next", "prev", "device", "shuffle", "repeat", "playlist", "queue", "search",
+    "like", "party", "radio", "quit",
+];
+
+/// `:playlist` subcommands.
+pub const PLAYLIST_SUBCOMMANDS: [&str; 3] = ["new", "open", "add"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RepeatMode {
+    Off,
+    Track,
+    Context,
+}
+
+impl RepeatMode {
+    /// The value Spotify's repeat endpoint expects.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            RepeatMode::Off => "off",
+            RepeatMode::Track => "track",
+            RepeatMode::Context => "context",
+        }
+    }
+}
+
+/// Something the user asked for by name rather than with a key, e.g. from
+/// the `:` command line. Each one maps onto an action a key binding performs.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+    /// Volume in percent.
+    Volume(u8),
+    /// Position in milliseconds.
+    Seek(u64),
+    /// Resume, or play a Spotify URI or link.
+    Play(Option<SpotifyUri>),
+    Pause,
+    Next,
+    Previous,
+    /// Move playback to the device with this name.
+    Device(String),
+    Shuffle(ShuffleMode),
+    Repeat(RepeatMode),
+    PlaylistNew(String),
+    PlaylistOpen(String),
+    /// Add the marked or selected tracks to the named playlist.
+    PlaylistAdd(String),
+    /// Drop the tracks the app is holding; Spotify's own queue can't be cleared.
+    QueueClearLocal,
+    Search(String),
+    Like,
+    /// Start the guest page if needed and show the Party panel.
+    Party,
+    PartyStop,
+    /// Stop the radio station and drop the tracks it queued.
+    RadioStop,
+    Quit,
+}
+
+impl Command {
+    /// Parses a command line such as `vol 40` or `playlist new "Gym"`. A
+    /// leading `:` is allowed.
+    pub fn parse(line: &str) -> Result<Self> {
+        let line = line.trim().trim_start_matches(':');
+        let args = split_args(line)?;
+        let (name, rest) = match args.split_first() {
+            Some((name, rest)) => (name.as_str(), rest),
+            None => return Err(anyhow!("Type a command, e.g. vol 40")),
+        };
+        let joined = rest.join(" ");
+
+        match name {
+            "vol" | "volume" => {
+                let volume: u8 = one_arg(name, rest)?.parse().map_err(|_| anyhow!("Volume must be 0-100"))?;
+                if volume > 100 {
+                    return Err(anyhow!("Volume must be 0-100"));
+                }
+                Ok(Command::Volume(volume))
+            }
+            "seek" => parse_position(one_arg(name, rest)?).map(Command::Seek),
+            "play" if joined.is_empty() => Ok(Command::Play(None)),
+            "play" => SpotifyUri::parse(&joined).map(|uri| Command::Play(Some(uri))),
+            "pause" => Ok(Command::Pause),
+            "next" => Ok(Command::Next),
+            "prev" | "previous" => Ok(Command::Previous),
+            "device" if !joined.is_empty() => Ok(Command::Device(joined)),
+            "shuffle" => match one_arg(name, rest)? {
+                "on" => Ok(Command::Shuffle(ShuffleMode::On)),
+                "off" => Ok(Command::Shuffle(ShuffleMode::Off)),
+                "smart" => Ok(Command::Shuffle(ShuffleMode::SmartShuffle)),
+                other => Err(anyhow!("Unknown shuffle mode '{}' (on, off, smart)", other)),
+            },
+            "repeat" => match one_arg(name, rest)? {
+                "off" => Ok(Command::Repeat(RepeatMode::Off)),
+                "track" => Ok(Command::Repeat(RepeatMode::Track)),
+                "context" | "playlist" | "album" => Ok(Command::Repeat(RepeatMode::Context)),
+                other => Err(anyhow!("Unknown repeat mode '{}' (off, track, context)", other)),
+            },
+            "playlist" => {
+                let (sub, name) = match rest.split_first() {
+                    Some((sub, words)) if !words.is_empty() => (sub.as_str(), words.join(" ")),
+                    _ => return Err(anyhow!("Usage: playlist new|open|add <name>")),
+                };
+                match sub {
+                    "new" => Ok(Command::PlaylistNew(name)),
+                    "open" => Ok(Command::PlaylistOpen(name)),
+                    "add" => Ok(Command::PlaylistAdd(name)),
+                    other => Err(anyhow!("Unknown playlist command '{}' (new, open, add)", other)),
+                }
+            }
+            "queue" => match one_arg(name, rest)? {
+                "clear-local" => Ok(Command::QueueClearLocal),
+                other => Err(anyhow!("Unknown queue command '{}' (clear-local)", other)),
+            },
+            "search" if !joined.is_empty() => Ok(Command::Search(joined)),
+            "like" => Ok(Command::Like),
+            "party" if joined.is_empty() => Ok(Command::Party),
+            "party" => match one_arg(name, rest)? {
+                "stop" => Ok(Command::PartyStop),
+                other => Err(anyhow!("Unknown party command '{}' (stop)", other)),
+            },
+            "radio" => match one_arg(name, rest)? {
+                "stop" => Ok(Command::RadioStop),
+                other => Err(anyhow!("Unknown radio command '{}' (stop)", other)),
+            },
+            "q" | "quit" => Ok(Command::Quit),
+            "device" | "search" => Err(anyhow!("Usage: {} <name>", name)),
+            other => Err(anyhow!("Unknown command '{}'", other)),
+        }
+    }
+}
+
+fn one_arg<'a>(name: &str, args: &'a [String]) -> Result<&'a str> {
+    match args {
+        [arg] => Ok(arg.as_str()),
+        _ => Err(anyhow!("Usage: {} <value>", name)),
+    }
+}
+
+/// `83`, `1:23` or `1:02:03` to milliseconds.
+pub fn parse_position(text: &str) -> Result<u64> {
+    let invalid = || anyhow!("Position must look like 1:23 or 83");
+    let parts: Vec<&str> = text.split(':').collect();
+    if parts.len() > 3 {
+        return Err(invalid());
+    }
+    let mut seconds = 0u64;
+    for (i, part) in parts.iter().enumerate() {
+        let value: u64 = part.parse().map_err(|_| invalid())?;
+        // Only the leading part may run past 59
+        if i > 0 && value >= 60 {
+            return Err(invalid());
+        }
+        seconds = seconds.checked_mul(60).and_then(|s| s.checked_add(value)).ok_or_else(|| anyhow!("Position is too far"))?;
+    }
+    seconds.checked_mul(1000).ok_or_else(|| anyhow!("Position is too far"))
+}
+
+/// Splits on whitespace, keeping double-quoted words together.
+pub fn split_args(line: &str) -> Result<Vec<String>> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut quoted = false;
+    let mut in_word = false;
+
+    for c in line.chars() {
+        match c {
+            '"' => {
+                quoted = !quoted;
+                in_word = true;
+            }
+            c if c.is_whitespace() && !quoted => {
+                if in_word {
+                    args.push(std::mem::take(&mut current));
+                    in_word = false;
+                }
+            }
+            c => {
+                current.push(c);
+                in_word = true;
+            }
+        }
+    }
+    if quoted {
+        return Err(anyhow!("Missing closing quote"));
+    }
+    if in_word {
+        args.push(current);
+    }
+    Ok(args)
+}
@@ -0,0 +1,218 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+/// User settings loaded from `.spotify_config` (JSON) in the working directory.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Config {
+    /// Name of the theme to use: a built-in (`default`, `nord`, `gruvbox`, `mono`)
+    /// or one of the entries in `themes`.
+    pub theme: Option<String>,
+    /// User-defined color schemes, keyed by name.
+    pub themes: HashMap<String, ThemeConfig>,
+    /// `unicode` (default) or `ascii` for emoji-free, screen-reader friendly output.
+    pub render_mode: RenderMode,
+    /// Columns shown in track tables, left to right.
+    pub track_columns: Vec<TrackColumn>,
+    /// Where `spotycli daemon` serves the MPD protocol, e.g. `127.0.0.1:6600`.
+    /// Off when unset.
+    pub mpd_address: Option<String>,
+    /// Where `spotycli daemon` serves its HTTP API and event WebSocket, e.g.
+    /// `127.0.0.1:8765`. Off when unset; needs `http_token`.
+    pub http_address: Option<String>,
+    /// Bearer token HTTP clients must send.
+    pub http_token: Option<String>,
+    /// The guest page started with `:party`.
+    pub party: PartyConfig,
+    /// How radio picks tracks.
+    pub radio: RadioConfig,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            theme: None,
+            themes: HashMap::new(),
+            render_mode: RenderMode::default(),
+            track_columns: vec![
+                TrackColumn::Index,
+                TrackColumn::Title,
+                TrackColumn::Artists,
+                TrackColumn::Album,
+                TrackColumn::Duration,
+                TrackColumn::AddedAt,
+            ],
+            mpd_address: None,
+            http_address: None,
+            http_token: None,
+            party: PartyConfig::default(),
+            radio: RadioConfig::default(),
+        }
+    }
+}
+
+/// Party mode: guests on the LAN search and submit tracks from their phones.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct PartyConfig {
+    /// Where the guest page is served.
+    pub address: String,
+    /// Submissions each guest may make per `window_minutes`.
+    pub submissions_per_window: usize,
+    pub window_minutes: u64,
+    /// Hold submissions until the host approves them from the Party panel.
+    pub require_approval: bool,
+    /// Artist names (any case) guests can't submit.
+    pub blocked_artists: Vec<String>,
+    /// Track URIs guests can't submit.
+    pub blocked_tracks: Vec<String>,
+}
+
+impl Default for PartyConfig {
+    fn default() -> Self {
+        Self {
+            address: "0.0.0.0:8080".to_string(),
+            submissions_per_window: 3,
+            window_minutes: 10,
+            require_approval: true,
+            blocked_artists: Vec::new(),
+            blocked_tracks: Vec::new(),
+        }
+    }
+}
+
+/// Radio: tracks like a seed track, from the sources listed, first source
+/// first.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct RadioConfig {
+    pub sources: Vec<RadioSource>,
+    /// Picks before an artist can come up again.
+    pub artist_gap: usize,
+    /// How many of the latest plays radio won't pick.
+    pub avoid_recent: usize,
+    /// Radio adds more when fewer tracks than this are waiting.
+    pub keep_queued: usize,
+}
+
+impl Default for RadioConfig {
+    fn default() -> Self {
+        Self {
+            sources: vec![
+                RadioSource::RelatedArtists,
+                RadioSource::ArtistTopTracks,
+                RadioSource::AlbumNeighbors,
+                RadioSource::LikedSongs,
+                RadioSource::History,
+            ],
+            artist_gap: 3,
+            avoid_recent: 20,
+            keep_queued: 3,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RadioSource {
+    /// Top tracks of artists Spotify lists as related to the seed's.
+    RelatedArtists,
+    /// Top tracks of the seed's own artists.
+    ArtistTopTracks,
+    /// The seed's album, nearest tracks first.
+    AlbumNeighbors,
+    /// Liked songs by the seed's or related artists.
+    LikedSongs,
+    /// Older plays by the seed's or related artists.
+    History,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RenderMode {
+    #[default]
+    Unicode,
+    Ascii,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TrackColumn {
+    Index,
+    Title,
+    Artists,
+    Album,
+    Duration,
+    Popularity,
+    AddedAt,
+    AddedBy,
+}
+
+impl TrackColumn {
+    pub fn header(&self) -> &'static str {
+        match self {
+            TrackColumn::Index => "#",
+            TrackColumn::Title => "Title",
+            TrackColumn::Artists => "Artist",
+            TrackColumn::Album => "Album",
+            TrackColumn::Duration => "Time",
+            TrackColumn::Popularity => "Pop",
+            TrackColumn::AddedAt => "Added",
+            TrackColumn::AddedBy => "Added By",
+        }
+    }
+
+    /// Numeric columns are right-aligned.
+    pub fn is_numeric(&self) -> bool {
+        matches!(self, TrackColumn::Index | TrackColumn::Duration | TrackColumn::Popularity)
+    }
+}
+
+/// A user color scheme. Every role is optional and falls back to `base`
+/// (or the default theme). Colors accept names (`yellow`, `light-blue`),
+/// 256-color indexes (`208`) and hex values (`#ebcb8b`).
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct ThemeConfig {
+    pub base: Option<String>,
+    pub text: Option<String>,
+    pub muted: Option<String>,
+    pub border: Option<String>,
+    pub title: Option<String>,
+    pub highlight: Option<String>,
+    pub playing: Option<String>,
+    pub liked: Option<String>,
+    pub accent: Option<String>,
+    pub input: Option<String>,
+    pub success: Option<String>,
+    pub warning: Option<String>,
+    pub error: Option<String>,
+    pub progress: Option<String>,
+}
+
+impl Config {
+    const CONFIG_FILE: &'static str = ".spotify_config";
+
+    /// The saved config, or the defaults with the reason it couldn't be
+    /// used, so a typo doesn't go unnoticed.
+    pub fn load() -> (Self, Option<String>) {
+        if !Path::new(Self::CONFIG_FILE).exists() {
+            return (Self::default(), None);
+        }
+        let parsed = fs::read_to_string(Self::CONFIG_FILE)
+            .map_err(|e| e.to_string())
+            .and_then(|content| serde_json::from_str::<Config>(&content).map_err(|e| e.to_string()));
+        match parsed {
+            Ok(config) => (config, None),
+            Err(e) => (Self::default(), Some(format!("Ignoring {}, using defaults: {}", Self::CONFIG_FILE, e))),
+        }
+    }
+
+    pub fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let content = serde_json::to_string_pretty(self)?;
+        fs::write(Self::CONFIG_FILE, content)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,96 @@
+use anyhow::anyhow;
+use serde_json::Value;
+use std::collections::VecDeque;
+use std::io;
+use std::path::Path;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader, Lines};
+use tokio::net::unix::{OwnedReadHalf, OwnedWriteHalf};
+use tokio::net::UnixStream;
+
+use super::protocol::{Message, Request, Response};
+use crate::cli::Failure;
+use crate::events::PlaybackEvent;
+
+/// A connection to a running daemon.
+pub struct DaemonClient {
+    lines: Lines<BufReader<OwnedReadHalf>>,
+    writer: OwnedWriteHalf,
+    next_id: u64,
+    /// Events that arrived while waiting for a reply.
+    pending: VecDeque<PlaybackEvent>,
+}
+
+/// One line from the daemon.
+enum Incoming {
+    Reply(Response),
+    Event(PlaybackEvent),
+}
+
+impl DaemonClient {
+    /// Fails when nothing is listening, which callers take to mean "no daemon".
+    pub async fn connect(path: &Path) -> io::Result<Self> {
+        let (reader, writer) = UnixStream::connect(path).await?.into_split();
+        Ok(Self {
+            lines: BufReader::new(reader).lines(),
+            writer,
+            next_id: 1,
+            pending: VecDeque::new(),
+        })
+    }
+
+    /// Sends a request and waits for its reply. Failures come back with the
+    /// daemon's exit status.
+    pub async fn request(&mut self, request: Request) -> Result<Value, Failure> {
+        let id = self.next_id;
+        self.next_id += 1;
+        let mut line = serde_json::to_string(&Message { id: Some(id), request }).map_err(anyhow::Error::from)?;
+        line.push('\n');
+        self.writer.write_all(line.as_bytes()).await.map_err(lost)?;
+        loop {
+            match self.read().await? {
+                Incoming::Reply(response) if response.id == Some(id) => return response.into_result(),
+                Incoming::Reply(_) => {}
+                Incoming::Event(event) => self.pending.push_back(event),
+            }
+        }
+    }
+
+    /// Starts streaming playback events to `next_event`.
+    pub async fn subscribe(&mut self) -> Result<(), Failure> {
+        self.request(Request::Subscribe).await.map(|_| ())
+    }
+
+    /// The next playback event, or `None` once the daemon goes away.
+    pub async fn next_event(&mut self) -> Option<PlaybackEvent> {
+        if let Some(event) = self.pending.pop_front() {
+            return Some(event);
+        }
+        loop {
+            match self.read().await {
+                Ok(Incoming::Event(event)) => return Some(event),
+                Ok(Incoming::Reply(_)) => {}
+                Err(_) => return None,
+            }
+        }
+    }
+
+    async fn read(&mut self) -> Result<Incoming, Failure> {
+        let line = self
+            .lines
+            .next_line()
+            .await
+            .map_err(lost)?
+            .ok_or_else(|| lost("connection closed"))?;
+        let value: Value = serde_json::from_str(&line).map_err(anyhow::Error::from)?;
+        let incoming = if value.get("event").is_some() {
+            Incoming::Event(serde_json::from_value(value).map_err(anyhow::Error::from)?)
+        } else {
+            Incoming::Reply(serde_json::from_value(value).map_err(anyhow::Error::from)?)
+        };
+        Ok(incoming)
+    }
+}
+
+fn lost(error: impl std::fmt::Display) -> Failure {
+    Failure::Api(anyhow!("lost connection to the daemon: {}", error))
+}
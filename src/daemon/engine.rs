@@ -0,0 +1,337 @@
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::future::Future;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::{broadcast, Mutex};
+
+use super::protocol::{Adjust, Request, SearchHit};
+use crate::cli::{usage, Failure, Status};
+use crate::commands::RepeatMode;
+use crate::events::{PlaybackEvent, PlaybackEvents};
+use crate::models::{CurrentPlayback, DeviceList, ShuffleMode, Track};
+use crate::ui::PlaybackClock;
+use crate::uri::{ItemKind, SpotifyUri};
+
+/// After a playback command, poll again this soon so the change shows up.
+const SETTLE: Duration = Duration::from_millis(500);
+/// How long to wait before retrying a poll that failed.
+const RETRY: Duration = Duration::from_secs(10);
+
+/// A resolved playback command: relative changes and device names have
+/// already been turned into exact values.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Control {
+    Play(SpotifyUri),
+    Resume,
+    Pause,
+    Next,
+    Previous,
+    Volume(u8),
+    Seek(u64),
+    Shuffle(ShuffleMode),
+    Repeat(RepeatMode),
+    /// Track id.
+    Like(String),
+    Unlike(String),
+    Queue(SpotifyUri),
+    /// Device id.
+    Transfer(String),
+}
+
+/// What the engine needs from Spotify. `cli::Session` talks to the Web API;
+/// tests swap in a scripted player.
+pub trait Player: Send + 'static {
+    fn playback(&mut self) -> impl Future<Output = Result<Option<CurrentPlayback>, Failure>> + Send;
+    fn devices(&mut self) -> impl Future<Output = Result<DeviceList, Failure>> + Send;
+    fn search(&mut self, query: &str, kind: ItemKind, limit: u32) -> impl Future<Output = Result<Vec<SearchHit>, Failure>> + Send;
+    /// Saved tracks, albums or playlists, depending on `kind`.
+    fn library(&mut self, kind: ItemKind, limit: u32) -> impl Future<Output = Result<Vec<SearchHit>, Failure>> + Send;
+    fn is_liked(&mut self, track_id: &str) -> impl Future<Output = Result<bool, Failure>> + Send;
+    /// Tracks queued after the current one.
+    fn queue(&mut self) -> impl Future<Output = Result<Vec<Track>, Failure>> + Send;
+    fn control(&mut self, control: Control) -> impl Future<Output = Result<(), Failure>> + Send;
+}
+
+/// One engine shared by everything that serves it: the socket, the poll
+/// loop and, with the `mpris` feature, D-Bus.
+pub type SharedEngine<P> = Arc<Engine<P>>;
+
+/// Answers protocol requests from a cached playback state, polling Spotify
+/// on the same adaptive schedule as the TUI. The CLI runs one in-process when
+/// no daemon is listening; the daemon shares one between all connections.
+///
+/// The player and the cache are locked separately: a request waits on the
+/// player only for its own Spotify call, and the cache is only locked to
+/// read or update it, never across a call.
+pub struct Engine<P> {
+    player: Mutex<P>,
+    cache: std::sync::Mutex<Cache>,
+}
+
+/// What the last poll and the commands since then say about playback.
+struct Cache {
+    playback: Option<CurrentPlayback>,
+    clock: PlaybackClock,
+    events: PlaybackEvents,
+    /// Whether the track with this id is in Liked Songs; `None` when the
+    /// check failed.
+    liked: Option<(String, Option<bool>)>,
+    /// `None` until the first poll, so the first request always polls.
+    next_poll: Option<Instant>,
+}
+
+impl<P: Player> Engine<P> {
+    pub fn new(player: P) -> Self {
+        Self {
+            player: Mutex::new(player),
+            cache: std::sync::Mutex::new(Cache {
+                playback: None,
+                clock: PlaybackClock::default(),
+                events: PlaybackEvents::new(),
+                liked: None,
+                next_poll: None,
+            }),
+        }
+    }
+
+    fn cache(&self) -> std::sync::MutexGuard<'_, Cache> {
+        self.cache.lock().unwrap()
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<PlaybackEvent> {
+        self.cache().events.subscribe()
+    }
+
+    /// Time until the next scheduled poll; zero when one is due.
+    pub fn until_next_poll(&self) -> Duration {
+        self.cache().next_poll.map(|at| at.saturating_duration_since(Instant::now())).unwrap_or(Duration::ZERO)
+    }
+
+    /// Fetches playback, broadcasts what changed and schedules the next poll.
+    pub async fn poll(&self) -> Result<(), Failure> {
+        let result = self.player.lock().await.playback().await;
+        let mut cache = self.cache();
+        match result {
+            Ok(playback) => {
+                cache.events.update(playback.as_ref());
+                cache.clock.sync(playback.as_ref());
+                cache.playback = playback;
+                cache.next_poll = Some(Instant::now() + cache.clock.next_poll());
+                Ok(())
+            }
+            Err(failure) => {
+                cache.next_poll = Some(Instant::now() + RETRY);
+                Err(failure)
+            }
+        }
+    }
+
+    async fn poll_if_due(&self) -> Result<(), Failure> {
+        if self.until_next_poll().is_zero() {
+            self.poll().await
+        } else {
+            Ok(())
+        }
+    }
+
+    pub async fn handle(&self, request: Request) -> Result<Value, Failure> {
+        match request {
+            Request::Ping => Ok(json!("pong")),
+            Request::Playback => Ok(value(self.playback().await?)),
+            Request::Sync => {
+                self.poll().await?;
+                Ok(value(self.current()))
+            }
+            Request::Status => {
+                self.poll_if_due().await?;
+                let liked = self.liked().await;
+                let cache = self.cache();
+                Ok(value(Status::new(cache.playback.as_ref(), cache.clock.position_ms(), liked)))
+            }
+            Request::Upcoming => Ok(value(self.upcoming().await?)),
+            Request::Devices => Ok(value(self.player.lock().await.devices().await?.devices)),
+            Request::Search { query, kind, limit } => {
+                let hits = self.player.lock().await.search(&query, kind, limit).await?;
+                if hits.is_empty() {
+                    return Err(Failure::NotFound(format!("nothing found for '{}'", query)));
+                }
+                Ok(value(hits))
+            }
+            Request::Library { kind, limit } => Ok(value(self.player.lock().await.library(kind, limit).await?)),
+            Request::Subscribe => Err(usage("subscribe needs a daemon connection")),
+            request => {
+                let control = self.resolve(request).await?;
+                self.player.lock().await.control(control.clone()).await?;
+                self.apply(&control);
+                Ok(Value::Null)
+            }
+        }
+    }
+
+    /// Turns a playback request into the exact call to make, reading
+    /// relative changes off the cached state.
+    async fn resolve(&self, request: Request) -> Result<Control, Failure> {
+        let control = match request {
+            Request::Play { target: None } | Request::Resume => Control::Resume,
+            Request::Play { target: Some(target) } => match SpotifyUri::parse(&target) {
+                Ok(uri) => Control::Play(uri),
+                Err(_) => {
+                    let hit = self
+                        .player
+                        .lock()
+                        .await
+                        .search(&target, ItemKind::Track, 1)
+                        .await?
+                        .into_iter()
+                        .next()
+                        .ok_or_else(|| Failure::NotFound(format!("nothing found for '{}'", target)))?;
+                    Control::Play(SpotifyUri::parse(&hit.uri)?)
+                }
+            },
+            Request::Pause => Control::Pause,
+            Request::Toggle => {
+                self.poll_if_due().await?;
+                let playing = self.cache().playback.as_ref().is_some_and(|p| p.is_playing);
+                if playing {
+                    Control::Pause
+                } else {
+                    Control::Resume
+                }
+            }
+            Request::Next => Control::Next,
+            Request::Previous => Control::Previous,
+            Request::Volume { change } => Control::Volume(match change {
+                Adjust::To(volume) => volume.min(100),
+                Adjust::Up(step) => self.current_volume().await?.saturating_add(step).min(100),
+                Adjust::Down(step) => self.current_volume().await?.saturating_sub(step),
+            }),
+            Request::Seek { change } => Control::Seek(match change {
+                Adjust::To(position_ms) => position_ms,
+                Adjust::Up(ms) => {
+                    let position = self.current_position().await?.saturating_add(ms);
+                    match self.playing(|p| p.item.as_ref().map(|t| t.duration_ms as u64))? {
+                        Some(duration) if duration > 0 => position.min(duration),
+                        _ => position,
+                    }
+                }
+                Adjust::Down(ms) => self.current_position().await?.saturating_sub(ms),
+            }),
+            Request::Shuffle { mode: Some(mode) } => Control::Shuffle(mode),
+            Request::Shuffle { mode: None } => {
+                self.poll_if_due().await?;
+                Control::Shuffle(if self.playing(|p| p.shuffle_state)? { ShuffleMode::Off } else { ShuffleMode::On })
+            }
+            Request::Repeat { mode } => Control::Repeat(mode),
+            Request::Like | Request::Unlike => {
+                // Always ask: the cached track may have ended since the last poll
+                self.poll().await?;
+                let id = self
+                    .playing(|p| p.item.as_ref().map(|t| t.id.clone()))?
+                    .ok_or_else(|| Failure::NotFound("nothing is playing".to_string()))?;
+                if request == Request::Like {
+                    Control::Like(id)
+                } else {
+                    Control::Unlike(id)
+                }
+            }
+            Request::Queue { uri } => {
+                let uri = SpotifyUri::parse(&uri).map_err(|e| usage(e.to_string()))?;
+                if uri.is_context() {
+                    return Err(usage("only tracks and episodes can be queued"));
+                }
+                Control::Queue(uri)
+            }
+            Request::Transfer { device } => {
+                let devices = self.player.lock().await.devices().await?;
+                let found = devices.find(&device).ok_or_else(|| Failure::NotFound(format!("no device called {}", device)))?;
+                let id = found.id.clone().ok_or_else(|| Failure::Api(anyhow::anyhow!("{} can't be controlled remotely", found.name)))?;
+                Control::Transfer(id)
+            }
+            other => return Err(usage(format!("{:?} is not a playback command", other))),
+        };
+        Ok(control)
+    }
+
+    /// Applies a command that went through to the cached state, so requests
+    /// arriving before the next poll see it, and polls again soon.
+    fn apply(&self, control: &Control) {
+        let mut cache = self.cache();
+        match control {
+            Control::Pause | Control::Resume => cache.clock.set_playing(*control == Control::Resume),
+            Control::Seek(position_ms) => cache.clock.seek(*position_ms),
+            Control::Like(id) => cache.liked = Some((id.clone(), Some(true))),
+            Control::Unlike(id) => cache.liked = Some((id.clone(), Some(false))),
+            _ => {}
+        }
+        cache.next_poll = Some(Instant::now() + SETTLE);
+        let Some(playback) = cache.playback.as_mut() else {
+            return;
+        };
+        match control {
+            Control::Pause => playback.is_playing = false,
+            Control::Resume => playback.is_playing = true,
+            Control::Volume(volume) => playback.device.volume_percent = Some(*volume),
+            Control::Shuffle(mode) => playback.shuffle_state = *mode != ShuffleMode::Off,
+            Control::Repeat(mode) => playback.repeat_state = mode.as_str().to_string(),
+            _ => {}
+        }
+    }
+
+    /// Playback with the position advanced to now, polling first if due.
+    pub async fn playback(&self) -> Result<Option<CurrentPlayback>, Failure> {
+        self.poll_if_due().await?;
+        Ok(self.current())
+    }
+
+    /// Tracks queued after the current one, straight from Spotify.
+    pub async fn upcoming(&self) -> Result<Vec<Track>, Failure> {
+        self.player.lock().await.queue().await
+    }
+
+    /// The cached playback with its position advanced to now.
+    pub fn current(&self) -> Option<CurrentPlayback> {
+        let cache = self.cache();
+        let mut playback = cache.playback.clone()?;
+        playback.progress_ms = Some(cache.clock.position_ms());
+        Some(playback)
+    }
+
+    /// Reads the cached playback, or fails with `NoActiveDevice` when
+    /// nothing is playing anywhere.
+    fn playing<T>(&self, read: impl FnOnce(&CurrentPlayback) -> T) -> Result<T, Failure> {
+        self.cache().playback.as_ref().map(read).ok_or(Failure::NoActiveDevice)
+    }
+
+    async fn current_volume(&self) -> Result<u8, Failure> {
+        self.poll_if_due().await?;
+        self.playing(|p| p.device.volume_percent.unwrap_or(0))
+    }
+
+    async fn current_position(&self) -> Result<u64, Failure> {
+        self.poll_if_due().await?;
+        self.playing(|_| ())?;
+        Ok(self.cache().clock.position_ms())
+    }
+
+    /// Whether the current track is liked, asking Spotify once per track.
+    async fn liked(&self) -> Option<bool> {
+        let id = {
+            let cache = self.cache();
+            let id = cache.playback.as_ref()?.item.as_ref()?.id.clone();
+            if let Some((checked, liked)) = &cache.liked {
+                if *checked == id {
+                    return *liked;
+                }
+            }
+            id
+        };
+        let liked = self.player.lock().await.is_liked(&id).await.ok();
+        self.cache().liked = Some((id, liked));
+        liked
+    }
+}
+
+fn value(data: impl Serialize) -> Value {
+    serde_json::to_value(data).unwrap_or(Value::Null)
+}
@@ -0,0 +1,81 @@
+use anyhow::anyhow;
+use std::env;
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::net::{TcpListener, UnixListener};
+
+use crate::cli::{usage, Failure, Session};
+use crate::config::Config;
+
+mod client;
+mod engine;
+pub mod protocol;
+mod server;
+
+pub use client::DaemonClient;
+pub use engine::{Control, Engine, Player, SharedEngine};
+pub use protocol::{Adjust, Message, Request, Response, SearchHit};
+pub use server::serve;
+
+/// Where the daemon listens: `$SPOTYCLI_SOCKET`, else `spotycli.sock` in
+/// `$XDG_RUNTIME_DIR`, else a per-user file in the temp directory.
+pub fn socket_path() -> PathBuf {
+    if let Some(path) = env::var_os("SPOTYCLI_SOCKET") {
+        return PathBuf::from(path);
+    }
+    if let Some(dir) = env::var_os("XDG_RUNTIME_DIR") {
+        return PathBuf::from(dir).join("spotycli.sock");
+    }
+    let user = env::var("USER").unwrap_or_else(|_| "user".to_string());
+    env::temp_dir().join(format!("spotycli-{}.sock", user))
+}
+
+/// Runs `spotycli daemon` in the foreground until it's killed.
+pub async fn run(mut session: Session) -> Result<(), Failure> {
+    session.sign_in()?;
+    let path = socket_path();
+    if DaemonClient::connect(&path).await.is_ok() {
+        return Err(Failure::Api(anyhow!("a daemon is already listening on {}", path.display())));
+    }
+    // Left behind by a daemon that didn't shut down cleanly
+    let _ = fs::remove_file(&path);
+    let listener = UnixListener::bind(&path)?;
+    // Anyone who can connect can control playback
+    fs::set_permissions(&path, fs::Permissions::from_mode(0o600))?;
+    eprintln!("spotycli: daemon listening on {}", path.display());
+    let engine = Arc::new(Engine::new(session));
+    // Held for as long as the daemon runs; dropping it leaves the bus
+    #[cfg(feature = "mpris")]
+    let _mpris = match crate::mpris::start(engine.clone()).await {
+        Ok(connection) => Some(connection),
+        Err(e) => {
+            eprintln!("spotycli: MPRIS unavailable: {}", e);
+            None
+        }
+    };
+    let (config, config_error) = Config::load();
+    if let Some(error) = config_error {
+        eprintln!("spotycli: {}", error);
+    }
+    if let Some(address) = config.http_address {
+        let token = config.http_token.filter(|token| !token.is_empty()).ok_or_else(|| usage("set http_token to serve HTTP"))?;
+        let address = address.parse().map_err(|_| usage(format!("http_address {} isn't an address", address)))?;
+        let (address, server) = crate::http::serve(address, &token, engine.clone()).map_err(|e| Failure::Api(e.into()))?;
+        eprintln!("spotycli: HTTP API on http://{}", address);
+        tokio::spawn(server);
+    }
+    if let Some(address) = config.mpd_address {
+        let mpd = TcpListener::bind(&address).await?;
+        eprintln!("spotycli: MPD protocol on {}", address);
+        let engine = engine.clone();
+        tokio::spawn(async move {
+            if let Err(e) = crate::mpd::serve(mpd, engine).await {
+                eprintln!("spotycli: MPD server stopped: {}", e);
+            }
+        });
+    }
+    serve(listener, engine).await?;
+    Ok(())
+}
@@ -0,0 +1,145 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::cli::Failure;
+use crate::commands::RepeatMode;
+use crate::models::ShuffleMode;
+use crate::uri::ItemKind;
+
+/// An absolute value, or a change relative to the current one. On the wire:
+/// `{"to":40}`, `{"up":5}` or `{"down":5}`.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Adjust<T> {
+    To(T),
+    Up(T),
+    Down(T),
+}
+
+/// One request line, e.g. `{"id":1,"cmd":"volume","change":{"up":5}}`. The
+/// optional `id` is echoed back so clients can match replies to requests.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct Message {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<u64>,
+    #[serde(flatten)]
+    pub request: Request,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(tag = "cmd", rename_all = "snake_case")]
+pub enum Request {
+    Ping,
+    /// Playback as last polled, with the position advanced to now; `null`
+    /// when nothing is playing.
+    Playback,
+    /// Poll Spotify right away, then answer like `playback`.
+    Sync,
+    /// Playback in the shape `spotycli status` prints.
+    Status,
+    /// Tracks queued after the current one.
+    Upcoming,
+    Devices,
+    Search {
+        query: String,
+        #[serde(default = "default_search_kind")]
+        kind: ItemKind,
+        #[serde(default = "default_search_limit")]
+        limit: u32,
+    },
+    /// Saved items: liked songs (`track`), saved albums or the user's
+    /// playlists, most recently added first.
+    Library {
+        #[serde(default = "default_search_kind")]
+        kind: ItemKind,
+        #[serde(default = "default_library_limit")]
+        limit: u32,
+    },
+    /// Resume, or play a URI or link, or play the first track matching a query.
+    Play {
+        #[serde(default)]
+        target: Option<String>,
+    },
+    Pause,
+    Resume,
+    Toggle,
+    Next,
+    Previous,
+    Volume { change: Adjust<u8> },
+    /// Milliseconds, both for absolute positions and relative moves.
+    Seek { change: Adjust<u64> },
+    /// `null` toggles between on and off.
+    Shuffle {
+        #[serde(default)]
+        mode: Option<ShuffleMode>,
+    },
+    Repeat { mode: RepeatMode },
+    Like,
+    Unlike,
+    Queue { uri: String },
+    /// Moves playback to a device given by id or name.
+    Transfer { device: String },
+    /// Stream playback events on this connection from now on.
+    Subscribe,
+}
+
+fn default_search_kind() -> ItemKind {
+    ItemKind::Track
+}
+
+fn default_search_limit() -> u32 {
+    10
+}
+
+fn default_library_limit() -> u32 {
+    50
+}
+
+/// The reply to a request. Failures carry the exit status the CLI would use.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct Response {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<u64>,
+    pub ok: bool,
+    #[serde(default)]
+    pub data: Value,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<i32>,
+}
+
+impl Response {
+    pub fn success(id: Option<u64>, data: Value) -> Self {
+        Self { id, ok: true, data, error: None, code: None }
+    }
+
+    pub fn failure(id: Option<u64>, failure: &Failure) -> Self {
+        Self {
+            id,
+            ok: false,
+            data: Value::Null,
+            error: Some(failure.to_string()),
+            code: Some(failure.exit_code()),
+        }
+    }
+
+    pub fn into_result(self) -> Result<Value, Failure> {
+        if self.ok {
+            Ok(self.data)
+        } else {
+            Err(Failure::Daemon {
+                code: self.code.unwrap_or(1),
+                message: self.error.unwrap_or_else(|| "daemon request failed".to_string()),
+            })
+        }
+    }
+}
+
+/// A search result as returned by `search` and `library`.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct SearchHit {
+    pub uri: String,
+    /// Tracks read `name\tartists`.
+    pub name: String,
+}
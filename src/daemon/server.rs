@@ -0,0 +1,116 @@
+use serde::Serialize;
+use serde_json::Value;
+use std::io;
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::unix::OwnedWriteHalf;
+use tokio::net::{UnixListener, UnixStream};
+use tokio::sync::broadcast;
+
+use super::engine::{Player, SharedEngine};
+use super::protocol::{Message, Request, Response};
+use crate::cli::{usage, Failure};
+use crate::events::PlaybackEvent;
+
+/// How often the poll loop checks whether a poll is due. Commands can pull
+/// the next poll forward, so it can't just sleep until the scheduled one.
+const POLL_CHECK: Duration = Duration::from_millis(250);
+
+/// Serves the protocol on `listener` until accepting fails, and keeps the
+/// engine polling. Polling stops when this future is dropped; open
+/// connections finish on their own.
+pub async fn serve<P: Player>(listener: UnixListener, engine: SharedEngine<P>) -> io::Result<()> {
+    let poller = engine.clone();
+    let poll_loop = async move {
+        loop {
+            tokio::time::sleep(poller.until_next_poll().min(POLL_CHECK)).await;
+            if poller.until_next_poll().is_zero() {
+                // A failed poll is retried on the engine's schedule
+                let _ = poller.poll().await;
+            }
+        }
+    };
+    let accept_loop = async move {
+        loop {
+            let (stream, _) = listener.accept().await?;
+            tokio::spawn(connection(stream, engine.clone()));
+        }
+    };
+    tokio::select! {
+        () = poll_loop => Ok(()),
+        result = accept_loop => result,
+    }
+}
+
+/// Answers request lines in order and, once subscribed, interleaves event
+/// lines as they happen.
+async fn connection<P: Player>(stream: UnixStream, engine: SharedEngine<P>) {
+    let (reader, mut writer) = stream.into_split();
+    let mut lines = BufReader::new(reader).lines();
+    let mut events: Option<broadcast::Receiver<PlaybackEvent>> = None;
+    loop {
+        tokio::select! {
+            line = lines.next_line() => {
+                let line = match line {
+                    Ok(Some(line)) => line,
+                    _ => return,
+                };
+                if line.trim().is_empty() {
+                    continue;
+                }
+                let response = match parse(&line) {
+                    Err((id, failure)) => Response::failure(id, &failure),
+                    Ok(Message { id, request: Request::Subscribe }) => {
+                        events = Some(engine.subscribe());
+                        Response::success(id, Value::Null)
+                    }
+                    Ok(Message { id, request }) => match engine.handle(request).await {
+                        Ok(data) => Response::success(id, data),
+                        Err(failure) => Response::failure(id, &failure),
+                    },
+                };
+                if write_line(&mut writer, &response).await.is_err() {
+                    return;
+                }
+            }
+            Some(event) = next_event(&mut events) => {
+                if write_line(&mut writer, &event).await.is_err() {
+                    return;
+                }
+            }
+        }
+    }
+}
+
+/// Parses a request line, keeping its id for the error reply when the rest
+/// doesn't parse.
+fn parse(line: &str) -> Result<Message, (Option<u64>, Failure)> {
+    let value: Value = serde_json::from_str(line).map_err(|e| (None, usage(format!("invalid JSON: {}", e))))?;
+    let id = value.get("id").and_then(Value::as_u64);
+    serde_json::from_value(value).map_err(|e| (id, usage(format!("bad request: {}", e))))
+}
+
+/// The next event for a subscribed connection; never resolves otherwise.
+/// A subscriber that falls behind skips what it missed.
+async fn next_event(events: &mut Option<broadcast::Receiver<PlaybackEvent>>) -> Option<PlaybackEvent> {
+    let receiver = match events {
+        Some(receiver) => receiver,
+        None => return std::future::pending().await,
+    };
+    loop {
+        match receiver.recv().await {
+            Ok(event) => return Some(event),
+            Err(broadcast::error::RecvError::Lagged(_)) => continue,
+            Err(broadcast::error::RecvError::Closed) => {
+                *events = None;
+                return None;
+            }
+        }
+    }
+}
+
+async fn write_line(writer: &mut OwnedWriteHalf, message: &impl Serialize) -> io::Result<()> {
+    let mut line = serde_json::to_string(message).map_err(io::Error::other)?;
+    line.push('\n');
+    writer.write_all(line.as_bytes()).await
+}
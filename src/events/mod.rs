@@ -0,0 +1,126 @@
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+use tokio::sync::broadcast;
+
+use crate::models::{CurrentPlayback, Device, Track};
+
+/// How far the position may drift from where it should be before the jump
+/// counts as a seek rather than polling jitter.
+const SEEK_TOLERANCE_MS: u64 = 3000;
+
+/// Events kept for subscribers that fall behind.
+const CHANNEL_CAPACITY: usize = 64;
+
+/// A change between two consecutive playback snapshots. Serialized with an
+/// `event` tag, e.g. `{"event":"volume_changed","volume":40}`.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum PlaybackEvent {
+    /// A different track (or nothing) is playing now.
+    TrackChanged { previous: Option<Box<Track>>, current: Option<Box<Track>> },
+    Paused,
+    Resumed,
+    /// Position jumped within the same track.
+    Seeked { position_ms: u64 },
+    /// Playback moved to another device, or stopped everywhere (`None`).
+    DeviceChanged { device: Option<Device> },
+    VolumeChanged { volume: u8 },
+    ShuffleChanged { shuffle: bool },
+    RepeatChanged { repeat: String },
+    /// Playing from a different playlist, album, artist or none at all.
+    ContextChanged { uri: Option<String> },
+}
+
+/// Diffs each playback snapshot against the previous one and broadcasts
+/// what changed. Subscribe with `subscribe`; slow receivers miss the oldest
+/// events rather than holding up the app.
+pub struct PlaybackEvents {
+    sender: broadcast::Sender<PlaybackEvent>,
+    last: Option<(CurrentPlayback, Instant)>,
+}
+
+impl Default for PlaybackEvents {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PlaybackEvents {
+    pub fn new() -> Self {
+        let (sender, _) = broadcast::channel(CHANNEL_CAPACITY);
+        Self { sender, last: None }
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<PlaybackEvent> {
+        self.sender.subscribe()
+    }
+
+    /// Records a fresh snapshot, broadcasts the events it produces and
+    /// returns them for the caller to act on too.
+    pub fn update(&mut self, playback: Option<&CurrentPlayback>) -> Vec<PlaybackEvent> {
+        let now = Instant::now();
+        let events = match self.last.as_ref() {
+            Some((previous, at)) => diff(Some(previous), playback, now.duration_since(*at)),
+            None => diff(None, playback, Duration::ZERO),
+        };
+        for event in &events {
+            // No subscribers is fine
+            let _ = self.sender.send(event.clone());
+        }
+        self.last = playback.map(|p| (p.clone(), now));
+        events
+    }
+}
+
+/// Events that turn `previous` into `current`, taken `elapsed` apart.
+pub fn diff(previous: Option<&CurrentPlayback>, current: Option<&CurrentPlayback>, elapsed: Duration) -> Vec<PlaybackEvent> {
+    let mut events = Vec::new();
+    let track_id = |p: Option<&CurrentPlayback>| p.and_then(|p| p.item.as_ref()).map(|t| t.id.clone());
+    let context_uri = |p: Option<&CurrentPlayback>| p.and_then(|p| p.context.as_ref()).map(|c| c.uri.clone());
+
+    if track_id(previous) != track_id(current) {
+        events.push(PlaybackEvent::TrackChanged {
+            previous: previous.and_then(|p| p.item.clone()).map(Box::new),
+            current: current.and_then(|p| p.item.clone()).map(Box::new),
+        });
+    } else if let (Some(before), Some(after)) = (previous, current) {
+        // Where the old snapshot says playback should be by now
+        let expected = before.progress_ms.unwrap_or(0) + if before.is_playing { elapsed.as_millis() as u64 } else { 0 };
+        if let Some(position_ms) = after.progress_ms {
+            if position_ms.abs_diff(expected) > SEEK_TOLERANCE_MS {
+                events.push(PlaybackEvent::Seeked { position_ms });
+            }
+        }
+    }
+
+    let was_playing = previous.is_some_and(|p| p.is_playing);
+    let is_playing = current.is_some_and(|p| p.is_playing);
+    if was_playing && !is_playing {
+        events.push(PlaybackEvent::Paused);
+    } else if !was_playing && is_playing {
+        events.push(PlaybackEvent::Resumed);
+    }
+
+    let device_id = |p: Option<&CurrentPlayback>| p.map(|p| (p.device.id.clone(), p.device.name.clone()));
+    if device_id(previous) != device_id(current) {
+        events.push(PlaybackEvent::DeviceChanged { device: current.map(|p| p.device.clone()) });
+    }
+
+    if let (Some(before), Some(after)) = (previous, current) {
+        if let Some(volume) = after.device.volume_percent.filter(|v| Some(*v) != before.device.volume_percent) {
+            events.push(PlaybackEvent::VolumeChanged { volume });
+        }
+        if before.shuffle_state != after.shuffle_state {
+            events.push(PlaybackEvent::ShuffleChanged { shuffle: after.shuffle_state });
+        }
+        if before.repeat_state != after.repeat_state {
+            events.push(PlaybackEvent::RepeatChanged { repeat: after.repeat_state.clone() });
+        }
+    }
+
+    if context_uri(previous) != context_uri(current) {
+        events.push(PlaybackEvent::ContextChanged { uri: context_uri(current) });
+    }
+
+    events
+}
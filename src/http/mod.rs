@@ -0,0 +1,218 @@
+use futures_util::{SinkExt, StreamExt};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::future::Future;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tokio::sync::broadcast;
+use warp::http::header::{CONTENT_LENGTH, TRANSFER_ENCODING};
+use warp::http::{HeaderMap, Method, StatusCode};
+use warp::hyper::body::Bytes;
+use warp::ws::{Message, WebSocket, Ws};
+use warp::{Filter, Rejection, Reply};
+
+use crate::cli::{usage, Failure};
+use crate::daemon::{Player, Request, Response, SharedEngine};
+use crate::uri::ItemKind;
+
+/// Request bodies are a few fields of JSON; anything bigger is turned away.
+const BODY_LIMIT: u64 = 64 * 1024;
+
+/// Starts serving the API on `address`, returning the address actually
+/// bound and the server to run.
+pub fn serve<P: Player>(
+    address: SocketAddr,
+    token: &str,
+    engine: SharedEngine<P>,
+) -> Result<(SocketAddr, impl Future<Output = ()>), warp::Error> {
+    warp::serve(routes(token, engine)).try_bind_ephemeral(address)
+}
+
+/// Every endpoint, behind the token check:
+///
+/// - `GET /api/playback`, `/api/status`, `/api/devices` and `/api/queue`
+/// - `GET /api/search?query=…&kind=track&limit=10`
+/// - `GET /api/library?kind=track&limit=50`
+/// - `POST /api/<cmd>` for any daemon request, its fields as a JSON body,
+///   e.g. `POST /api/volume` with `{"change":{"up":5}}`
+/// - `GET /api/events`, a WebSocket streaming playback events
+///
+/// Replies are daemon responses (`{"ok":true,"data":…}`) with a matching
+/// HTTP status.
+pub fn routes<P: Player>(token: &str, engine: SharedEngine<P>) -> impl Filter<Extract = (impl Reply,), Error = Infallible> + Clone {
+    let engine = warp::any().map(move || engine.clone());
+    let api = warp::path("api").and(authorized(token));
+
+    let requests = warp::path::param::<String>()
+        .and(warp::path::end())
+        .and(warp::method())
+        .and(warp::query::<HashMap<String, String>>())
+        .and(body())
+        .map(|name: String, method: Method, query: HashMap<String, String>, body: Bytes| request(&name, &method, &query, &body))
+        .and(engine.clone())
+        .then(respond);
+
+    let events = warp::path!("events")
+        .and(warp::ws())
+        .and(engine)
+        .map(|ws: Ws, engine: SharedEngine<P>| ws.on_upgrade(move |socket| stream_events(socket, engine)));
+
+    api.and(events.map(Reply::into_response).or(requests.map(Reply::into_response)).unify()).recover(rejected)
+}
+
+#[derive(Debug)]
+struct Unauthorized;
+
+impl warp::reject::Reject for Unauthorized {}
+
+/// A body sent without saying how long it is.
+#[derive(Debug)]
+struct UnsizedBody;
+
+impl warp::reject::Reject for UnsizedBody {}
+
+/// The body, up to `BODY_LIMIT`. GETs and bare POSTs send no length and
+/// get an empty one.
+fn body() -> impl Filter<Extract = (Bytes,), Error = Rejection> + Clone {
+    let sized = warp::body::content_length_limit(BODY_LIMIT).and(warp::body::bytes());
+    let empty = warp::header::headers_cloned().and_then(|headers: HeaderMap| async move {
+        if headers.contains_key(CONTENT_LENGTH) || headers.contains_key(TRANSFER_ENCODING) {
+            Err(warp::reject::custom(UnsizedBody))
+        } else {
+            Ok(Bytes::new())
+        }
+    });
+    sized.or(empty).unify()
+}
+
+/// Passes requests carrying the token as `Authorization: Bearer <token>` or,
+/// for browsers opening a WebSocket, `?token=<token>`.
+fn authorized(token: &str) -> impl Filter<Extract = (), Error = Rejection> + Clone {
+    let token: Arc<str> = token.into();
+    warp::header::optional::<String>("authorization")
+        .and(warp::query::<Map<String, Value>>())
+        .and_then(move |header: Option<String>, query: Map<String, Value>| {
+            let token = token.clone();
+            async move {
+                let header = header.as_deref().and_then(|h| h.strip_prefix("Bearer "));
+                let query = query.get("token").and_then(Value::as_str);
+                match header.or(query) {
+                    Some(given) if same(given.as_bytes(), token.as_bytes()) => Ok(()),
+                    _ => Err(warp::reject::custom(Unauthorized)),
+                }
+            }
+        })
+        .untuple_one()
+}
+
+/// Compares in constant time, so the token can't be guessed byte by byte.
+fn same(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+
+/// The daemon request for `method /api/name`.
+fn request(name: &str, method: &Method, query: &HashMap<String, String>, body: &[u8]) -> Result<Request, Failure> {
+    let kind = || match query.get("kind") {
+        Some(kind) => serde_json::from_value(Value::String(kind.clone())).map_err(|_| usage(format!("unknown kind {}", kind))),
+        None => Ok(ItemKind::Track),
+    };
+    let limit = |default| match query.get("limit") {
+        Some(limit) => limit.parse().map_err(|_| usage(format!("limit {} isn't a number", limit))),
+        None => Ok(default),
+    };
+    match (method, name) {
+        (&Method::POST, cmd) => command(cmd, body),
+        (&Method::GET, "playback") => Ok(Request::Playback),
+        (&Method::GET, "status") => Ok(Request::Status),
+        (&Method::GET, "devices") => Ok(Request::Devices),
+        (&Method::GET, "queue") => Ok(Request::Upcoming),
+        (&Method::GET, "search") => {
+            let query = query.get("query").cloned().ok_or_else(|| usage("search needs a query"))?;
+            Ok(Request::Search { query, kind: kind()?, limit: limit(10)? })
+        }
+        (&Method::GET, "library") => Ok(Request::Library { kind: kind()?, limit: limit(50)? }),
+        (&Method::GET, name) => Err(Failure::NotFound(format!("no endpoint /api/{}", name))),
+        (method, name) => Err(usage(format!("{} /api/{} isn't supported", method, name))),
+    }
+}
+
+/// A daemon request from `POST /api/<cmd>` and its (possibly empty) body.
+fn command(cmd: &str, body: &[u8]) -> Result<Request, Failure> {
+    let mut fields: Map<String, Value> = if body.iter().all(u8::is_ascii_whitespace) {
+        Map::new()
+    } else {
+        serde_json::from_slice(body).map_err(|e| usage(format!("invalid JSON: {}", e)))?
+    };
+    fields.insert("cmd".to_string(), Value::String(cmd.to_string()));
+    serde_json::from_value(Value::Object(fields)).map_err(|e| usage(format!("bad request: {}", e)))
+}
+
+async fn respond<P: Player>(request: Result<Request, Failure>, engine: SharedEngine<P>) -> warp::reply::WithStatus<warp::reply::Json> {
+    let result = match request {
+        Ok(request) => engine.handle(request).await,
+        Err(failure) => Err(failure),
+    };
+    match result {
+        Ok(data) => warp::reply::with_status(warp::reply::json(&Response::success(None, data)), StatusCode::OK),
+        Err(failure) => warp::reply::with_status(warp::reply::json(&Response::failure(None, &failure)), status(&failure)),
+    }
+}
+
+fn status(failure: &Failure) -> StatusCode {
+    match failure {
+        Failure::Usage(_) => StatusCode::BAD_REQUEST,
+        Failure::NotFound(_) => StatusCode::NOT_FOUND,
+        Failure::NoActiveDevice => StatusCode::CONFLICT,
+        Failure::PremiumRequired => StatusCode::FORBIDDEN,
+        // The daemon's own sign-in, not the caller's token
+        Failure::NotAuthenticated(_) => StatusCode::SERVICE_UNAVAILABLE,
+        Failure::Api(_) | Failure::Daemon { .. } => StatusCode::BAD_GATEWAY,
+    }
+}
+
+/// Failures that never reached the engine, in the same shape as the rest.
+async fn rejected(rejection: Rejection) -> Result<warp::reply::WithStatus<warp::reply::Json>, Infallible> {
+    let (status, message) = if rejection.find::<Unauthorized>().is_some() {
+        (StatusCode::UNAUTHORIZED, "missing or wrong token".to_string())
+    } else if rejection.is_not_found() {
+        (StatusCode::NOT_FOUND, "no such endpoint".to_string())
+    } else if rejection.find::<warp::reject::MethodNotAllowed>().is_some() {
+        (StatusCode::METHOD_NOT_ALLOWED, "method not allowed".to_string())
+    } else if rejection.find::<warp::reject::PayloadTooLarge>().is_some() {
+        (StatusCode::PAYLOAD_TOO_LARGE, format!("bodies are limited to {} bytes", BODY_LIMIT))
+    } else if rejection.find::<UnsizedBody>().is_some() {
+        (StatusCode::LENGTH_REQUIRED, "send a Content-Length".to_string())
+    } else {
+        (StatusCode::BAD_REQUEST, format!("{:?}", rejection))
+    };
+    let reply = Response { id: None, ok: false, data: Value::Null, error: Some(message), code: None };
+    Ok(warp::reply::with_status(warp::reply::json(&reply), status))
+}
+
+/// Sends each playback event as a JSON text message until the client goes
+/// away. Messages from the client are ignored.
+async fn stream_events<P: Player>(socket: WebSocket, engine: SharedEngine<P>) {
+    let mut events = engine.subscribe();
+    let (mut sender, mut receiver) = socket.split();
+    loop {
+        tokio::select! {
+            event = events.recv() => {
+                let event = match event {
+                    Ok(event) => event,
+                    // A slow client skips what it missed
+                    Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                    Err(broadcast::error::RecvError::Closed) => return,
+                };
+                let Ok(text) = serde_json::to_string(&event) else { continue };
+                if sender.send(Message::text(text)).await.is_err() {
+                    return;
+                }
+            }
+            message = receiver.next() => match message {
+                Some(Ok(message)) if !message.is_close() => {}
+                _ => return,
+            },
+        }
+    }
+}
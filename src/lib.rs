@@ -1,4 +1,16 @@
 pub mod api;
 pub mod auth;
+pub mod cli;
+pub mod commands;
+pub mod config;
+pub mod daemon;
+pub mod events;
+pub mod http;
 pub mod models;
-pub mod ui;
\ No newline at end of file
+pub mod mpd;
+pub mod party;
+pub mod radio;
+#[cfg(feature = "mpris")]
+pub mod mpris;
+pub mod ui;
+pub mod uri;
\ No newline at end of file
@@ -1,14 +1,25 @@
 pub mod api;
 pub mod auth;
+pub mod cli;
+pub mod commands;
+pub mod config;
+pub mod daemon;
+pub mod events;
+pub mod http;
 pub mod models;
+pub mod mpd;
+pub mod party;
+pub mod radio;
+#[cfg(feature = "mpris")]
+pub mod mpris;
 pub mod ui;
+pub mod uri;
 
 use anyhow::Result;
 use dotenv::dotenv;
 use std::env;
 use std::fs;
 use std::io;
-use tokio;
 
 use api::SpotifyClient;
 use auth::{SpotifyAuth, UserTokens};
@@ -25,6 +36,12 @@ async fn main() -> Result<()> {
     let client_secret = env::var("SPOTIFY_CLIENT_SECRET")
         .unwrap_or_else(|_| "your_client_secret_here".to_string());
 
+    // Any arguments mean a one-shot command for scripts and hotkeys, not the TUI
+    let args: Vec<String> = env::args().skip(1).collect();
+    if !args.is_empty() {
+        std::process::exit(cli::run(client_id, client_secret, &args).await);
+    }
+
     // Initialize Spotify client for basic API access
     let mut spotify_client = SpotifyClient::new(client_id.clone(), client_secret.clone());
 
@@ -182,6 +199,11 @@ async fn main() -> Result<()> {
     let auth_client = SpotifyAuth::new(client_id, client_secret);
     app.set_auth_client(auth_client);
 
+    // Read playback from a running daemon rather than polling Spotify too
+    if let Ok(daemon) = daemon::DaemonClient::connect(&daemon::socket_path()).await {
+        app.set_daemon(daemon);
+    }
+
     // Set authentication status if tokens were loaded
     app.state.user_authenticated = user_authenticated;
 
@@ -2,6 +2,8 @@ use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::Path;
 
+use crate::uri::{ItemKind, SpotifyUri};
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Track {
     pub id: String,
@@ -10,8 +12,16 @@ pub struct Track {
     pub artists: Vec<Artist>,
     pub album: Option<Album>,
     pub duration_ms: u32,
+    /// Missing on the simplified tracks returned for album listings.
+    #[serde(default)]
     pub popularity: u8,
     pub preview_url: Option<String>,
+    /// When the track was added to the playlist or library it was loaded from.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub added_at: Option<String>,
+    /// Who added the track, for playlist tracks.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub added_by: Option<String>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -30,6 +40,8 @@ pub struct Album {
     pub release_date: Option<String>,
     pub total_tracks: u32,
     pub images: Option<Vec<Image>>,
+    #[serde(default)]
+    pub uri: Option<String>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -53,6 +65,93 @@ pub struct PlaylistTracks {
 pub struct PlaylistTrack {
     pub track: Option<Track>,
     pub added_at: Option<String>,
+    #[serde(default)]
+    pub added_by: Option<User>,
+}
+
+impl Track {
+    /// Fails for local files, which have no Spotify URI.
+    pub fn spotify_uri(&self) -> anyhow::Result<SpotifyUri> {
+        SpotifyUri::parse_as(ItemKind::Track, &self.uri)
+    }
+}
+
+impl Album {
+    pub fn spotify_uri(&self) -> anyhow::Result<SpotifyUri> {
+        SpotifyUri::parse_as(ItemKind::Album, self.uri.as_deref().unwrap_or(&self.id))
+    }
+}
+
+impl Artist {
+    pub fn spotify_uri(&self) -> anyhow::Result<SpotifyUri> {
+        SpotifyUri::parse_as(ItemKind::Artist, &self.id)
+    }
+}
+
+impl Playlist {
+    pub fn spotify_uri(&self) -> anyhow::Result<SpotifyUri> {
+        SpotifyUri::parse_as(ItemKind::Playlist, self.uri.as_deref().unwrap_or(&self.id))
+    }
+}
+
+impl PlaylistTrack {
+    /// The track with `added_at`/`added_by` copied onto it, if it is still available.
+    pub fn into_track(self) -> Option<Track> {
+        let added_at = self.added_at;
+        let added_by = self.added_by.map(|user| user.display_name.unwrap_or(user.id));
+        self.track.map(|mut track| {
+            track.added_at = added_at;
+            track.added_by = added_by;
+            track
+        })
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SavedTrack {
+    pub track: Track,
+    pub added_at: Option<String>,
+}
+
+impl SavedTrack {
+    pub fn into_track(self) -> Track {
+        let mut track = self.track;
+        track.added_at = self.added_at;
+        track
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SavedAlbum {
+    pub album: Album,
+    pub added_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SavedAlbumsResponse {
+    pub items: Vec<SavedAlbum>,
+    pub total: u32,
+    pub next: Option<String>,
+}
+
+/// `me/following?type=artist` wraps its page in an `artists` object.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct FollowedArtistsResponse {
+    pub artists: FollowedArtists,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct FollowedArtists {
+    pub items: Vec<Artist>,
+    pub next: Option<String>,
+    pub cursors: Option<Cursors>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct AlbumTracksResponse {
+    pub items: Vec<Track>,
+    pub total: u32,
+    pub next: Option<String>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -61,6 +160,15 @@ pub struct User {
     pub display_name: Option<String>,
 }
 
+/// The signed-in account, from `GET /me`.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct UserProfile {
+    pub id: String,
+    pub display_name: Option<String>,
+    /// `premium`, `free` or `open`.
+    pub product: Option<String>,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Image {
     pub url: String,
@@ -144,6 +252,19 @@ pub struct DeviceList {
     pub devices: Vec<Device>,
 }
 
+impl DeviceList {
+    /// The device with this ID, or else the one called `name` ignoring case:
+    /// an exact match first, then a prefix.
+    pub fn find(&self, name: &str) -> Option<&Device> {
+        let wanted = name.to_lowercase();
+        self.devices
+            .iter()
+            .find(|d| d.id.as_deref() == Some(name))
+            .or_else(|| self.devices.iter().find(|d| d.name.to_lowercase() == wanted))
+            .or_else(|| self.devices.iter().find(|d| d.name.to_lowercase().starts_with(&wanted)))
+    }
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct RecentlyPlayedResponse {
     pub items: Vec<PlayHistoryItem>,
@@ -177,6 +298,16 @@ pub struct PlaylistsResponse {
     pub previous: Option<String>,
 }
 
+#[derive(Debug, Clone, Deserialize)]
+pub struct RelatedArtistsResponse {
+    pub artists: Vec<Artist>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct TopTracksResponse {
+    pub tracks: Vec<Track>,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct QueueResponse {
     pub currently_playing: Option<Track>,
@@ -194,6 +325,12 @@ pub struct RecentlyPlayedStorage {
     pub tracks: Vec<RecentlyPlayedTrack>,
 }
 
+impl Default for RecentlyPlayedStorage {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl RecentlyPlayedStorage {
     const MAX_TRACKS: usize = 30;
     const STORAGE_FILE: &'static str = ".spotify_recently_played";
@@ -254,7 +391,8 @@ impl RecentlyPlayedStorage {
     }
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
 pub enum ShuffleMode {
     Off,
     On,
@@ -272,6 +410,7 @@ pub struct AppState {
     pub is_playing: bool,
     pub current_playback: Option<CurrentPlayback>,
     pub user_authenticated: bool,
+    pub user_profile: Option<UserProfile>,
     pub auth_message: String,
     #[allow(dead_code)]
     pub volume: u8,
@@ -281,14 +420,18 @@ pub struct AppState {
     pub selected_playlist_tracks: Vec<Track>,
     pub liked_songs: Vec<Track>,
     pub user_albums: Vec<Album>,
+    pub selected_album: Option<Album>,
+    pub selected_album_tracks: Vec<Track>,
     pub user_artists: Vec<Artist>,
+    pub selected_artist: Option<Artist>,
+    pub selected_artist_albums: Vec<Album>,
     pub recently_played: Vec<Track>,
     pub recently_played_storage: RecentlyPlayedStorage,
     pub queue: Vec<Track>,
     pub error_logs: Vec<String>,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum ViewType {
     Search,
     LikedSongs,
@@ -296,10 +439,14 @@ pub enum ViewType {
     PlaylistTracks,
     Queue,
     Albums,
+    AlbumTracks,
     Artists,
+    ArtistAlbums,
     Errors,
     #[allow(dead_code)]
     Player,
+    /// Guest submissions while party mode is on.
+    Party,
 }
 
 impl Default for AppState {
@@ -320,6 +467,7 @@ impl Default for AppState {
             is_playing: false,
             current_playback: None,
             user_authenticated: false,
+            user_profile: None,
             auth_message: String::new(),
             volume: 80,
             shuffle_mode: ShuffleMode::Off,
@@ -328,7 +476,11 @@ impl Default for AppState {
             selected_playlist_tracks: Vec::new(),
             liked_songs: Vec::new(),
             user_albums: Vec::new(),
+            selected_album: None,
+            selected_album_tracks: Vec::new(),
             user_artists: Vec::new(),
+            selected_artist: None,
+            selected_artist_albums: Vec::new(),
             recently_played,
             recently_played_storage: storage,
             queue: Vec::new(),
@@ -353,6 +505,8 @@ fn create_sample_recent_tracks() -> Vec<Track> {
             duration_ms: 387000,
             popularity: 95,
             preview_url: None,
+            added_at: None,
+            added_by: None,
         },
         Track {
             id: "recent2".to_string(),
@@ -368,6 +522,8 @@ fn create_sample_recent_tracks() -> Vec<Track> {
             duration_ms: 331000,
             popularity: 98,
             preview_url: None,
+            added_at: None,
+            added_by: None,
         },
         Track {
             id: "recent3".to_string(),
@@ -383,6 +539,8 @@ fn create_sample_recent_tracks() -> Vec<Track> {
             duration_ms: 515000,
             popularity: 92,
             preview_url: None,
+            added_at: None,
+            added_by: None,
         },
         Track {
             id: "recent4".to_string(),
@@ -398,6 +556,8 @@ fn create_sample_recent_tracks() -> Vec<Track> {
             duration_ms: 446000,
             popularity: 90,
             preview_url: None,
+            added_at: None,
+            added_by: None,
         },
         Track {
             id: "recent5".to_string(),
@@ -413,6 +573,8 @@ fn create_sample_recent_tracks() -> Vec<Track> {
             duration_ms: 309000,
             popularity: 88,
             preview_url: None,
+            added_at: None,
+            added_by: None,
         },
     ]
 }
\ No newline at end of file
@@ -0,0 +1,103 @@
+use std::str::FromStr;
+
+use crate::cli::Failure;
+
+// MPD's error codes, sent as `ACK [code@index] {command} message`
+pub const ACK_ARG: u32 = 2;
+pub const ACK_PERMISSION: u32 = 4;
+pub const ACK_UNKNOWN: u32 = 5;
+pub const ACK_NO_EXIST: u32 = 50;
+pub const ACK_SYSTEM: u32 = 52;
+pub const ACK_PLAYER_SYNC: u32 = 55;
+
+/// A failed command.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Ack {
+    pub code: u32,
+    pub message: String,
+}
+
+impl Ack {
+    pub fn new(code: u32, message: impl Into<String>) -> Self {
+        Self { code, message: message.into() }
+    }
+
+    /// The reply line; `index` is the position in a command list.
+    pub fn line(&self, index: usize, command: &str) -> String {
+        format!("ACK [{}@{}] {{{}}} {}\n", self.code, index, command, self.message)
+    }
+}
+
+impl From<Failure> for Ack {
+    fn from(failure: Failure) -> Self {
+        let code = match failure {
+            Failure::Usage(_) => ACK_ARG,
+            Failure::NotAuthenticated(_) | Failure::PremiumRequired => ACK_PERMISSION,
+            Failure::NoActiveDevice => ACK_PLAYER_SYNC,
+            Failure::NotFound(_) => ACK_NO_EXIST,
+            Failure::Api(_) | Failure::Daemon { .. } => ACK_SYSTEM,
+        };
+        Ack::new(code, failure.to_string())
+    }
+}
+
+/// Splits a command line into words. Double quotes group words, and inside
+/// them a backslash escapes the next character.
+pub fn tokenize(line: &str) -> Result<Vec<String>, Ack> {
+    let mut words = Vec::new();
+    let mut chars = line.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+            continue;
+        }
+        let mut word = String::new();
+        if c == '"' {
+            chars.next();
+            loop {
+                match chars.next() {
+                    Some('"') => break,
+                    Some('\\') => word.extend(chars.next()),
+                    Some(c) => word.push(c),
+                    None => return Err(Ack::new(ACK_ARG, "Missing closing '\"'")),
+                }
+            }
+        } else {
+            while let Some(&c) = chars.peek() {
+                if c.is_whitespace() {
+                    break;
+                }
+                word.push(c);
+                chars.next();
+            }
+        }
+        words.push(word);
+    }
+    Ok(words)
+}
+
+/// Argument `index`, which the command requires.
+pub fn arg(args: &[String], index: usize) -> Result<&str, Ack> {
+    args.get(index).map(String::as_str).ok_or_else(|| Ack::new(ACK_ARG, "wrong number of arguments"))
+}
+
+pub fn number<T: FromStr>(value: &str) -> Result<T, Ack> {
+    value.parse().map_err(|_| Ack::new(ACK_ARG, format!("Number expected: {}", value)))
+}
+
+pub fn flag(value: &str) -> Result<bool, Ack> {
+    match value {
+        "0" => Ok(false),
+        "1" => Ok(true),
+        _ => Err(Ack::new(ACK_ARG, format!("Boolean (0/1) expected: {}", value))),
+    }
+}
+
+/// Seconds with an optional fraction, as milliseconds.
+pub fn seconds(value: &str) -> Result<u64, Ack> {
+    let seconds: f64 = value.parse().map_err(|_| Ack::new(ACK_ARG, format!("Time expected: {}", value)))?;
+    if !seconds.is_finite() || seconds < 0.0 {
+        return Err(Ack::new(ACK_ARG, format!("Time expected: {}", value)));
+    }
+    Ok((seconds * 1000.0).round() as u64)
+}
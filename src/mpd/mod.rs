@@ -0,0 +1,470 @@
+use serde_json::Value;
+use std::fmt::Write as _;
+use std::io;
+use std::sync::Arc;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader, Lines};
+use tokio::net::tcp::OwnedReadHalf;
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::{broadcast, Mutex};
+
+use crate::commands::RepeatMode;
+use crate::daemon::{Adjust, Player, Request, SearchHit, SharedEngine};
+use crate::events::PlaybackEvent;
+use crate::models::{CurrentPlayback, ShuffleMode, Track};
+use crate::uri::ItemKind;
+
+mod command;
+
+use command::{arg, flag, number, seconds, tokenize, Ack, ACK_ARG, ACK_NO_EXIST, ACK_UNKNOWN};
+
+/// The protocol version we claim. Clients use it to pick which commands to
+/// send; 0.23 predates the ones we don't have.
+const GREETING: &str = "OK MPD 0.23.5\n";
+const SEARCH_LIMIT: u32 = 50;
+
+/// Subsystems `idle` can report, in the order they are listed.
+const SUBSYSTEMS: [&str; 4] = ["player", "playlist", "mixer", "options"];
+
+const COMMANDS: &[&str] = &["add", "binarylimit", "channels", "close", "command_list_begin", "command_list_end", "command_list_ok_begin", "commands", "consume", "currentsong", "decoders", "find", "idle", "listplaylists", "lsinfo", "next", "noidle", "notcommands", "outputs", "password", "pause", "ping", "play", "playid", "playlistid", "playlistinfo", "plchanges", "plchangesposid", "previous", "random", "readmessages", "repeat", "replay_gain_status", "search", "seek", "seekcur", "seekid", "setvol", "single", "stats", "status", "stop", "tagtypes", "urlhandlers", "volume"];
+
+/// Serves MPD clients on `listener`. The "playlist" is the current track
+/// followed by Spotify's queue; there is no library beyond `search`.
+pub async fn serve<P: Player>(listener: TcpListener, engine: SharedEngine<P>) -> io::Result<()> {
+    let mut events = engine.subscribe();
+    let server = Arc::new(Server { engine, playlist: Mutex::new(Playlist::default()) });
+
+    let watcher = server.clone();
+    let watch = async move {
+        loop {
+            match events.recv().await {
+                Ok(event) if subsystems(&event).contains(&"playlist") => watcher.playlist_changed().await,
+                Ok(_) => {}
+                Err(broadcast::error::RecvError::Lagged(_)) => watcher.playlist_changed().await,
+                Err(broadcast::error::RecvError::Closed) => return,
+            }
+        }
+    };
+    let accept = async move {
+        loop {
+            let (stream, _) = listener.accept().await?;
+            tokio::spawn(server.clone().connection(stream));
+        }
+    };
+    tokio::select! {
+        () = watch => Ok(()),
+        result = accept => result,
+    }
+}
+
+struct Server<P> {
+    engine: SharedEngine<P>,
+    playlist: Mutex<Playlist>,
+}
+
+/// The queue as last fetched. `version` goes up whenever it may have
+/// changed, which is how clients know to ask again.
+struct Playlist {
+    version: u32,
+    upcoming: Option<Vec<Track>>,
+}
+
+impl Default for Playlist {
+    fn default() -> Self {
+        Self { version: 1, upcoming: None }
+    }
+}
+
+impl<P: Player> Server<P> {
+    async fn connection(self: Arc<Self>, stream: TcpStream) {
+        // Changes from here on are reported by the next `idle`
+        let mut events = self.engine.subscribe();
+        let (reader, mut writer) = stream.into_split();
+        let mut lines = BufReader::new(reader).lines();
+        if writer.write_all(GREETING.as_bytes()).await.is_err() {
+            return;
+        }
+        while let Ok(Some(line)) = lines.next_line().await {
+            let reply = match line.trim() {
+                "close" => return,
+                // Only meaningful while idle
+                "noidle" => continue,
+                list @ ("command_list_begin" | "command_list_ok_begin") => {
+                    let list_ok = list == "command_list_ok_begin";
+                    let mut commands = Vec::new();
+                    loop {
+                        match lines.next_line().await {
+                            Ok(Some(line)) if line.trim() == "command_list_end" => break,
+                            Ok(Some(line)) => commands.push(line),
+                            _ => return,
+                        }
+                    }
+                    self.run_list(&commands, list_ok).await
+                }
+                idle if idle == "idle" || idle.starts_with("idle ") => match idle_for(idle, &mut events, &mut lines).await {
+                    Some(reply) => reply,
+                    None => return,
+                },
+                line => match self.run(line).await {
+                    Ok(body) => body + "OK\n",
+                    Err((command, ack)) => ack.line(0, &command),
+                },
+            };
+            if writer.write_all(reply.as_bytes()).await.is_err() {
+                return;
+            }
+        }
+    }
+
+    /// Runs a command list, stopping at the first failure.
+    async fn run_list(&self, commands: &[String], list_ok: bool) -> String {
+        let mut reply = String::new();
+        for (index, line) in commands.iter().enumerate() {
+            match self.run(line).await {
+                Ok(body) => {
+                    reply += &body;
+                    if list_ok {
+                        reply += "list_OK\n";
+                    }
+                }
+                Err((command, ack)) => return reply + &ack.line(index, &command),
+            }
+        }
+        reply + "OK\n"
+    }
+
+    /// Runs one command line; failures name the command for the ACK line.
+    async fn run(&self, line: &str) -> Result<String, (String, Ack)> {
+        let words = tokenize(line).map_err(|ack| (String::new(), ack))?;
+        let Some((name, args)) = words.split_first() else {
+            return Err((String::new(), Ack::new(ACK_UNKNOWN, "No command given")));
+        };
+        match self.execute(name, args).await {
+            Ok(body) => Ok(body),
+            // MPD leaves the command out when it doesn't know it
+            Err(ack) if ack.code == ACK_UNKNOWN => Err((String::new(), ack)),
+            Err(ack) => Err((name.clone(), ack)),
+        }
+    }
+
+    async fn execute(&self, name: &str, args: &[String]) -> Result<String, Ack> {
+        match name {
+            "ping" | "password" | "binarylimit" | "consume" => Ok(String::new()),
+            "status" => self.status().await,
+            "currentsong" => {
+                let playback = self.engine.playback().await?;
+                Ok(playback.and_then(|p| p.item).map(|track| song(&track, 0)).unwrap_or_default())
+            }
+            "stats" => Ok("artists: 0\nalbums: 0\nsongs: 0\nuptime: 0\nplaytime: 0\ndb_playtime: 0\ndb_update: 0\n".to_string()),
+            "play" | "playid" => {
+                // Spotify can't jump within its queue, so skip forward to it
+                let skip = match args.first() {
+                    Some(value) if name == "playid" => number::<usize>(value)?.saturating_sub(1),
+                    Some(value) => number::<usize>(value)?,
+                    None => 0,
+                };
+                if skip == 0 {
+                    return self.control(Request::Resume).await;
+                }
+                let (_, songs) = self.songs().await?;
+                if skip >= songs.len() {
+                    return Err(Ack::new(ACK_NO_EXIST, "Bad song index"));
+                }
+                for _ in 0..skip {
+                    self.request(Request::Next).await?;
+                }
+                Ok(String::new())
+            }
+            "pause" => match args.first() {
+                None => self.control(Request::Toggle).await,
+                Some(value) if flag(value)? => self.control(Request::Pause).await,
+                Some(_) => self.control(Request::Resume).await,
+            },
+            // Spotify can't stop, only pause
+            "stop" => self.control(Request::Pause).await,
+            "next" => self.control(Request::Next).await,
+            "previous" => self.control(Request::Previous).await,
+            "setvol" => {
+                let volume: u8 = number(arg(args, 0)?)?;
+                if volume > 100 {
+                    return Err(Ack::new(ACK_ARG, "Invalid volume value"));
+                }
+                self.control(Request::Volume { change: Adjust::To(volume) }).await
+            }
+            "volume" => {
+                let change: i32 = number(arg(args, 0)?)?;
+                let step = change.unsigned_abs().min(100) as u8;
+                let change = if change < 0 { Adjust::Down(step) } else { Adjust::Up(step) };
+                self.control(Request::Volume { change }).await
+            }
+            "seekcur" => {
+                let time = arg(args, 0)?;
+                let change = if let Some(forward) = time.strip_prefix('+') {
+                    Adjust::Up(seconds(forward)?)
+                } else if let Some(back) = time.strip_prefix('-') {
+                    Adjust::Down(seconds(back)?)
+                } else {
+                    Adjust::To(seconds(time)?)
+                };
+                self.control(Request::Seek { change }).await
+            }
+            "seek" | "seekid" => {
+                // Only the current song can be seeked: position 0, id 1
+                let current = if name == "seek" { 0 } else { 1 };
+                if number::<usize>(arg(args, 0)?)? != current {
+                    return Err(Ack::new(ACK_NO_EXIST, "Only the current song can be seeked"));
+                }
+                let change = Adjust::To(seconds(arg(args, 1)?)?);
+                self.control(Request::Seek { change }).await
+            }
+            "random" => {
+                let mode = if flag(arg(args, 0)?)? { ShuffleMode::On } else { ShuffleMode::Off };
+                self.control(Request::Shuffle { mode: Some(mode) }).await
+            }
+            // Spotify has one repeat setting: off, track (MPD's repeat +
+            // single) or context (repeat alone)
+            "repeat" => {
+                let mode = if flag(arg(args, 0)?)? { RepeatMode::Context } else { RepeatMode::Off };
+                if mode == RepeatMode::Context && self.repeat().await? == RepeatMode::Track {
+                    return Ok(String::new());
+                }
+                self.control(Request::Repeat { mode }).await
+            }
+            "single" => {
+                if flag(arg(args, 0)?)? {
+                    self.control(Request::Repeat { mode: RepeatMode::Track }).await
+                } else if self.repeat().await? == RepeatMode::Track {
+                    self.control(Request::Repeat { mode: RepeatMode::Context }).await
+                } else {
+                    Ok(String::new())
+                }
+            }
+            "playlistinfo" | "playlistid" | "plchanges" => {
+                let (_, songs) = self.songs().await?;
+                let wanted = match (name, args.first()) {
+                    ("playlistinfo", Some(position)) => Some(number::<usize>(position)?),
+                    ("playlistid", Some(id)) => Some(number::<usize>(id)?.saturating_sub(1)),
+                    _ => None,
+                };
+                match wanted {
+                    Some(position) => songs
+                        .get(position)
+                        .map(|track| song(track, position))
+                        .ok_or_else(|| Ack::new(ACK_NO_EXIST, "No such song")),
+                    None => Ok(songs.iter().enumerate().map(|(position, track)| song(track, position)).collect()),
+                }
+            }
+            "plchangesposid" => {
+                let (_, songs) = self.songs().await?;
+                Ok((0..songs.len()).map(|position| format!("cpos: {}\nId: {}\n", position, position + 1)).collect())
+            }
+            "add" => {
+                self.request(Request::Queue { uri: arg(args, 0)?.to_string() }).await?;
+                self.playlist_changed().await;
+                Ok(String::new())
+            }
+            "search" | "find" => self.search(args).await,
+            "tagtypes" if args.is_empty() => Ok("tagtype: Artist\ntagtype: Album\ntagtype: Title\n".to_string()),
+            "tagtypes" => Ok(String::new()),
+            "outputs" => Ok("outputid: 0\noutputname: Spotify\nplugin: spotify\noutputenabled: 1\n".to_string()),
+            "commands" => Ok(COMMANDS.iter().map(|command| format!("command: {}\n", command)).collect()),
+            "urlhandlers" => Ok("handler: spotify:\n".to_string()),
+            "notcommands" | "listplaylists" | "lsinfo" | "decoders" | "channels" | "readmessages" => Ok(String::new()),
+            "replay_gain_status" => Ok("replay_gain_mode: off\n".to_string()),
+            other => Err(Ack::new(ACK_UNKNOWN, format!("unknown command \"{}\"", other))),
+        }
+    }
+
+    async fn request(&self, request: Request) -> Result<Value, Ack> {
+        Ok(self.engine.handle(request).await?)
+    }
+
+    /// A command with an empty reply.
+    async fn control(&self, request: Request) -> Result<String, Ack> {
+        self.request(request).await.map(|_| String::new())
+    }
+
+    async fn repeat(&self) -> Result<RepeatMode, Ack> {
+        let playback = self.engine.playback().await?;
+        Ok(match playback.map(|p| p.repeat_state).as_deref() {
+            Some("track") => RepeatMode::Track,
+            Some("context") => RepeatMode::Context,
+            _ => RepeatMode::Off,
+        })
+    }
+
+    async fn status(&self) -> Result<String, Ack> {
+        let playback = self.engine.playback().await?;
+        let (version, songs) = self.songs_for(playback.as_ref()).await;
+        let mut status = String::new();
+        let repeat = playback.as_ref().map(|p| p.repeat_state.as_str()).unwrap_or("off");
+        if let Some(volume) = playback.as_ref().and_then(|p| p.device.volume_percent) {
+            let _ = writeln!(status, "volume: {}", volume);
+        }
+        let _ = writeln!(status, "repeat: {}", u8::from(repeat != "off"));
+        let _ = writeln!(status, "random: {}", u8::from(playback.as_ref().is_some_and(|p| p.shuffle_state)));
+        let _ = writeln!(status, "single: {}", u8::from(repeat == "track"));
+        let _ = writeln!(status, "consume: 0");
+        let _ = writeln!(status, "playlist: {}", version);
+        let _ = writeln!(status, "playlistlength: {}", songs.len());
+        let state = match playback.as_ref() {
+            Some(p) if p.is_playing => "play",
+            Some(_) => "pause",
+            None => "stop",
+        };
+        let _ = writeln!(status, "state: {}", state);
+        if let Some((playback, track)) = playback.as_ref().and_then(|p| Some((p, p.item.as_ref()?))) {
+            let elapsed = playback.progress_ms.unwrap_or(0) as f64 / 1000.0;
+            let duration = track.duration_ms as f64 / 1000.0;
+            let _ = writeln!(status, "song: 0\nsongid: 1");
+            let _ = writeln!(status, "time: {}:{}", elapsed as u64, duration.round() as u64);
+            let _ = writeln!(status, "elapsed: {:.3}\nduration: {:.3}", elapsed, duration);
+            if songs.len() > 1 {
+                let _ = writeln!(status, "nextsong: 1\nnextsongid: 2");
+            }
+        }
+        Ok(status)
+    }
+
+    /// The playlist: the current track, then the queue.
+    async fn songs(&self) -> Result<(u32, Vec<Track>), Ack> {
+        let playback = self.engine.playback().await?;
+        Ok(self.songs_for(playback.as_ref()).await)
+    }
+
+    async fn songs_for(&self, playback: Option<&CurrentPlayback>) -> (u32, Vec<Track>) {
+        let Some(current) = playback.and_then(|p| p.item.clone()) else {
+            return (self.playlist.lock().await.version, Vec::new());
+        };
+        let mut playlist = self.playlist.lock().await;
+        if playlist.upcoming.is_none() {
+            // A failed fetch shows just the current track and is retried next time
+            if let Ok(upcoming) = self.engine.upcoming().await {
+                playlist.upcoming = Some(upcoming);
+            }
+        }
+        let mut songs = vec![current];
+        songs.extend(playlist.upcoming.iter().flatten().cloned());
+        (playlist.version, songs)
+    }
+
+    async fn playlist_changed(&self) {
+        let mut playlist = self.playlist.lock().await;
+        playlist.version = playlist.version.wrapping_add(1);
+        playlist.upcoming = None;
+    }
+
+    /// `search`/`find` with `TYPE WHAT` pairs, turned into a Spotify query.
+    async fn search(&self, args: &[String]) -> Result<String, Ack> {
+        if args.first().is_some_and(|arg| arg.starts_with('(')) {
+            return Err(Ack::new(ACK_ARG, "filter expressions aren't supported; use TYPE WHAT pairs"));
+        }
+        if args.is_empty() || !args.len().is_multiple_of(2) {
+            return Err(Ack::new(ACK_ARG, "incorrect arguments"));
+        }
+        let mut terms = Vec::new();
+        for pair in args.chunks(2) {
+            let what = &pair[1];
+            terms.push(match pair[0].to_lowercase().as_str() {
+                "any" => what.clone(),
+                "title" => format!("track:\"{}\"", what),
+                "artist" | "albumartist" => format!("artist:\"{}\"", what),
+                "album" => format!("album:\"{}\"", what),
+                other => return Err(Ack::new(ACK_ARG, format!("Unsupported tag type: {}", other))),
+            });
+        }
+        let request = Request::Search { query: terms.join(" "), kind: ItemKind::Track, limit: SEARCH_LIMIT };
+        let hits: Vec<SearchHit> = match self.engine.handle(request).await {
+            Ok(data) => serde_json::from_value(data).unwrap_or_default(),
+            Err(crate::cli::Failure::NotFound(_)) => Vec::new(),
+            Err(failure) => return Err(failure.into()),
+        };
+        let mut reply = String::new();
+        for hit in hits {
+            let (title, artists) = hit.name.split_once('\t').unwrap_or((hit.name.as_str(), ""));
+            let _ = writeln!(reply, "file: {}\nTitle: {}", hit.uri, title);
+            for artist in artists.split(", ").filter(|a| !a.is_empty()) {
+                let _ = writeln!(reply, "Artist: {}", artist);
+            }
+        }
+        Ok(reply)
+    }
+}
+
+/// Waits for a change in one of the subsystems `line` asks about (all when
+/// none are named), or for `noidle`. Changes since the last command count.
+/// `None` means the client broke protocol or left.
+async fn idle_for(
+    line: &str,
+    events: &mut broadcast::Receiver<PlaybackEvent>,
+    lines: &mut Lines<BufReader<OwnedReadHalf>>,
+) -> Option<String> {
+    let wanted: Vec<String> = tokenize(line).ok()?.into_iter().skip(1).collect();
+    let mut changed = Vec::new();
+    drain(events, &wanted, &mut changed);
+    while changed.is_empty() {
+        tokio::select! {
+            line = lines.next_line() => match line {
+                Ok(Some(line)) if line.trim() == "noidle" => return Some("OK\n".to_string()),
+                // Anything else while idle is a protocol error
+                _ => return None,
+            },
+            event = events.recv() => match event {
+                Ok(event) => note(subsystems(&event), &wanted, &mut changed),
+                Err(broadcast::error::RecvError::Lagged(_)) => note(&SUBSYSTEMS, &wanted, &mut changed),
+                Err(broadcast::error::RecvError::Closed) => return None,
+            },
+        }
+    }
+    // Report whatever else arrived with it in one go
+    drain(events, &wanted, &mut changed);
+    let mut reply: String = changed.iter().map(|subsystem| format!("changed: {}\n", subsystem)).collect();
+    reply += "OK\n";
+    Some(reply)
+}
+
+fn drain(events: &mut broadcast::Receiver<PlaybackEvent>, wanted: &[String], changed: &mut Vec<&'static str>) {
+    loop {
+        match events.try_recv() {
+            Ok(event) => note(subsystems(&event), wanted, changed),
+            Err(broadcast::error::TryRecvError::Lagged(_)) => note(&SUBSYSTEMS, wanted, changed),
+            Err(_) => return,
+        }
+    }
+}
+
+fn note(subsystems: &[&'static str], wanted: &[String], changed: &mut Vec<&'static str>) {
+    for subsystem in subsystems {
+        if (wanted.is_empty() || wanted.iter().any(|w| w == subsystem)) && !changed.contains(subsystem) {
+            changed.push(subsystem);
+        }
+    }
+}
+
+fn subsystems(event: &PlaybackEvent) -> &'static [&'static str] {
+    match event {
+        PlaybackEvent::TrackChanged { .. } => &["player", "playlist"],
+        PlaybackEvent::Paused | PlaybackEvent::Resumed | PlaybackEvent::Seeked { .. } | PlaybackEvent::DeviceChanged { .. } => {
+            &["player"]
+        }
+        PlaybackEvent::VolumeChanged { .. } => &["mixer"],
+        PlaybackEvent::ShuffleChanged { .. } | PlaybackEvent::RepeatChanged { .. } => &["options"],
+        PlaybackEvent::ContextChanged { .. } => &["playlist"],
+    }
+}
+
+/// A playlist entry. Ids are positions plus one; Spotify's queue has no
+/// stable ids, and the playlist version tells clients when to refetch.
+fn song(track: &Track, position: usize) -> String {
+    let mut song = format!("file: {}\n", track.uri);
+    for artist in &track.artists {
+        let _ = writeln!(song, "Artist: {}", artist.name);
+    }
+    if let Some(album) = track.album.as_ref() {
+        let _ = writeln!(song, "Album: {}", album.name);
+    }
+    let duration = track.duration_ms as f64 / 1000.0;
+    let _ = writeln!(song, "Title: {}", track.name);
+    let _ = writeln!(song, "Time: {}\nduration: {:.3}", duration.round() as u64, duration);
+    let _ = writeln!(song, "Pos: {}\nId: {}", position, position + 1);
+    song
+}
@@ -0,0 +1,326 @@
+use std::collections::HashMap;
+use tokio::sync::broadcast;
+use zbus::object_server::SignalEmitter;
+use zbus::zvariant::{ObjectPath, OwnedValue, Value};
+use zbus::{connection, fdo, interface, Connection};
+
+use crate::commands::RepeatMode;
+use crate::daemon::{Adjust, Player, Request, SharedEngine};
+use crate::events::PlaybackEvent;
+use crate::models::{CurrentPlayback, ShuffleMode};
+
+/// Well-known name desktop shells look for; anything under
+/// `org.mpris.MediaPlayer2.` is picked up.
+pub const BUS_NAME: &str = "org.mpris.MediaPlayer2.spotycli";
+pub const OBJECT_PATH: &str = "/org/mpris/MediaPlayer2";
+/// The track id MPRIS reserves for "nothing playing".
+const NO_TRACK: &str = "/org/mpris/MediaPlayer2/TrackList/NoTrack";
+
+/// Publishes `engine` on the session bus. It stays published until the
+/// returned connection is dropped.
+pub async fn start<P: Player>(engine: SharedEngine<P>) -> zbus::Result<Connection> {
+    serve(connection::Builder::session()?, engine).await
+}
+
+/// Publishes `engine` on the bus `builder` connects to, and forwards its
+/// playback events as property changes and `Seeked` signals.
+pub async fn serve<P: Player>(builder: connection::Builder<'_>, engine: SharedEngine<P>) -> zbus::Result<Connection> {
+    let events = engine.subscribe();
+    let connection = builder
+        .name(BUS_NAME)?
+        .serve_at(OBJECT_PATH, Root)?
+        .serve_at(OBJECT_PATH, MprisPlayer { engine })?
+        .build()
+        .await?;
+    tokio::spawn(forward_events::<P>(connection.clone(), events));
+    Ok(connection)
+}
+
+async fn forward_events<P: Player>(connection: Connection, mut events: broadcast::Receiver<PlaybackEvent>) {
+    let Ok(player) = connection.object_server().interface::<_, MprisPlayer<P>>(OBJECT_PATH).await else {
+        return;
+    };
+    loop {
+        let event = match events.recv().await {
+            Ok(event) => event,
+            Err(broadcast::error::RecvError::Lagged(_)) => continue,
+            Err(broadcast::error::RecvError::Closed) => return,
+        };
+        let emitter = player.signal_emitter();
+        let iface = player.get().await;
+        // Nobody listening is fine
+        let _ = match event {
+            PlaybackEvent::TrackChanged { .. } => iface.metadata_changed(emitter).await,
+            PlaybackEvent::Paused | PlaybackEvent::Resumed | PlaybackEvent::DeviceChanged { .. } => {
+                iface.playback_status_changed(emitter).await
+            }
+            PlaybackEvent::Seeked { position_ms } => MprisPlayer::<P>::seeked(emitter, position_ms as i64 * 1000).await,
+            PlaybackEvent::VolumeChanged { .. } => iface.volume_changed(emitter).await,
+            PlaybackEvent::ShuffleChanged { .. } => iface.shuffle_changed(emitter).await,
+            PlaybackEvent::RepeatChanged { .. } => iface.loop_status_changed(emitter).await,
+            PlaybackEvent::ContextChanged { .. } => Ok(()),
+        };
+    }
+}
+
+/// `org.mpris.MediaPlayer2`: who we are. There is no window to raise.
+struct Root;
+
+#[interface(name = "org.mpris.MediaPlayer2")]
+impl Root {
+    fn raise(&self) {}
+
+    fn quit(&self) {}
+
+    #[zbus(property)]
+    fn can_quit(&self) -> bool {
+        false
+    }
+
+    #[zbus(property)]
+    fn can_raise(&self) -> bool {
+        false
+    }
+
+    #[zbus(property)]
+    fn has_track_list(&self) -> bool {
+        false
+    }
+
+    #[zbus(property)]
+    fn identity(&self) -> String {
+        "SpotyCli".to_string()
+    }
+
+    #[zbus(property)]
+    fn supported_uri_schemes(&self) -> Vec<String> {
+        vec!["spotify".to_string(), "https".to_string()]
+    }
+
+    #[zbus(property)]
+    fn supported_mime_types(&self) -> Vec<String> {
+        Vec::new()
+    }
+}
+
+/// `org.mpris.MediaPlayer2.Player`, answered from the engine's cached
+/// playback. Commands go through the engine like socket requests do.
+pub struct MprisPlayer<P> {
+    engine: SharedEngine<P>,
+}
+
+impl<P: Player> MprisPlayer<P> {
+    async fn request(&self, request: Request) -> fdo::Result<()> {
+        match self.engine.handle(request).await {
+            Ok(_) => Ok(()),
+            Err(failure) => Err(fdo::Error::Failed(failure.to_string())),
+        }
+    }
+
+    async fn current(&self) -> Option<CurrentPlayback> {
+        self.engine.current()
+    }
+}
+
+#[interface(name = "org.mpris.MediaPlayer2.Player")]
+impl<P: Player> MprisPlayer<P> {
+    async fn play_pause(&self) -> fdo::Result<()> {
+        self.request(Request::Toggle).await
+    }
+
+    async fn play(&self) -> fdo::Result<()> {
+        self.request(Request::Resume).await
+    }
+
+    async fn pause(&self) -> fdo::Result<()> {
+        self.request(Request::Pause).await
+    }
+
+    /// Spotify can't stop, only pause.
+    async fn stop(&self) -> fdo::Result<()> {
+        self.request(Request::Pause).await
+    }
+
+    async fn next(&self) -> fdo::Result<()> {
+        self.request(Request::Next).await
+    }
+
+    async fn previous(&self) -> fdo::Result<()> {
+        self.request(Request::Previous).await
+    }
+
+    /// Moves by `offset` microseconds, back when negative.
+    async fn seek(&self, offset: i64) -> fdo::Result<()> {
+        let ms = offset.unsigned_abs() / 1000;
+        let change = if offset < 0 { Adjust::Down(ms) } else { Adjust::Up(ms) };
+        self.request(Request::Seek { change }).await
+    }
+
+    /// Ignored when `track_id` is no longer playing, as the spec asks.
+    async fn set_position(&self, track_id: ObjectPath<'_>, position: i64) -> fdo::Result<()> {
+        let current = self.current().await.map(|playback| track_path(&playback));
+        if position < 0 || current.as_deref() != Some(track_id.as_str()) {
+            return Ok(());
+        }
+        self.request(Request::Seek { change: Adjust::To(position as u64 / 1000) }).await
+    }
+
+    /// Takes `spotify:` URIs and open.spotify.com links.
+    async fn open_uri(&self, uri: String) -> fdo::Result<()> {
+        self.request(Request::Play { target: Some(uri) }).await
+    }
+
+    #[zbus(signal)]
+    async fn seeked(emitter: &SignalEmitter<'_>, position: i64) -> zbus::Result<()>;
+
+    #[zbus(property)]
+    async fn playback_status(&self) -> String {
+        match self.current().await {
+            Some(playback) if playback.is_playing => "Playing",
+            Some(_) => "Paused",
+            None => "Stopped",
+        }
+        .to_string()
+    }
+
+    #[zbus(property)]
+    async fn loop_status(&self) -> String {
+        match self.current().await.map(|playback| playback.repeat_state) {
+            Some(repeat) if repeat == "track" => "Track",
+            Some(repeat) if repeat == "context" => "Playlist",
+            _ => "None",
+        }
+        .to_string()
+    }
+
+    #[zbus(property)]
+    async fn set_loop_status(&self, status: String) -> fdo::Result<()> {
+        let mode = match status.as_str() {
+            "Track" => RepeatMode::Track,
+            "Playlist" => RepeatMode::Context,
+            _ => RepeatMode::Off,
+        };
+        self.request(Request::Repeat { mode }).await
+    }
+
+    #[zbus(property)]
+    async fn shuffle(&self) -> bool {
+        self.current().await.is_some_and(|playback| playback.shuffle_state)
+    }
+
+    #[zbus(property)]
+    async fn set_shuffle(&self, shuffle: bool) -> fdo::Result<()> {
+        let mode = if shuffle { ShuffleMode::On } else { ShuffleMode::Off };
+        self.request(Request::Shuffle { mode: Some(mode) }).await
+    }
+
+    #[zbus(property)]
+    async fn metadata(&self) -> HashMap<String, OwnedValue> {
+        match self.current().await {
+            Some(playback) => metadata(&playback),
+            None => HashMap::from([("mpris:trackid".to_string(), owned(ObjectPath::from_static_str_unchecked(NO_TRACK)))]),
+        }
+    }
+
+    /// 0.0 to 1.0.
+    #[zbus(property)]
+    async fn volume(&self) -> f64 {
+        let percent = self.current().await.and_then(|playback| playback.device.volume_percent).unwrap_or(0);
+        f64::from(percent) / 100.0
+    }
+
+    #[zbus(property)]
+    async fn set_volume(&self, volume: f64) -> fdo::Result<()> {
+        let percent = (volume.clamp(0.0, 1.0) * 100.0).round() as u8;
+        self.request(Request::Volume { change: Adjust::To(percent) }).await
+    }
+
+    /// Microseconds. Clients are expected to extrapolate it, so changes
+    /// aren't signalled.
+    #[zbus(property(emits_changed_signal = "false"))]
+    async fn position(&self) -> i64 {
+        self.current().await.and_then(|playback| playback.progress_ms).unwrap_or(0) as i64 * 1000
+    }
+
+    #[zbus(property)]
+    fn rate(&self) -> f64 {
+        1.0
+    }
+
+    #[zbus(property)]
+    fn minimum_rate(&self) -> f64 {
+        1.0
+    }
+
+    #[zbus(property)]
+    fn maximum_rate(&self) -> f64 {
+        1.0
+    }
+
+    #[zbus(property)]
+    fn can_go_next(&self) -> bool {
+        true
+    }
+
+    #[zbus(property)]
+    fn can_go_previous(&self) -> bool {
+        true
+    }
+
+    #[zbus(property)]
+    fn can_play(&self) -> bool {
+        true
+    }
+
+    #[zbus(property)]
+    fn can_pause(&self) -> bool {
+        true
+    }
+
+    #[zbus(property)]
+    fn can_seek(&self) -> bool {
+        true
+    }
+
+    #[zbus(property)]
+    fn can_control(&self) -> bool {
+        true
+    }
+}
+
+/// An object path for the track, since MPRIS track ids must be paths.
+fn track_path(playback: &CurrentPlayback) -> String {
+    match playback.item.as_ref() {
+        Some(track) => format!("/org/spotycli/track/{}", track.id),
+        None => NO_TRACK.to_string(),
+    }
+}
+
+fn metadata(playback: &CurrentPlayback) -> HashMap<String, OwnedValue> {
+    let mut metadata = HashMap::new();
+    if let Ok(path) = ObjectPath::try_from(track_path(playback)) {
+        metadata.insert("mpris:trackid".to_string(), owned(path));
+    }
+    let Some(track) = playback.item.as_ref() else {
+        return metadata;
+    };
+    metadata.insert("mpris:length".to_string(), owned(i64::from(track.duration_ms) * 1000));
+    metadata.insert("xesam:title".to_string(), owned(track.name.clone()));
+    metadata.insert("xesam:artist".to_string(), owned(track.artists.iter().map(|a| a.name.clone()).collect::<Vec<_>>()));
+    if let Ok(uri) = track.spotify_uri() {
+        metadata.insert("xesam:url".to_string(), owned(uri.url()));
+    }
+    if let Some(album) = track.album.as_ref() {
+        metadata.insert("xesam:album".to_string(), owned(album.name.clone()));
+        // Spotify lists the largest cover first
+        if let Some(image) = album.images.as_ref().and_then(|images| images.first()) {
+            metadata.insert("mpris:artUrl".to_string(), owned(image.url.clone()));
+        }
+    }
+    metadata
+}
+
+/// Plain values always convert; only file descriptors can fail.
+fn owned<'a>(value: impl Into<Value<'a>>) -> OwnedValue {
+    OwnedValue::try_from(value.into()).expect("plain D-Bus values convert to owned ones")
+}
@@ -0,0 +1,430 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt;
+use std::future::Future;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use warp::http::StatusCode;
+use warp::Filter;
+
+use crate::api::SpotifyClient;
+use crate::config::PartyConfig;
+use crate::models::Track;
+use crate::uri::{ItemKind, SpotifyUri};
+
+/// The guest page: search, submit and vote, all against the routes below.
+const PAGE: &str = include_str!("page.html");
+const SEARCH_LIMIT: u32 = 10;
+/// App tokens last an hour; fetch a new one a little before that.
+const APP_TOKEN_LIFETIME: Duration = Duration::from_secs(50 * 60);
+const GUEST_NAME_LENGTH: usize = 24;
+/// Searches a guest may make a minute, so one phone can't hog the catalog.
+const SEARCHES_PER_MINUTE: usize = 20;
+/// Submit and vote bodies are a URI and a name at most.
+const BODY_LIMIT: u64 = 4 * 1024;
+
+/// Shared between the web server and the TUI's Party panel.
+pub type SharedParty = Arc<Mutex<Party>>;
+
+/// A track a guest asked for, waiting to be fed to Spotify.
+#[derive(Debug, Clone)]
+pub struct Submission {
+    pub id: u64,
+    pub track: Track,
+    pub guest: IpAddr,
+    /// What the guest called themselves, or their address.
+    pub from: String,
+    pub votes: HashSet<IpAddr>,
+    /// Approved by the host, or never needed approval.
+    pub approved: bool,
+}
+
+/// Why a submission or vote was turned down.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Refusal {
+    Blocked,
+    NotAllowed,
+    AlreadyPending,
+    TooSoon(Duration),
+    NoSuchSubmission,
+}
+
+impl fmt::Display for Refusal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Refusal::Blocked => write!(f, "The host has blocked you from submitting"),
+            Refusal::NotAllowed => write!(f, "That track isn't allowed tonight"),
+            Refusal::AlreadyPending => write!(f, "That track is already on the list"),
+            Refusal::TooSoon(wait) => write!(f, "Slow down! Try again in {} min", wait.as_secs().div_ceil(60).max(1)),
+            Refusal::NoSuchSubmission => write!(f, "That track isn't on the list any more"),
+        }
+    }
+}
+
+impl Refusal {
+    fn status(&self) -> StatusCode {
+        match self {
+            Refusal::Blocked | Refusal::NotAllowed => StatusCode::FORBIDDEN,
+            Refusal::AlreadyPending => StatusCode::CONFLICT,
+            Refusal::TooSoon(_) => StatusCode::TOO_MANY_REQUESTS,
+            Refusal::NoSuchSubmission => StatusCode::NOT_FOUND,
+        }
+    }
+}
+
+/// Submissions, votes and who may do what. Guests are told apart by
+/// address, which on a home network is one per phone.
+pub struct Party {
+    config: PartyConfig,
+    submissions: Vec<Submission>,
+    next_id: u64,
+    /// When each guest's recent submissions were made.
+    recent: HashMap<IpAddr, VecDeque<Instant>>,
+    /// When each guest's searches in the last minute were made.
+    searches: HashMap<IpAddr, VecDeque<Instant>>,
+    blocked: HashSet<IpAddr>,
+    /// Shown at the top of the guest page.
+    pub now_playing: Option<String>,
+}
+
+impl Party {
+    pub fn new(config: PartyConfig) -> Self {
+        Self {
+            config,
+            submissions: Vec::new(),
+            next_id: 1,
+            recent: HashMap::new(),
+            searches: HashMap::new(),
+            blocked: HashSet::new(),
+            now_playing: None,
+        }
+    }
+
+    /// Refuses blocked or rate-limited guests before anything is looked up.
+    pub fn check(&mut self, guest: IpAddr) -> Result<(), Refusal> {
+        if self.blocked.contains(&guest) {
+            return Err(Refusal::Blocked);
+        }
+        let window = Duration::from_secs(self.config.window_minutes * 60);
+        match wait(self.recent.entry(guest).or_default(), window, self.config.submissions_per_window) {
+            Some(wait) => Err(Refusal::TooSoon(wait)),
+            None => Ok(()),
+        }
+    }
+
+    /// Refuses blocked guests, and counts the search against the guest's
+    /// allowance.
+    pub fn check_search(&mut self, guest: IpAddr) -> Result<(), Refusal> {
+        if self.blocked.contains(&guest) {
+            return Err(Refusal::Blocked);
+        }
+        let searches = self.searches.entry(guest).or_default();
+        if let Some(wait) = wait(searches, Duration::from_secs(60), SEARCHES_PER_MINUTE) {
+            return Err(Refusal::TooSoon(wait));
+        }
+        searches.push_back(Instant::now());
+        Ok(())
+    }
+
+    pub fn submit(&mut self, guest: IpAddr, name: Option<String>, track: Track) -> Result<u64, Refusal> {
+        self.check(guest)?;
+        let blocked_artist = track
+            .artists
+            .iter()
+            .any(|artist| self.config.blocked_artists.iter().any(|blocked| blocked.eq_ignore_ascii_case(&artist.name)));
+        if blocked_artist || self.config.blocked_tracks.contains(&track.uri) {
+            return Err(Refusal::NotAllowed);
+        }
+        if self.submissions.iter().any(|s| s.track.uri == track.uri) {
+            return Err(Refusal::AlreadyPending);
+        }
+        self.recent.entry(guest).or_default().push_back(Instant::now());
+        let id = self.next_id;
+        self.next_id += 1;
+        let from = name
+            .map(|name| name.trim().chars().take(GUEST_NAME_LENGTH).collect::<String>())
+            .filter(|name| !name.is_empty())
+            .unwrap_or_else(|| guest.to_string());
+        self.submissions.push(Submission {
+            id,
+            track,
+            guest,
+            from,
+            // Submitting counts as a vote
+            votes: HashSet::from([guest]),
+            approved: !self.config.require_approval,
+        });
+        Ok(id)
+    }
+
+    /// Adds the guest's vote, or takes it back.
+    pub fn vote(&mut self, guest: IpAddr, id: u64) -> Result<(), Refusal> {
+        if self.blocked.contains(&guest) {
+            return Err(Refusal::Blocked);
+        }
+        let submission = self.submissions.iter_mut().find(|s| s.id == id).ok_or(Refusal::NoSuchSubmission)?;
+        if !submission.votes.remove(&guest) {
+            submission.votes.insert(guest);
+        }
+        Ok(())
+    }
+
+    /// Everything waiting, most votes first, then oldest first.
+    pub fn pending(&self) -> Vec<&Submission> {
+        let mut pending: Vec<&Submission> = self.submissions.iter().collect();
+        pending.sort_by(|a, b| b.votes.len().cmp(&a.votes.len()).then(a.id.cmp(&b.id)));
+        pending
+    }
+
+    pub fn awaiting_approval(&self) -> usize {
+        self.submissions.iter().filter(|s| !s.approved).count()
+    }
+
+    pub fn approve(&mut self, id: u64) {
+        if let Some(submission) = self.submissions.iter_mut().find(|s| s.id == id) {
+            submission.approved = true;
+        }
+    }
+
+    pub fn veto(&mut self, id: u64) -> Option<Submission> {
+        let index = self.submissions.iter().position(|s| s.id == id)?;
+        Some(self.submissions.remove(index))
+    }
+
+    /// Blocks whoever submitted `id` and drops everything they submitted.
+    pub fn block_submitter(&mut self, id: u64) -> Option<String> {
+        let submission = self.submissions.iter().find(|s| s.id == id)?;
+        let (guest, from) = (submission.guest, submission.from.clone());
+        self.blocked.insert(guest);
+        self.submissions.retain(|s| s.guest != guest);
+        Some(from)
+    }
+
+    /// The approved track with the most votes, taken off the list.
+    pub fn take_next(&mut self) -> Option<Track> {
+        let id = self.pending().into_iter().find(|s| s.approved)?.id;
+        self.veto(id).map(|s| s.track)
+    }
+
+    /// The list as a guest sees it.
+    fn view(&self, guest: IpAddr) -> PartyView {
+        PartyView {
+            now_playing: self.now_playing.clone(),
+            submissions: self
+                .pending()
+                .into_iter()
+                .map(|s| SubmissionView {
+                    id: s.id,
+                    title: s.track.name.clone(),
+                    artists: artist_names(&s.track),
+                    from: s.from.clone(),
+                    votes: s.votes.len(),
+                    voted: s.votes.contains(&guest),
+                    approved: s.approved,
+                })
+                .collect(),
+        }
+    }
+}
+
+/// How long until `recent` has room for another, once it holds `limit`
+/// from the last `window`. Older entries are dropped.
+fn wait(recent: &mut VecDeque<Instant>, window: Duration, limit: usize) -> Option<Duration> {
+    while recent.front().is_some_and(|at| at.elapsed() >= window) {
+        recent.pop_front();
+    }
+    match recent.front() {
+        Some(oldest) if recent.len() >= limit => Some(window.saturating_sub(oldest.elapsed())),
+        _ => None,
+    }
+}
+
+pub fn artist_names(track: &Track) -> String {
+    track.artists.iter().map(|a| a.name.as_str()).collect::<Vec<_>>().join(", ")
+}
+
+#[derive(Serialize)]
+struct PartyView {
+    now_playing: Option<String>,
+    submissions: Vec<SubmissionView>,
+}
+
+#[derive(Serialize)]
+struct SubmissionView {
+    id: u64,
+    title: String,
+    artists: String,
+    from: String,
+    votes: usize,
+    voted: bool,
+    approved: bool,
+}
+
+#[derive(Serialize)]
+struct SearchResult {
+    uri: String,
+    title: String,
+    artists: String,
+}
+
+#[derive(Deserialize)]
+struct SearchQuery {
+    q: String,
+}
+
+#[derive(Deserialize)]
+struct SubmitBody {
+    uri: String,
+    name: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct VoteBody {
+    id: u64,
+}
+
+/// Catalog lookups with the app's own token, renewed before it expires.
+struct Catalog {
+    client: SpotifyClient,
+    renew_at: Instant,
+}
+
+impl Catalog {
+    async fn client(&mut self) -> anyhow::Result<&SpotifyClient> {
+        if Instant::now() >= self.renew_at {
+            self.client.authenticate().await?;
+            self.renew_at = Instant::now() + APP_TOKEN_LIFETIME;
+        }
+        Ok(&self.client)
+    }
+}
+
+/// Starts serving the guest page on `address`, returning the address bound
+/// and the server to run.
+pub fn serve(
+    address: SocketAddr,
+    party: SharedParty,
+    client: SpotifyClient,
+) -> Result<(SocketAddr, impl Future<Output = ()>), warp::Error> {
+    let catalog = Arc::new(tokio::sync::Mutex::new(Catalog { client, renew_at: Instant::now() }));
+    let party = warp::any().map(move || party.clone());
+    let catalog = warp::any().map(move || catalog.clone());
+    let guest = warp::addr::remote().map(|address: Option<SocketAddr>| address.map(|a| a.ip()).unwrap_or(IpAddr::from([0, 0, 0, 0])));
+
+    let page = warp::get().and(warp::path::end()).map(|| warp::reply::html(PAGE));
+    let search = warp::get()
+        .and(warp::path!("api" / "search"))
+        .and(warp::query::<SearchQuery>())
+        .and(party.clone())
+        .and(catalog.clone())
+        .and(guest)
+        .then(search);
+    let list = warp::get().and(warp::path!("api" / "queue")).and(party.clone()).and(guest).map(|party: SharedParty, guest| {
+        let view = party.lock().unwrap().view(guest);
+        warp::reply::json(&view)
+    });
+    let submit = warp::post()
+        .and(warp::path!("api" / "submit"))
+        .and(warp::body::content_length_limit(BODY_LIMIT))
+        .and(warp::body::json::<SubmitBody>())
+        .and(party.clone())
+        .and(catalog)
+        .and(guest)
+        .then(submit);
+    let vote = warp::post()
+        .and(warp::path!("api" / "vote"))
+        .and(warp::body::content_length_limit(BODY_LIMIT))
+        .and(warp::body::json::<VoteBody>())
+        .and(party)
+        .and(guest)
+        .map(|body: VoteBody, party: SharedParty, guest| reply(party.lock().unwrap().vote(guest, body.id).map(|()| "Vote counted".to_string())));
+
+    let routes = page
+        .map(warp::Reply::into_response)
+        .or(search.map(warp::Reply::into_response))
+        .unify()
+        .or(list.map(warp::Reply::into_response))
+        .unify()
+        .or(submit.map(warp::Reply::into_response))
+        .unify()
+        .or(vote.map(warp::Reply::into_response))
+        .unify();
+    warp::serve(routes).try_bind_ephemeral(address)
+}
+
+async fn search(
+    query: SearchQuery,
+    party: SharedParty,
+    catalog: Arc<tokio::sync::Mutex<Catalog>>,
+    guest: IpAddr,
+) -> warp::reply::WithStatus<warp::reply::Json> {
+    if let Err(refusal) = party.lock().unwrap().check_search(guest) {
+        return reply(Err(refusal));
+    }
+    let mut catalog = catalog.lock().await;
+    let found = match catalog.client().await {
+        Ok(client) => client.search(&query.q, "track", SEARCH_LIMIT).await,
+        Err(e) => Err(e),
+    };
+    match found {
+        Ok(response) => {
+            let results: Vec<SearchResult> = response
+                .tracks
+                .map(|page| page.items)
+                .unwrap_or_default()
+                .iter()
+                .map(|track| SearchResult { uri: track.uri.clone(), title: track.name.clone(), artists: artist_names(track) })
+                .collect();
+            warp::reply::with_status(warp::reply::json(&results), StatusCode::OK)
+        }
+        Err(_) => message("Search isn't working right now", StatusCode::BAD_GATEWAY),
+    }
+}
+
+async fn submit(
+    body: SubmitBody,
+    party: SharedParty,
+    catalog: Arc<tokio::sync::Mutex<Catalog>>,
+    guest: IpAddr,
+) -> warp::reply::WithStatus<warp::reply::Json> {
+    // Refuse early so blocked and rate-limited guests cost no lookups
+    if let Err(refusal) = party.lock().unwrap().check(guest) {
+        return reply(Err(refusal));
+    }
+    let Ok(uri) = SpotifyUri::parse_as(ItemKind::Track, &body.uri) else {
+        return message("That isn't a track", StatusCode::BAD_REQUEST);
+    };
+    // Look the track up rather than trusting what the page sent
+    let mut catalog = catalog.lock().await;
+    let track = match catalog.client().await {
+        Ok(client) => client.get_track(uri.id().as_str()).await,
+        Err(e) => Err(e),
+    };
+    drop(catalog);
+    let Ok(track) = track else {
+        return message("Couldn't find that track", StatusCode::BAD_GATEWAY);
+    };
+    let title = track.name.clone();
+    reply(party.lock().unwrap().submit(guest, body.name, track).map(|_| format!("Added {}", title)))
+}
+
+fn reply(result: Result<String, Refusal>) -> warp::reply::WithStatus<warp::reply::Json> {
+    match result {
+        Ok(text) => message(&text, StatusCode::OK),
+        Err(refusal) => message(&refusal.to_string(), refusal.status()),
+    }
+}
+
+fn message(text: &str, status: StatusCode) -> warp::reply::WithStatus<warp::reply::Json> {
+    warp::reply::with_status(warp::reply::json(&serde_json::json!({ "message": text })), status)
+}
+
+/// This machine's address on the LAN, for showing guests where to go. No
+/// packets are sent: connecting a UDP socket only picks the route.
+pub fn lan_address() -> Option<IpAddr> {
+    let socket = std::net::UdpSocket::bind("0.0.0.0:0").ok()?;
+    socket.connect("192.0.2.1:80").ok()?;
+    socket.local_addr().ok().map(|address| address.ip())
+}
+
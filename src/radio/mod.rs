@@ -0,0 +1,217 @@
+use anyhow::Result;
+use std::collections::{HashSet, VecDeque};
+use std::future::Future;
+
+use crate::api::SpotifyClient;
+use crate::config::{RadioConfig, RadioSource};
+use crate::models::{Album, Artist, SavedTrack, Track};
+
+mod station;
+
+pub use station::{spawn, Station, StationCommand, StationEvent, StationHandle};
+
+/// Related artists whose top tracks go into the mix.
+const RELATED_ARTISTS: usize = 5;
+
+/// What radio needs from Spotify. `SpotifyClient` talks to the Web API;
+/// tests use fixtures.
+pub trait Catalog: Sync {
+    fn related_artists(&self, artist_id: &str) -> impl Future<Output = Result<Vec<Artist>>> + Send;
+    fn top_tracks(&self, artist_id: &str) -> impl Future<Output = Result<Vec<Track>>> + Send;
+    fn album_tracks(&self, album: &Album) -> impl Future<Output = Result<Vec<Track>>> + Send;
+    /// Recently liked tracks, newest first.
+    fn liked_tracks(&self) -> impl Future<Output = Result<Vec<Track>>> + Send;
+    /// Recently played tracks, newest first.
+    fn history(&self) -> impl Future<Output = Result<Vec<Track>>> + Send;
+}
+
+impl Catalog for SpotifyClient {
+    async fn related_artists(&self, artist_id: &str) -> Result<Vec<Artist>> {
+        self.get_related_artists(artist_id).await
+    }
+
+    async fn top_tracks(&self, artist_id: &str) -> Result<Vec<Track>> {
+        self.get_artist_top_tracks(artist_id).await
+    }
+
+    async fn album_tracks(&self, album: &Album) -> Result<Vec<Track>> {
+        self.get_all_album_tracks(album).await
+    }
+
+    async fn liked_tracks(&self) -> Result<Vec<Track>> {
+        let page = self.get_liked_songs(50, 0).await?;
+        let items = page.get("items").and_then(|items| items.as_array()).cloned().unwrap_or_default();
+        Ok(items
+            .into_iter()
+            .filter_map(|item| serde_json::from_value::<SavedTrack>(item).ok())
+            .map(SavedTrack::into_track)
+            .collect())
+    }
+
+    async fn history(&self) -> Result<Vec<Track>> {
+        Ok(self.get_recently_played(50).await?.items.into_iter().map(|item| item.track).collect())
+    }
+}
+
+/// Picks tracks like a seed track from the configured sources, taking
+/// turns between them. Nothing is picked twice or from the latest plays,
+/// and an artist waits `artist_gap` picks before coming up again. Keep one
+/// around to pick more for the same station.
+pub struct Radio {
+    sources: Vec<RadioSource>,
+    artist_gap: usize,
+    avoid_recent: usize,
+    /// Ids and titles not to pick: recent plays and everything picked.
+    seen: HashSet<String>,
+    /// Artist ids of the latest picks, newest last.
+    recent_artists: VecDeque<Vec<String>>,
+    /// Artists never to pick again.
+    banned: HashSet<String>,
+}
+
+impl Radio {
+    pub fn new(config: &RadioConfig) -> Self {
+        Self {
+            sources: config.sources.clone(),
+            artist_gap: config.artist_gap,
+            avoid_recent: config.avoid_recent,
+            seen: HashSet::new(),
+            recent_artists: VecDeque::new(),
+            banned: HashSet::new(),
+        }
+    }
+
+    /// Up to `count` tracks like `seed`. Sources that fail are skipped, with
+    /// a line in `logs`.
+    pub async fn next_batch<C: Catalog>(&mut self, catalog: &C, seed: &Track, count: usize, logs: &mut Vec<String>) -> Vec<Track> {
+        let history = catalog.history().await.unwrap_or_else(|e| {
+            logs.push(format!("⚠️  Couldn't read listening history: {}", e));
+            Vec::new()
+        });
+        for track in history.iter().take(self.avoid_recent) {
+            self.exclude(track);
+        }
+        // The seed counts as just played, so its artist waits like any other
+        if !self.seen.contains(&seed.id) {
+            self.remember(seed);
+        }
+
+        let mut neighborhood: Vec<Artist> = seed.artists.clone();
+        let related = match seed.artists.first() {
+            // Liked songs and history are narrowed down to related artists too
+            Some(artist) if self.sources.iter().any(|s| matches!(s, RadioSource::RelatedArtists | RadioSource::LikedSongs | RadioSource::History)) => {
+                catalog.related_artists(&artist.id).await.unwrap_or_else(|e| {
+                    logs.push(format!("⚠️  Couldn't get artists related to {}: {}", artist.name, e));
+                    Vec::new()
+                })
+            }
+            _ => Vec::new(),
+        };
+        neighborhood.extend(related.iter().cloned());
+        let near = |track: &Track| track.artists.iter().any(|a| neighborhood.iter().any(|n| n.id == a.id));
+
+        let mut pools = Vec::new();
+        for source in self.sources.clone() {
+            let pool = match source {
+                RadioSource::RelatedArtists => top_tracks(catalog, related.iter().take(RELATED_ARTISTS)).await,
+                RadioSource::ArtistTopTracks => top_tracks(catalog, &seed.artists).await,
+                RadioSource::AlbumNeighbors => match &seed.album {
+                    Some(album) => catalog.album_tracks(album).await.map(|tracks| nearest_first(tracks, &seed.id)),
+                    None => Ok(Vec::new()),
+                },
+                RadioSource::LikedSongs => catalog.liked_tracks().await.map(|liked| liked.into_iter().filter(|t| near(t)).collect()),
+                RadioSource::History => Ok(history.iter().skip(self.avoid_recent).filter(|t| near(t)).cloned().collect()),
+            };
+            match pool {
+                Ok(pool) => {
+                    logs.push(format!("🔍 {:?}: {} candidates", source, pool.len()));
+                    pools.push(pool);
+                }
+                Err(e) => logs.push(format!("⚠️  {:?} failed: {}", source, e)),
+            }
+        }
+
+        let picks = self.pick(pools, count);
+        logs.push(format!("📻 Picked {} tracks", picks.len()));
+        picks
+    }
+
+    /// Takes up to `count` tracks from `pools`, one from each in turn. A
+    /// track whose artist came up too recently waits in its pool for a later
+    /// turn.
+    pub fn pick(&mut self, pools: Vec<Vec<Track>>, count: usize) -> Vec<Track> {
+        let mut pools: Vec<VecDeque<Track>> = pools.into_iter().map(VecDeque::from).collect();
+        let mut picks = Vec::new();
+        while picks.len() < count {
+            let mut picked = false;
+            for pool in &mut pools {
+                if picks.len() == count {
+                    break;
+                }
+                pool.retain(|track| {
+                    !keys(track).iter().any(|key| self.seen.contains(key)) && !track.artists.iter().any(|a| self.banned.contains(&a.id))
+                });
+                if let Some(track) = pool.iter().position(|track| !self.too_soon(track)).and_then(|i| pool.remove(i)) {
+                    self.remember(&track);
+                    picks.push(track);
+                    picked = true;
+                }
+            }
+            if !picked {
+                break;
+            }
+        }
+        picks
+    }
+
+    /// Keeps `track` from being picked.
+    pub fn exclude(&mut self, track: &Track) {
+        self.seen.extend(keys(track));
+    }
+
+    /// Keeps `track`'s artists out of everything picked from now on.
+    pub fn ban(&mut self, track: &Track) {
+        self.exclude(track);
+        self.banned.extend(track.artists.iter().map(|a| a.id.clone()));
+    }
+
+    fn remember(&mut self, track: &Track) {
+        self.exclude(track);
+        if self.artist_gap > 0 {
+            self.recent_artists.push_back(track.artists.iter().map(|a| a.id.clone()).collect());
+            if self.recent_artists.len() > self.artist_gap {
+                self.recent_artists.pop_front();
+            }
+        }
+    }
+
+    fn too_soon(&self, track: &Track) -> bool {
+        track.artists.iter().any(|artist| self.recent_artists.iter().flatten().any(|id| *id == artist.id))
+    }
+}
+
+/// The id, and the title with the lead artist so the same song on another
+/// release counts as a repeat.
+fn keys(track: &Track) -> [String; 2] {
+    let artist = track.artists.first().map(|a| a.id.as_str()).unwrap_or_default();
+    [track.id.clone(), format!("{}\t{}", track.name.to_lowercase(), artist)]
+}
+
+/// Top tracks of each artist, one after another.
+async fn top_tracks<'a, C: Catalog>(catalog: &C, artists: impl IntoIterator<Item = &'a Artist>) -> Result<Vec<Track>> {
+    let mut tracks = Vec::new();
+    for artist in artists {
+        tracks.extend(catalog.top_tracks(&artist.id).await?);
+    }
+    Ok(tracks)
+}
+
+/// Album tracks ordered by distance from the seed, the track after it first.
+fn nearest_first(mut tracks: Vec<Track>, seed_id: &str) -> Vec<Track> {
+    let Some(seed) = tracks.iter().position(|t| t.id == seed_id) else {
+        return tracks;
+    };
+    let mut indexed: Vec<(usize, Track)> = tracks.drain(..).enumerate().collect();
+    indexed.sort_by_key(|(i, _)| (i.abs_diff(seed), *i < seed));
+    indexed.into_iter().map(|(_, track)| track).collect()
+}
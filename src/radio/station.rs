@@ -0,0 +1,124 @@
+use std::collections::HashSet;
+use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
+use tokio::task::JoinHandle;
+
+use super::{Catalog, Radio};
+use crate::config::RadioConfig;
+use crate::models::Track;
+
+/// Radio that keeps going: picks around its seeds whenever asked for more,
+/// steered by thumbs up and down.
+pub struct Station {
+    radio: Radio,
+    /// Tracks to pick around: the one it started from, then every thumbs up.
+    seeds: Vec<Track>,
+    /// Which seed the next batch is picked around.
+    next_seed: usize,
+}
+
+impl Station {
+    pub fn new(config: &RadioConfig, seed: Track) -> Self {
+        Self { radio: Radio::new(config), seeds: vec![seed], next_seed: 0 }
+    }
+
+    /// Up to `count` more tracks, around each seed in turn.
+    pub async fn next_batch<C: Catalog>(&mut self, catalog: &C, count: usize, logs: &mut Vec<String>) -> Vec<Track> {
+        let seed = self.seeds[self.next_seed % self.seeds.len()].clone();
+        self.next_seed += 1;
+        logs.push(format!("📻 Picking {} more around {}", count, seed.name));
+        self.radio.next_batch(catalog, &seed, count, logs).await
+    }
+
+    /// Picks around `track` next, and keeps it as a seed.
+    pub fn thumbs_up(&mut self, track: Track) {
+        self.radio.exclude(&track);
+        self.next_seed = self.seeds.len();
+        self.seeds.push(track);
+    }
+
+    /// Stops picking `track`'s artists.
+    pub fn thumbs_down(&mut self, track: &Track) {
+        self.radio.ban(track);
+    }
+}
+
+pub enum StationCommand {
+    /// Pick this many more tracks.
+    TopUp(usize),
+    ThumbsUp(Track),
+    ThumbsDown(Track),
+}
+
+pub enum StationEvent {
+    Log(String),
+    /// A batch asked for with `TopUp`; empty when radio has run dry.
+    Picked(Vec<Track>),
+}
+
+/// A station running in the background. Dropping it stops the station.
+pub struct StationHandle {
+    /// The track it started from.
+    pub name: String,
+    /// A `TopUp` hasn't been answered yet.
+    pub topping_up: bool,
+    /// Ids of the tracks it picked.
+    pub picked: HashSet<String>,
+    pub thumbs_up: usize,
+    pub thumbs_down: usize,
+    commands: UnboundedSender<StationCommand>,
+    events: UnboundedReceiver<StationEvent>,
+    task: JoinHandle<()>,
+}
+
+impl StationHandle {
+    pub fn send(&mut self, command: StationCommand) {
+        match &command {
+            StationCommand::TopUp(_) => self.topping_up = true,
+            StationCommand::ThumbsUp(_) => self.thumbs_up += 1,
+            StationCommand::ThumbsDown(_) => self.thumbs_down += 1,
+        }
+        let _ = self.commands.send(command);
+    }
+
+    /// The next event, if one is waiting.
+    pub fn try_recv(&mut self) -> Option<StationEvent> {
+        let event = self.events.try_recv().ok()?;
+        if let StationEvent::Picked(tracks) = &event {
+            self.topping_up = false;
+            self.picked.extend(tracks.iter().map(|t| t.id.clone()));
+        }
+        Some(event)
+    }
+}
+
+impl Drop for StationHandle {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// Runs `station` on its own task, so picking doesn't hold up the caller.
+pub fn spawn<C: Catalog + Send + 'static>(mut station: Station, catalog: C) -> StationHandle {
+    let name = station.seeds[0].name.clone();
+    let (commands, mut requests) = mpsc::unbounded_channel();
+    let (events, received) = mpsc::unbounded_channel();
+    let task = tokio::spawn(async move {
+        while let Some(command) = requests.recv().await {
+            match command {
+                StationCommand::TopUp(count) => {
+                    let mut logs = Vec::new();
+                    let picks = station.next_batch(&catalog, count, &mut logs).await;
+                    for line in logs {
+                        let _ = events.send(StationEvent::Log(line));
+                    }
+                    if events.send(StationEvent::Picked(picks)).is_err() {
+                        return;
+                    }
+                }
+                StationCommand::ThumbsUp(track) => station.thumbs_up(track),
+                StationCommand::ThumbsDown(track) => station.thumbs_down(&track),
+            }
+        }
+    });
+    StationHandle { name, topping_up: false, picked: HashSet::new(), thumbs_up: 0, thumbs_down: 0, commands, events: received, task }
+}
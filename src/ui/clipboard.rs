@@ -0,0 +1,14 @@
+use std::io::{self, Write};
+
+use base64::Engine;
+
+/// Puts `text` on the system clipboard with an OSC 52 escape sequence.
+///
+/// The terminal does the copying, so this also works over SSH. Terminals
+/// that don't support OSC 52 (or have it disabled) silently ignore it.
+pub fn copy(text: &str) -> io::Result<()> {
+    let encoded = base64::engine::general_purpose::STANDARD.encode(text.as_bytes());
+    let mut stdout = io::stdout();
+    write!(stdout, "\x1b]52;c;{}\x07", encoded)?;
+    stdout.flush()
+}
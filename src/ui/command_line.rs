@@ -0,0 +1,172 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+use crate::commands::{COMMAND_NAMES, PLAYLIST_SUBCOMMANDS};
+
+/// Commands typed at the `:` prompt, oldest first, kept between sessions.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CommandHistory {
+    pub entries: Vec<String>,
+}
+
+impl CommandHistory {
+    const MAX_ENTRIES: usize = 100;
+    const STORAGE_FILE: &'static str = ".spotify_command_history";
+
+    pub fn load() -> Self {
+        if Path::new(Self::STORAGE_FILE).exists() {
+            if let Ok(content) = fs::read_to_string(Self::STORAGE_FILE) {
+                if let Ok(history) = serde_json::from_str::<CommandHistory>(&content) {
+                    return history;
+                }
+            }
+        }
+        Self::default()
+    }
+
+    pub fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let content = serde_json::to_string_pretty(self)?;
+        fs::write(Self::STORAGE_FILE, content)?;
+        Ok(())
+    }
+
+    /// Records `line`, moving it to the end if it was typed before.
+    pub fn push(&mut self, line: &str) {
+        self.entries.retain(|entry| entry != line);
+        self.entries.push(line.to_string());
+        if self.entries.len() > Self::MAX_ENTRIES {
+            self.entries.remove(0);
+        }
+    }
+}
+
+/// The `:` prompt being typed.
+#[derive(Debug, Default)]
+pub struct CommandLine {
+    pub input: String,
+    /// History entry shown while browsing with Up/Down.
+    browsing: Option<usize>,
+    /// What was typed before browsing started, restored past the newest entry.
+    draft: String,
+    /// Candidates from the last Tab, and which one is in `input`.
+    completions: Vec<String>,
+    completion: usize,
+    /// Length of the part all candidates share, e.g. `device `.
+    head_len: usize,
+}
+
+impl CommandLine {
+    pub fn push(&mut self, c: char) {
+        self.input.push(c);
+        self.reset();
+    }
+
+    pub fn pop(&mut self) {
+        self.input.pop();
+        self.reset();
+    }
+
+    fn reset(&mut self) {
+        self.browsing = None;
+        self.completions.clear();
+    }
+
+    /// Whether completing the current input needs the device list.
+    pub fn wants_devices(&self) -> bool {
+        self.input.trim_start().starts_with("device ")
+    }
+
+    /// Tab: fills in the first candidate, or the next one on repeated presses.
+    pub fn complete(&mut self, forward: bool, devices: &[String], playlists: &[String]) {
+        if self.completions.is_empty() {
+            let (head, completions) = candidates(&self.input, devices, playlists);
+            self.completions = completions;
+            self.head_len = head.len();
+            self.completion = 0;
+            if self.completions.is_empty() {
+                return;
+            }
+        } else {
+            let len = self.completions.len();
+            self.completion = if forward { (self.completion + 1) % len } else { (self.completion + len - 1) % len };
+        }
+        self.input = self.completions[self.completion].clone();
+        // A lone match is final, so the next Tab completes the argument after it
+        if self.completions.len() == 1 {
+            self.completions.clear();
+        }
+    }
+
+    /// What each candidate fills in, for display while cycling with Tab.
+    pub fn completion_labels(&self) -> Vec<&str> {
+        self.completions.iter().map(|c| c.get(self.head_len..).unwrap_or(c).trim_end()).collect()
+    }
+
+    /// Up/Down through earlier commands.
+    pub fn browse(&mut self, history: &CommandHistory, older: bool) {
+        let len = history.entries.len();
+        if len == 0 {
+            return;
+        }
+        let next = match (self.browsing, older) {
+            (None, true) => {
+                self.draft = self.input.clone();
+                Some(len - 1)
+            }
+            (None, false) => return,
+            (Some(i), true) => Some(i.saturating_sub(1)),
+            (Some(i), false) if i + 1 < len => Some(i + 1),
+            (Some(_), false) => None,
+        };
+        self.input = match next {
+            Some(i) => history.entries[i].clone(),
+            None => std::mem::take(&mut self.draft),
+        };
+        self.browsing = next;
+        self.completions.clear();
+    }
+}
+
+/// Full replacement lines for `input`: command names first, then the
+/// arguments each command takes (device and playlist names, modes).
+fn candidates(input: &str, devices: &[String], playlists: &[String]) -> (String, Vec<String>) {
+    let input = input.trim_start();
+    let (command, rest) = match input.split_once(' ') {
+        Some(split) => split,
+        None => {
+            let names = COMMAND_NAMES
+                .iter()
+                .filter(|name| name.starts_with(input))
+                .map(|name| format!("{} ", name))
+                .collect();
+            return (String::new(), names);
+        }
+    };
+
+    let (head, options): (String, Vec<String>) = match command {
+        "device" => ("device ".to_string(), devices.to_vec()),
+        "shuffle" => ("shuffle ".to_string(), words(&["on", "off", "smart"])),
+        "repeat" => ("repeat ".to_string(), words(&["off", "track", "context"])),
+        "queue" => ("queue ".to_string(), words(&["clear-local"])),
+        "radio" => ("radio ".to_string(), words(&["stop"])),
+        "playlist" => match rest.split_once(' ') {
+            Some((sub @ ("open" | "add"), _)) => (format!("playlist {} ", sub), playlists.to_vec()),
+            Some(_) => return (String::new(), Vec::new()),
+            None => ("playlist ".to_string(), PLAYLIST_SUBCOMMANDS.iter().map(|s| format!("{} ", s)).collect()),
+        },
+        _ => return (String::new(), Vec::new()),
+    };
+
+    let partial = input.strip_prefix(head.as_str()).unwrap_or("").to_lowercase();
+    let lines = options
+        .into_iter()
+        .filter(|option| option.to_lowercase().starts_with(&partial))
+        .map(|option| format!("{}{}", head, option))
+        .collect();
+    (head, lines)
+}
+
+fn words(options: &[&str]) -> Vec<String> {
+    options.iter().map(|s| s.to_string()).collect()
+}
@@ -0,0 +1,188 @@
+use ratatui::widgets::ListState;
+
+use crate::models::{Album, Artist, Playlist, Track};
+use crate::uri::SpotifyUri;
+
+/// What a context menu entry acts on.
+#[derive(Debug, Clone)]
+pub enum MenuTarget {
+    Track(Track),
+    Album(Album),
+    Artist(Artist),
+    Playlist(Playlist),
+}
+
+impl MenuTarget {
+    pub fn name(&self) -> &str {
+        match self {
+            MenuTarget::Track(track) => &track.name,
+            MenuTarget::Album(album) => &album.name,
+            MenuTarget::Artist(artist) => &artist.name,
+            MenuTarget::Playlist(playlist) => &playlist.name,
+        }
+    }
+
+    pub fn spotify_uri(&self) -> anyhow::Result<SpotifyUri> {
+        match self {
+            MenuTarget::Track(track) => track.spotify_uri(),
+            MenuTarget::Album(album) => album.spotify_uri(),
+            MenuTarget::Artist(artist) => artist.spotify_uri(),
+            MenuTarget::Playlist(playlist) => playlist.spotify_uri(),
+        }
+    }
+
+    /// Actions offered for this kind of item, in menu order.
+    fn actions(&self) -> &'static [MenuAction] {
+        use MenuAction::*;
+        match self {
+            MenuTarget::Track(_) => &[
+                Play, PlayNext, Queue, Like, AddToPlaylist, GoToArtist, GoToAlbum, StartRadio, CopyLink, ShowPlaylists,
+            ],
+            MenuTarget::Album(_) => &[Play, Queue, Like, AddToPlaylist, Open, GoToArtist, CopyLink],
+            MenuTarget::Artist(_) => &[Play, Open, CopyLink],
+            MenuTarget::Playlist(_) => &[Play, Queue, Open, CopyLink],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MenuAction {
+    Play,
+    PlayNext,
+    Queue,
+    Like,
+    AddToPlaylist,
+    Open,
+    GoToArtist,
+    GoToAlbum,
+    StartRadio,
+    CopyLink,
+    ShowPlaylists,
+}
+
+impl MenuAction {
+    fn label(self, target: &MenuTarget) -> &'static str {
+        match (self, target) {
+            (MenuAction::Play, _) => "Play",
+            (MenuAction::PlayNext, _) => "Play next",
+            (MenuAction::Queue, MenuTarget::Track(_)) => "Add to queue",
+            (MenuAction::Queue, _) => "Add all tracks to queue",
+            (MenuAction::Like, MenuTarget::Album(_)) => "Save / remove album",
+            (MenuAction::Like, _) => "Like / unlike",
+            (MenuAction::AddToPlaylist, MenuTarget::Track(_)) => "Add to playlist...",
+            (MenuAction::AddToPlaylist, _) => "Add all tracks to playlist...",
+            (MenuAction::Open, MenuTarget::Album(_)) => "Open album",
+            (MenuAction::Open, MenuTarget::Artist(_)) => "Open artist",
+            (MenuAction::Open, _) => "Open playlist",
+            (MenuAction::GoToArtist, _) => "Go to artist",
+            (MenuAction::GoToAlbum, _) => "Go to album",
+            (MenuAction::StartRadio, _) => "Start radio",
+            (MenuAction::CopyLink, _) => "Copy link",
+            (MenuAction::ShowPlaylists, _) => "Show playlists containing it",
+        }
+    }
+
+    /// Scopes the action needs, and whether it controls playback (Premium only).
+    fn requirement(self) -> (&'static [&'static str], bool) {
+        match self {
+            MenuAction::Play | MenuAction::PlayNext | MenuAction::Queue | MenuAction::StartRadio => {
+                (&["user-modify-playback-state"], true)
+            }
+            MenuAction::Like => (&["user-library-modify"], false),
+            // The playlist is picked afterwards and may be public or private
+            MenuAction::AddToPlaylist => (&["playlist-modify-public", "playlist-modify-private"], false),
+            MenuAction::ShowPlaylists => (&["playlist-read-private"], false),
+            MenuAction::Open | MenuAction::GoToArtist | MenuAction::GoToAlbum | MenuAction::CopyLink => (&[], false),
+        }
+    }
+}
+
+/// What the signed-in account is allowed to do.
+#[derive(Debug, Clone, Default)]
+pub struct Capabilities {
+    pub authenticated: bool,
+    /// Scopes granted to the saved token.
+    pub scopes: Vec<String>,
+    /// `None` until the account's subscription is known.
+    pub premium: Option<bool>,
+}
+
+impl Capabilities {
+    /// Why `action` can't be used, or `None` if it can.
+    fn missing(&self, action: MenuAction) -> Option<String> {
+        let (scopes, needs_premium) = action.requirement();
+        if scopes.is_empty() {
+            return None;
+        }
+        if !self.authenticated {
+            return Some("sign in first".to_string());
+        }
+        if let Some(scope) = scopes.iter().find(|scope| !self.scopes.iter().any(|s| s == *scope)) {
+            return Some(format!("needs the {} scope, sign in again", scope));
+        }
+        if needs_premium && self.premium == Some(false) {
+            return Some("needs Spotify Premium".to_string());
+        }
+        None
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct MenuItem {
+    pub label: String,
+    pub action: MenuAction,
+    pub target: MenuTarget,
+    /// Why the entry is greyed out.
+    pub disabled: Option<String>,
+}
+
+/// Popup listing what can be done with the selected item.
+#[derive(Debug, Clone)]
+pub struct ContextMenu {
+    pub title: String,
+    pub items: Vec<MenuItem>,
+    pub state: ListState,
+}
+
+impl ContextMenu {
+    pub fn new(target: MenuTarget, capabilities: &Capabilities) -> Self {
+        let items = target
+            .actions()
+            .iter()
+            .map(|action| MenuItem {
+                label: action.label(&target).to_string(),
+                action: *action,
+                target: target.clone(),
+                disabled: capabilities.missing(*action),
+            })
+            .collect();
+        Self::with_items(target.name().to_string(), items)
+    }
+
+    /// A menu of arbitrary entries, e.g. the playlists that contain a track.
+    pub fn with_items(title: String, items: Vec<MenuItem>) -> Self {
+        let first = items.iter().position(|item| item.disabled.is_none()).unwrap_or(0);
+        Self {
+            title,
+            items,
+            state: ListState::default().with_selected(Some(first)),
+        }
+    }
+
+    /// Moves the selection, skipping disabled entries.
+    pub fn step(&mut self, forward: bool) {
+        let selected = self.state.selected().unwrap_or(0);
+        let next = if forward {
+            (selected + 1..self.items.len()).find(|i| self.items[*i].disabled.is_none())
+        } else {
+            (0..selected).rev().find(|i| self.items[*i].disabled.is_none())
+        };
+        if let Some(next) = next {
+            self.state.select(Some(next));
+        }
+    }
+
+    pub fn selected(&self) -> Option<&MenuItem> {
+        self.state.selected().and_then(|i| self.items.get(i))
+    }
+}
@@ -0,0 +1,194 @@
+use ratatui::symbols::border;
+use ratatui::text::{Line, Span};
+
+use crate::config::RenderMode;
+
+/// ASCII spellings for every symbol the UI prints. Semantic icons become
+/// words, decorative ones are dropped, box-drawing turns into plain ASCII.
+const ASCII_REPLACEMENTS: &[(&str, &str)] = &[
+    ("⏸️", "||"),
+    ("⏸", "||"),
+    ("⏹️", "[stopped]"),
+    ("⏹", "[stopped]"),
+    ("▶", ">"),
+    ("⏭️", ">>|"),
+    ("⏭", ">>|"),
+    ("⏮", "|<<"),
+    ("✅", "[ok]"),
+    ("❌", "[error]"),
+    ("⚠️", "[warning]"),
+    ("⚠", "[warning]"),
+    ("💡", "Tip:"),
+    ("🔄", "..."),
+    ("🔀✨", "Shuffle:"),
+    ("🔀", "Shuffle"),
+    ("🔂", "Repeat track"),
+    ("🔁", "Repeat"),
+    ("✨", ""),
+    ("❤️", "Liked"),
+    ("♥", "<3"),
+    ("♫", "(playing)"),
+    ("●", "*"),
+    ("💔", "Unliked"),
+    ("📻", "Radio:"),
+    ("👍", "up"),
+    ("👎", "down"),
+    ("🔊", "Volume"),
+    ("🔉", "Volume"),
+    ("🚀", ""),
+    ("🎵", ""),
+    ("🎶", ""),
+    ("🎤", ""),
+    ("🎧", ""),
+    ("🎚️", ""),
+    ("🔍", ""),
+    ("👤", ""),
+    ("💿", ""),
+    ("⏱️", ""),
+    ("⏱", ""),
+    ("🆔", ""),
+    ("🔑", ""),
+    ("🔐", ""),
+    ("🔗", ""),
+    ("📋", ""),
+    ("📊", ""),
+    ("↑↓", "Up/Down"),
+    ("←→", "Left/Right"),
+    ("↑", "Up"),
+    ("↓", "Down"),
+    ("←", "Left"),
+    ("→", "Right"),
+    ("═", "="),
+    ("─", "-"),
+    ("│", "|"),
+    ("┼", "+"),
+    ("║", "|"),
+    ("╔", "+"),
+    ("╗", "+"),
+    ("╚", "+"),
+    ("╝", "+"),
+    ("╠", "+"),
+    ("╣", "+"),
+    ("█", "#"),
+    ("░", "."),
+    ("•", "-"),
+    ("·", "-"),
+    ("▲", "^"),
+    ("›", ">"),
+    ("…", "..."),
+];
+
+/// Border set for ASCII mode: a single dashed line carrying the title.
+const ASCII_BORDER: border::Set = border::Set {
+    top_left: "-",
+    top_right: "-",
+    bottom_left: "-",
+    bottom_right: "-",
+    vertical_left: " ",
+    vertical_right: " ",
+    horizontal_top: "-",
+    horizontal_bottom: "-",
+};
+
+/// Decides how symbols are drawn for the active `RenderMode`.
+#[derive(Debug, Clone, Copy)]
+pub struct Glyphs {
+    pub mode: RenderMode,
+}
+
+impl Glyphs {
+    pub fn new(mode: RenderMode) -> Self {
+        Self { mode }
+    }
+
+    pub fn is_ascii(&self) -> bool {
+        self.mode == RenderMode::Ascii
+    }
+
+    pub fn toggle(&mut self) {
+        self.mode = match self.mode {
+            RenderMode::Unicode => RenderMode::Ascii,
+            RenderMode::Ascii => RenderMode::Unicode,
+        };
+    }
+
+    /// Returns `text` unchanged in Unicode mode. In ASCII mode every known
+    /// symbol is swapped for its ASCII spelling and any leftover emoji are
+    /// removed; letters from other scripts (CJK titles etc.) are kept.
+    pub fn text(&self, text: &str) -> String {
+        if !self.is_ascii() {
+            return text.to_string();
+        }
+
+        let mut result = text.to_string();
+        for (symbol, replacement) in ASCII_REPLACEMENTS {
+            if !result.contains(symbol) {
+                continue;
+            }
+            if replacement.is_empty() {
+                // Dropped icons take their trailing space with them
+                result = result.replace(&format!("{} ", symbol), "");
+            }
+            result = result.replace(symbol, replacement);
+        }
+
+        let mut stripped = String::with_capacity(result.len());
+        let mut chars = result.chars().peekable();
+        while let Some(c) = chars.next() {
+            if is_pictograph(c) {
+                if chars.peek() == Some(&' ') {
+                    chars.next();
+                }
+            } else {
+                stripped.push(c);
+            }
+        }
+        stripped
+    }
+
+    /// Applies `text` to every span, keeping the span styles.
+    pub fn line(&self, line: Line<'static>) -> Line<'static> {
+        if !self.is_ascii() {
+            return line;
+        }
+        let style = line.style;
+        let spans: Vec<Span<'static>> = line
+            .spans
+            .into_iter()
+            .map(|span| Span::styled(self.text(&span.content), span.style))
+            .collect();
+        Line::from(spans).style(style)
+    }
+
+    pub fn lines(&self, lines: Vec<Line<'static>>) -> Vec<Line<'static>> {
+        lines.into_iter().map(|line| self.line(line)).collect()
+    }
+
+    pub fn bar_filled(&self) -> &'static str {
+        if self.is_ascii() { "#" } else { "█" }
+    }
+
+    pub fn bar_empty(&self) -> &'static str {
+        if self.is_ascii() { "." } else { "░" }
+    }
+
+    pub fn ellipsis(&self) -> &'static str {
+        if self.is_ascii() { "..." } else { "…" }
+    }
+
+    pub fn border_set(&self) -> border::Set {
+        if self.is_ascii() { ASCII_BORDER } else { border::PLAIN }
+    }
+}
+
+/// Emoji, pictographs, dingbats and the invisible joiners that glue them together.
+fn is_pictograph(c: char) -> bool {
+    matches!(c as u32,
+        0x1F000..=0x1FAFF
+        | 0x2600..=0x27BF
+        | 0x2B00..=0x2BFF
+        | 0x2300..=0x23FF
+        | 0xFE0E..=0xFE0F
+        | 0x200D
+    )
+}
@@ -0,0 +1,157 @@
+use std::collections::HashMap;
+
+use ratatui::widgets::ListState;
+
+use crate::models::{Album, Artist, Playlist, ViewType};
+
+/// Older entries are dropped once the back stack grows past this.
+const MAX_HISTORY: usize = 50;
+
+/// What a nested view is showing.
+#[derive(Debug, Clone)]
+pub enum Context {
+    Playlist(Playlist),
+    Album(Album),
+    Artist(Artist),
+}
+
+/// A place the user can navigate to: a top-level view, or a view of one
+/// playlist, album or artist.
+#[derive(Debug, Clone)]
+pub struct Location {
+    pub view: ViewType,
+    pub context: Option<Context>,
+}
+
+impl Location {
+    pub fn view(view: ViewType) -> Self {
+        Self { view, context: None }
+    }
+
+    pub fn playlist(playlist: Playlist) -> Self {
+        Self { view: ViewType::PlaylistTracks, context: Some(Context::Playlist(playlist)) }
+    }
+
+    pub fn album(album: Album) -> Self {
+        Self { view: ViewType::AlbumTracks, context: Some(Context::Album(album)) }
+    }
+
+    pub fn artist(artist: Artist) -> Self {
+        Self { view: ViewType::ArtistAlbums, context: Some(Context::Artist(artist)) }
+    }
+
+    /// Identifies the list shown here, for remembering its selection.
+    pub fn key(&self) -> String {
+        match self.context {
+            Some(Context::Playlist(ref p)) => format!("playlist:{}", p.id),
+            Some(Context::Album(ref a)) => format!("album:{}", a.id),
+            Some(Context::Artist(ref a)) => format!("artist:{}", a.id),
+            None => format!("{:?}", self.view),
+        }
+    }
+
+    /// Label used in breadcrumbs.
+    pub fn label(&self) -> String {
+        match self.context {
+            Some(Context::Playlist(ref p)) => p.name.clone(),
+            Some(Context::Album(ref a)) => a.name.clone(),
+            Some(Context::Artist(ref a)) => a.name.clone(),
+            None => match self.view {
+                ViewType::Search => "Search",
+                ViewType::LikedSongs => "Liked Songs",
+                ViewType::Playlists => "Playlists",
+                ViewType::Queue => "Queue",
+                ViewType::Albums => "Albums",
+                ViewType::Artists => "Artists",
+                ViewType::Errors => "Errors/Logs",
+                ViewType::Player => "Now Playing",
+                ViewType::Party => "Party",
+                ViewType::PlaylistTracks => "Playlist",
+                ViewType::AlbumTracks => "Album",
+                ViewType::ArtistAlbums => "Artist",
+            }
+            .to_string(),
+        }
+    }
+}
+
+/// Back/forward stacks of visited locations, plus the list selection and
+/// scroll offset last used in each of them.
+#[derive(Debug)]
+pub struct History {
+    current: Location,
+    back: Vec<Location>,
+    forward: Vec<Location>,
+    selections: HashMap<String, ListState>,
+}
+
+impl History {
+    pub fn new(start: Location) -> Self {
+        Self {
+            current: start,
+            back: Vec::new(),
+            forward: Vec::new(),
+            selections: HashMap::new(),
+        }
+    }
+
+    pub fn current(&self) -> &Location {
+        &self.current
+    }
+
+    /// Moves to `location`, dropping anything ahead of the current one.
+    pub fn visit(&mut self, location: Location) {
+        if location.key() == self.current.key() {
+            self.current = location;
+            return;
+        }
+        let previous = std::mem::replace(&mut self.current, location);
+        self.back.push(previous);
+        if self.back.len() > MAX_HISTORY {
+            self.back.remove(0);
+        }
+        self.forward.clear();
+    }
+
+    pub fn back(&mut self) -> Option<Location> {
+        let location = self.back.pop()?;
+        let previous = std::mem::replace(&mut self.current, location);
+        self.forward.push(previous);
+        Some(self.current.clone())
+    }
+
+    pub fn forward(&mut self) -> Option<Location> {
+        let location = self.forward.pop()?;
+        let previous = std::mem::replace(&mut self.current, location);
+        self.back.push(previous);
+        Some(self.current.clone())
+    }
+
+    /// The chain of nested locations that led here, starting at the
+    /// top-level view they were opened from, e.g. Playlists › Mix › Artist.
+    pub fn breadcrumbs(&self) -> Vec<String> {
+        let mut trail = vec![self.current.label()];
+        if self.current.context.is_some() {
+            for location in self.back.iter().rev() {
+                trail.push(location.label());
+                if location.context.is_none() {
+                    break;
+                }
+            }
+        }
+        trail.reverse();
+        trail
+    }
+
+    pub fn save_selection(&mut self, state: &ListState) {
+        self.selections.insert(self.current.key(), state.clone());
+    }
+
+    /// Selection last used at the current location, or the first row.
+    pub fn selection(&self) -> ListState {
+        self.selections
+            .get(&self.current.key())
+            .cloned()
+            .unwrap_or_else(|| ListState::default().with_selected(Some(0)))
+    }
+}
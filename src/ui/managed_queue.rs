@@ -0,0 +1,131 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+use crate::models::Track;
+
+/// How long before the current track ends the next one is handed to Spotify.
+const FEED_AHEAD_MS: u64 = 10_000;
+
+/// Tracks the app holds on to and gives Spotify one at a time, just before
+/// the current track ends, so the order can still change until then.
+/// Spotify's own queue only takes appends, so this is where play next,
+/// remove and reorder happen. Kept between sessions.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ManagedQueue {
+    pub tracks: Vec<Track>,
+    /// Tracks at the front that were added with play next, so the next one
+    /// goes after them rather than jumping ahead.
+    #[serde(default)]
+    next_count: usize,
+    /// The track that was playing when one was last fed, so each track
+    /// feeds at most once.
+    #[serde(skip)]
+    fed_during: Option<String>,
+}
+
+impl ManagedQueue {
+    const STORAGE_FILE: &'static str = ".spotify_managed_queue";
+
+    pub fn load() -> Self {
+        if Path::new(Self::STORAGE_FILE).exists() {
+            if let Ok(content) = fs::read_to_string(Self::STORAGE_FILE) {
+                if let Some(queue) = Self::from_json(&content) {
+                    return queue;
+                }
+            }
+        }
+        Self::default()
+    }
+
+    /// Reads a saved queue, trimming a play-next count that runs past the
+    /// tracks.
+    pub fn from_json(content: &str) -> Option<Self> {
+        let mut queue = serde_json::from_str::<ManagedQueue>(content).ok()?;
+        queue.next_count = queue.next_count.min(queue.tracks.len());
+        Some(queue)
+    }
+
+    pub fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let content = serde_json::to_string_pretty(self)?;
+        fs::write(Self::STORAGE_FILE, content)?;
+        Ok(())
+    }
+
+    /// Play later: after everything already waiting.
+    pub fn push_back(&mut self, track: Track) {
+        self.tracks.push(track);
+    }
+
+    /// Play next: ahead of everything added with play later, after earlier
+    /// play-next tracks, keeping `tracks` in order.
+    pub fn play_next(&mut self, tracks: Vec<Track>) {
+        let added = tracks.len();
+        self.tracks.splice(self.next_count..self.next_count, tracks);
+        self.next_count += added;
+    }
+
+    pub fn remove(&mut self, index: usize) -> Option<Track> {
+        if index >= self.tracks.len() {
+            return None;
+        }
+        if index < self.next_count {
+            self.next_count -= 1;
+        }
+        Some(self.tracks.remove(index))
+    }
+
+    /// Swaps the track at `index` with its neighbour, returning where it
+    /// ended up. A track moved past the last play-next track changes sides;
+    /// the one it passed keeps its own.
+    pub fn move_by(&mut self, index: usize, up: bool) -> Option<usize> {
+        let to = if up { index.checked_sub(1)? } else { index + 1 };
+        if to >= self.tracks.len() {
+            return None;
+        }
+        self.tracks.swap(index, to);
+        if up && index == self.next_count {
+            self.next_count += 1;
+        } else if !up && to == self.next_count {
+            self.next_count -= 1;
+        }
+        Some(to)
+    }
+
+    /// How many tracks at the front were added with play next.
+    pub fn next_count(&self) -> usize {
+        self.next_count
+    }
+
+    /// Drops the tracks `keep` turns down.
+    pub fn retain(&mut self, mut keep: impl FnMut(&Track) -> bool) {
+        let mut index = 0;
+        let mut dropped_next = 0;
+        self.tracks.retain(|track| {
+            let kept = keep(track);
+            if !kept && index < self.next_count {
+                dropped_next += 1;
+            }
+            index += 1;
+            kept
+        });
+        self.next_count -= dropped_next;
+    }
+
+    pub fn clear(&mut self) {
+        self.tracks.clear();
+        self.next_count = 0;
+    }
+
+    /// Whether to feed now, with `playing_id` this far from its end.
+    pub fn is_due(&self, playing_id: &str, remaining_ms: u64) -> bool {
+        remaining_ms <= FEED_AHEAD_MS && self.fed_during.as_deref() != Some(playing_id)
+    }
+
+    /// The next track to feed, remembering it went out during `playing_id`.
+    pub fn take_for(&mut self, playing_id: &str) -> Option<Track> {
+        let track = self.remove(0)?;
+        self.fed_during = Some(playing_id.to_string());
+        Some(track)
+    }
+}
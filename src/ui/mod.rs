@@ -1,29 +1,188 @@
 use crossterm::{
-    event::{self, Event, KeyCode, KeyEventKind},
+    event::{self, DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture, Event, KeyCode, KeyEvent, KeyEventKind, MouseButton, MouseEvent, MouseEventKind},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
 use ratatui::{
     backend::CrosstermBackend,
-    layout::{Constraint, Direction, Layout, Rect},
-    style::{Color, Modifier, Style},
+    layout::{Constraint, Direction, Flex, Layout, Rect},
+    style::Style,
+    text::{Line, Span},
     widgets::{
-        Block, Borders, List, ListItem, ListState, Paragraph, Wrap,
+        Block, Borders, Clear, Gauge, List, ListItem, ListState, Paragraph, Wrap,
     },
     Frame, Terminal,
 };
 use std::io;
-
-use crate::models::{AppState, ViewType, ShuffleMode};
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+mod clipboard;
+mod command_line;
+mod context_menu;
+mod glyphs;
+mod history;
+mod managed_queue;
+mod mouse;
+mod navigation;
+mod playback_clock;
+mod selection;
+mod text;
+mod theme;
+mod track_table;
+mod track_view;
+
+use crate::commands::{Command, RepeatMode};
+use crate::models::{Album, AppState, Artist, CurrentPlayback, Playlist, ViewType, ShuffleMode, Track, UserProfile};
 use crate::api::SpotifyClient;
 use crate::auth::SpotifyAuth;
+use crate::config::Config;
+use crate::cli::Failure;
+use crate::daemon::{Adjust, DaemonClient, Request};
+use crate::events::{PlaybackEvent, PlaybackEvents};
+use crate::party::{artist_names, Party, SharedParty};
+use crate::radio::{Station, StationCommand, StationEvent, StationHandle};
+use crate::uri::{ItemKind, SpotifyUri};
+use std::collections::{HashMap, HashSet};
+
+use command_line::{CommandHistory, CommandLine};
+use context_menu::{Capabilities, ContextMenu, MenuAction, MenuItem, MenuTarget};
+use history::{Context, History, Location};
+use selection::Selection;
+use track_table::TrackTable;
+
+pub use glyphs::Glyphs;
+pub use managed_queue::ManagedQueue;
+pub use mouse::{ClickTracker, HitAreas};
+pub use navigation::{find_next, target, Jump, ListNavigator, Motion, NavResult};
+pub use playback_clock::PlaybackClock;
+pub use text::{display_width, pad_left_to_width, truncate_to_width};
+pub use theme::{ColorSupport, Theme};
+pub use track_view::{SortDirection, TrackView};
+
+/// How often to ask an attached daemon for playback.
+const DAEMON_POLL: Duration = Duration::from_secs(1);
+
+/// Tracks radio adds each time the queue runs low.
+const RADIO_TOP_UP: usize = 5;
+
+/// Sidebar navigation entries, in the order of their `1`-`8` shortcuts.
+const NAV_ENTRIES: [(&str, ViewType); 8] = [
+    ("Search", ViewType::Search),
+    ("Liked Songs", ViewType::LikedSongs),
+    ("Playlists", ViewType::Playlists),
+    ("Queue", ViewType::Queue),
+    ("Albums", ViewType::Albums),
+    ("Artists", ViewType::Artists),
+    ("Errors/Logs", ViewType::Errors),
+    ("Now Playing", ViewType::Player),
+];
+
+/// Panes that can hold keyboard focus, cycled with Tab / Shift-Tab.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Focus {
+    Sidebar,
+    Main,
+    Preview,
+}
+
+/// Popup for choosing the playlist that tracks are added to.
+pub struct PlaylistPicker {
+    pub uris: Vec<SpotifyUri>,
+    pub state: ListState,
+}
+
+/// What Spotify is playing from, named for the Now Playing view.
+pub struct PlayingContext {
+    pub uri: String,
+    /// e.g. `Playlist`.
+    pub kind: String,
+    /// e.g. `Gym`.
+    pub name: String,
+}
+
+/// Party mode while it's on: the guest page's server and what it shares
+/// with the Party panel. Dropping it stops the server.
+pub struct PartySession {
+    pub party: SharedParty,
+    /// Where guests go, e.g. `http://192.168.1.20:8080`.
+    pub url: String,
+    server: tokio::task::JoinHandle<()>,
+}
+
+impl Drop for PartySession {
+    fn drop(&mut self) {
+        self.server.abort();
+    }
+}
 
 pub struct App {
     pub state: AppState,
     pub list_state: ListState,
     pub input_mode: bool,
+    /// Typing into the current list's filter (`/` outside the search view).
+    pub filter_input: bool,
+    /// Sort and filter per track list, kept while switching views.
+    pub track_views: HashMap<ViewType, TrackView>,
+    pub navigator: ListNavigator,
+    /// Rows visible in the main list, updated on every draw for paging.
+    pub page_size: usize,
+    /// Clickable regions from the last draw.
+    pub hit_areas: HitAreas,
+    pub clicks: ClickTracker,
+    /// Volume shown while dragging the volume bar; sent to Spotify on release.
+    pub volume_drag: Option<u8>,
+    pub focus: Focus,
+    /// Pane currently being drawn, so `block` can mark the focused one.
+    drawing_pane: Option<Focus>,
+    /// Selected sidebar row: navigation entries first, then playlists.
+    pub sidebar_selected: usize,
+    pub sidebar_playlists_state: ListState,
+    pub preview_scroll: u16,
+    /// Visited views for back/forward, with each one's remembered selection.
+    pub history: History,
+    /// Tracks marked for bulk actions in the current list.
+    pub selection: Selection,
+    pub playlist_picker: Option<PlaylistPicker>,
+    pub context_menu: Option<ContextMenu>,
+    /// The `:` prompt, while it is open.
+    pub command_line: Option<CommandLine>,
+    pub command_history: CommandHistory,
+    /// Device names for completing `:device`, fetched on the first Tab.
+    pub device_names: Vec<String>,
+    /// Resolved once per context URI, not on every sync.
+    pub playing_context: Option<PlayingContext>,
+    /// Playback position between polls.
+    pub clock: PlaybackClock,
+    /// When to poll Spotify's playback state next.
+    pub next_sync: Instant,
+    /// Changes between polls, for anything that wants to react to them.
+    pub events: PlaybackEvents,
+    /// A running `spotycli daemon` to read playback from instead of polling
+    /// Spotify; dropped if it goes away.
+    pub daemon: Option<DaemonClient>,
+    /// `d` was pressed once; the next `d` removes the tracks.
+    pub confirm_remove: bool,
+    /// Tracks fed to Spotify one at a time as the current one ends.
+    pub managed_queue: ManagedQueue,
+    /// Spotify's own queue as last fetched; the Queue view shows it after
+    /// the managed tracks.
+    pub spotify_queue: Vec<Track>,
+    pub party: Option<PartySession>,
+    /// The radio station topping up the queue, while one is on.
+    pub radio: Option<StationHandle>,
     pub spotify_client: Option<SpotifyClient>,
     pub auth_client: Option<SpotifyAuth>,
+    pub config: Config,
+    pub theme: Theme,
+    pub glyphs: Glyphs,
+}
+
+impl Default for App {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl App {
@@ -31,25 +190,77 @@ impl App {
         let mut list_state = ListState::default();
         list_state.select(Some(0));
 
-        Self {
+        let (config, config_error) = Config::load();
+        let (theme, theme_warnings) = Theme::from_config(&config, ColorSupport::detect());
+
+        let mut app = Self {
             state: AppState::default(),
             list_state,
             input_mode: false,
+            filter_input: false,
+            track_views: HashMap::new(),
+            navigator: ListNavigator::default(),
+            page_size: 10,
+            hit_areas: HitAreas::default(),
+            clicks: ClickTracker::default(),
+            volume_drag: None,
+            focus: Focus::Main,
+            drawing_pane: None,
+            sidebar_selected: 0,
+            sidebar_playlists_state: ListState::default(),
+            preview_scroll: 0,
+            history: History::new(Location::view(ViewType::Search)),
+            selection: Selection::default(),
+            playlist_picker: None,
+            context_menu: None,
+            command_line: None,
+            command_history: CommandHistory::load(),
+            device_names: Vec::new(),
+            playing_context: None,
+            clock: PlaybackClock::default(),
+            next_sync: Instant::now(),
+            events: PlaybackEvents::new(),
+            daemon: None,
+            confirm_remove: false,
+            managed_queue: ManagedQueue::load(),
+            spotify_queue: Vec::new(),
+            party: None,
+            radio: None,
             spotify_client: None,
             auth_client: None,
+            glyphs: Glyphs::new(config.render_mode),
+            config,
+            theme,
+        };
+
+        if let Some(error) = config_error {
+            app.log_error(format!("❌ {}", error));
+        }
+        for warning in theme_warnings {
+            app.log_error(format!("⚠️ {}", warning));
         }
+        app.show_queue();
+
+        app
     }
 
     pub fn set_spotify_client(&mut self, client: SpotifyClient) {
         self.spotify_client = Some(client);
     }
 
+    pub fn set_daemon(&mut self, daemon: DaemonClient) {
+        self.daemon = Some(daemon);
+    }
+
     pub fn set_auth_client(&mut self, client: SpotifyAuth) {
         self.auth_client = Some(client);
     }
 
 
     async fn trigger_search(&mut self) {
+        // New results, so marks on the old ones no longer apply
+        self.selection.clear();
+
         if self.state.search_query.is_empty() {
             // Empty search - show recently played tracks
             self.state.search_results = None;
@@ -84,6 +295,8 @@ impl App {
                                 duration_ms: 0,
                                 popularity: 0,
                                 preview_url: None,
+                                added_at: None,
+                                added_by: None,
                             },
                         ];
 
@@ -202,7 +415,8 @@ impl App {
                 self.state.auth_message = "🔄 Loading queue...".to_string();
                 match client.get_queue().await {
                     Ok(response) => {
-                        self.state.queue = response.queue;
+                        self.spotify_queue = response.queue;
+                        self.show_queue();
                         self.state.auth_message = format!("✅ Loaded {} tracks in queue", self.state.queue.len());
                     },
                     Err(e) => {
@@ -217,7 +431,9 @@ impl App {
         }
     }
 
-    pub async fn load_selected_playlist_tracks(&mut self, playlist_id: &str) {
+    /// Loads a playlist's tracks into `selected_playlist_tracks`. Returns
+    /// whether anything was loaded; the caller switches the view.
+    pub async fn load_selected_playlist_tracks(&mut self, playlist_id: &str) -> bool {
         if self.state.user_authenticated {
             if let Some(ref client) = self.spotify_client {
                 self.state.auth_message = "🔄 Loading ALL playlist tracks...".to_string();
@@ -225,11 +441,10 @@ impl App {
                     Ok(all_items) => {
                         self.state.selected_playlist_tracks = all_items
                             .into_iter()
-                            .filter_map(|item| item.track)
+                            .filter_map(|item| item.into_track())
                             .collect();
-                        self.state.current_view = ViewType::PlaylistTracks;
-                        self.list_state.select(Some(0)); // Reset selection to first item
                         self.state.auth_message = format!("✅ Loaded ALL {} tracks", self.state.selected_playlist_tracks.len());
+                        return true;
                     },
                     Err(e) => {
                         self.state.auth_message = format!("❌ Failed to load all tracks: {}", e);
@@ -239,16 +454,14 @@ impl App {
                                 if let Some(items) = tracks_response.items {
                                     self.state.selected_playlist_tracks = items
                                         .into_iter()
-                                        .filter_map(|item| item.track)
+                                        .filter_map(|item| item.into_track())
                                         .collect();
-                                    self.state.current_view = ViewType::PlaylistTracks;
-                                    self.list_state.select(Some(0)); // Reset selection to first item
                                     self.state.auth_message = format!("⚠️ Loaded first {} tracks only", self.state.selected_playlist_tracks.len());
                                 } else {
                                     self.state.selected_playlist_tracks = Vec::new();
-                                    self.state.current_view = ViewType::PlaylistTracks;
                                     self.state.auth_message = "⚠️ Playlist has no tracks".to_string();
                                 }
+                                return true;
                             },
                             Err(_) => {
                                 self.state.auth_message = format!("❌ Failed to load playlist tracks: {}", e);
@@ -262,6 +475,7 @@ impl App {
         } else {
             self.state.auth_message = "❌ Authentication required to load playlist tracks".to_string();
         }
+        false
     }
 
     pub async fn load_liked_songs(&mut self) {
@@ -272,10 +486,8 @@ impl App {
                     Ok(all_items) => {
                         let mut tracks = Vec::new();
                         for item in all_items {
-                            if let Some(track_obj) = item.get("track") {
-                                if let Ok(track) = serde_json::from_value::<crate::models::Track>(track_obj.clone()) {
-                                    tracks.push(track);
-                                }
+                            if let Ok(saved) = serde_json::from_value::<crate::models::SavedTrack>(item) {
+                                tracks.push(saved.into_track());
                             }
                         }
                         self.state.liked_songs = tracks;
@@ -289,10 +501,8 @@ impl App {
                                 if let Some(items) = response.get("items").and_then(|v| v.as_array()) {
                                     let mut tracks = Vec::new();
                                     for item in items {
-                                        if let Some(track_obj) = item.get("track") {
-                                            if let Ok(track) = serde_json::from_value::<crate::models::Track>(track_obj.clone()) {
-                                                tracks.push(track);
-                                            }
+                                        if let Ok(saved) = serde_json::from_value::<crate::models::SavedTrack>(item.clone()) {
+                                            tracks.push(saved.into_track());
                                         }
                                     }
                                     self.state.liked_songs = tracks;
@@ -318,387 +528,1526 @@ impl App {
 
     async fn open_selected_playlist(&mut self) {
         if let Some(selected) = self.list_state.selected() {
-            if selected < self.state.user_playlists.len() {
-                let playlist = self.state.user_playlists[selected].clone();
-                self.state.selected_playlist = Some(playlist.clone());
-                self.load_selected_playlist_tracks(&playlist.id).await;
-            }
+            self.open_playlist_at(selected).await;
         }
     }
 
-    async fn play_selected_track(&mut self) {
-        if let Some(selected) = self.list_state.selected() {
-            let track = match self.state.current_view {
-                ViewType::Search => {
-                    if let Some(ref search_results) = self.state.search_results {
-                        if let Some(ref tracks) = search_results.tracks {
-                            if selected < tracks.items.len() {
-                                Some(tracks.items[selected].clone())
-                            } else {
-                                None
-                            }
-                        } else {
-                            None
-                        }
-                    } else {
-                        // Recently played tracks
-                        if selected < self.state.recently_played.len() {
-                            Some(self.state.recently_played[selected].clone())
-                        } else {
-                            None
-                        }
-                    }
-                }
-                ViewType::PlaylistTracks => {
-                    if selected < self.state.selected_playlist_tracks.len() {
-                        Some(self.state.selected_playlist_tracks[selected].clone())
-                    } else {
-                        None
-                    }
-                }
-                ViewType::LikedSongs => {
-                    if !self.state.liked_songs.is_empty() {
-                        // Use actual liked songs
-                        if selected < self.state.liked_songs.len() {
-                            Some(self.state.liked_songs[selected].clone())
-                        } else {
-                            None
-                        }
-                    } else {
-                        // Use sample liked songs from recently played for now
-                        if selected < self.state.recently_played.len() {
-                            Some(self.state.recently_played[selected].clone())
-                        } else {
-                            None
-                        }
-                    }
-                }
-                ViewType::Queue => {
-                    if selected < self.state.queue.len() {
-                        Some(self.state.queue[selected].clone())
-                    } else {
-                        None
-                    }
-                }
-                _ => None,
-            };
-
-            if let Some(track) = track {
-                if self.state.user_authenticated {
-                    if let Some(ref client) = self.spotify_client {
-                        let client_clone = client.clone(); // Clone early to avoid borrowing issues
-                        let play_result = match self.state.current_view {
-                            ViewType::PlaylistTracks => {
-                                // Play playlist with context for continuous playback
-                                if let Some(ref playlist) = self.state.selected_playlist {
-                                    let playlist_uri = playlist.uri.clone()
-                                        .unwrap_or_else(|| format!("spotify:playlist:{}", playlist.id));
-                                    client.play_playlist_with_offset(&playlist_uri, selected).await
-                                } else {
-                                    // Fallback to playing individual track
-                                    client.play_track(&track.uri).await
-                                }
-                            }
-                            ViewType::LikedSongs => {
-                                // Play liked songs with context
-                                let track_uris: Vec<String> = self.state.liked_songs.iter()
-                                    .map(|t| t.uri.clone())
-                                    .collect();
-                                if !track_uris.is_empty() {
-                                    client.play_tracks_with_offset(&track_uris, selected).await
-                                } else {
-                                    // Fallback to recently played
-                                    let track_uris: Vec<String> = self.state.recently_played.iter()
-                                        .map(|t| t.uri.clone())
-                                        .collect();
-                                    client.play_tracks_with_offset(&track_uris, selected).await
-                                }
-                            }
-                            ViewType::Search | ViewType::Albums | ViewType::Artists | ViewType::Queue => {
-                                // For individual tracks from search/albums/artists/queue, start radio to continue with similar songs
-                                match client.start_radio_from_track(&track.uri).await {
-                                    Ok(logs) => {
-                                        // Add all radio logs to the error logs tab
-                                        for log in logs {
-                                            self.log_radio(log);
-                                        }
-                                        Ok(())
-                                    }
-                                    Err(e) => Err(e)
-                                }
-                            }
-                            _ => {
-                                // For other views like recently played, also start radio
-                                match client.start_radio_from_track(&track.uri).await {
-                                    Ok(logs) => {
-                                        // Add all radio logs to the error logs tab
-                                        for log in logs {
-                                            self.log_radio(log);
-                                        }
-                                        Ok(())
-                                    }
-                                    Err(e) => Err(e)
-                                }
-                            }
-                        };
-
-                        match play_result {
-                            Ok(_) => {
-                                // Clear the current queue when starting a new song
-                                self.state.queue.clear();
-                                self.log_radio("🔄 Queue cleared - starting fresh".to_string());
-
-                                // Load the new queue after a short delay to allow Spotify to populate it
-                                tokio::spawn({
-                                    let client_for_spawn = client_clone.clone();
-                                    async move {
-                                        // Wait a moment for Spotify to populate the queue with new tracks
-                                        tokio::time::sleep(tokio::time::Duration::from_millis(1500)).await;
-                                        let _ = client_for_spawn.get_queue().await;
-                                    }
-                                });
-
-                                let message = match self.state.current_view {
-                                    ViewType::Search | ViewType::Albums | ViewType::Artists | ViewType::Queue => {
-                                        format!("📻 Starting radio: {} (Building playlist with similar tracks...)", track.name)
-                                    }
-                                    ViewType::PlaylistTracks => {
-                                        format!("▶ Playing from playlist: {}", track.name)
-                                    }
-                                    ViewType::LikedSongs => {
-                                        format!("❤️ Playing from liked songs: {}", track.name)
-                                    }
-                                    _ => {
-                                        format!("📻 Starting radio: {} (Building playlist with similar tracks...)", track.name)
-                                    }
-                                };
-                                self.state.auth_message = message;
-                                self.state.current_track = Some(track.clone());
-                                self.state.is_playing = true;
-
-                                // Add to recently played storage
-                                self.state.recently_played_storage.add_track(track, None);
-                                let _ = self.state.recently_played_storage.save();
+    async fn open_playlist_at(&mut self, index: usize) {
+        if let Some(playlist) = self.state.user_playlists.get(index).cloned() {
+            self.open_playlist(playlist).await;
+        }
+    }
 
-                                // Sync with Spotify after a short delay
-                                tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
-                                self.sync_playback_state().await;
-                            }
-                            Err(e) => {
-                                self.log_error(format!("❌ PLAY ERROR: {}", e));
-                                let error_msg = e.to_string();
-                                if error_msg.contains("NO_ACTIVE_DEVICE") {
-                                    self.state.auth_message = "❌ No active device! Open Spotify app first.".to_string();
-                                } else if error_msg.contains("PREMIUM_REQUIRED") {
-                                    self.state.auth_message = "❌ Spotify Premium required for playback.".to_string();
-                                } else {
-                                    self.state.auth_message = format!("❌ Play error: {}", e);
-                                }
-                            }
-                        }
-                    }
-                } else {
-                    self.state.auth_message = "❌ Authentication required for playback".to_string();
-                }
-            }
+    async fn open_playlist(&mut self, playlist: Playlist) {
+        if self.load_selected_playlist_tracks(&playlist.id).await {
+            self.track_views.remove(&ViewType::PlaylistTracks);
+            self.navigate(Location::playlist(playlist));
         }
     }
 
-    async fn toggle_playback(&mut self) {
+    pub async fn load_saved_albums(&mut self) {
         if self.state.user_authenticated {
             if let Some(ref client) = self.spotify_client {
-                let result = if self.state.is_playing {
-                    client.pause_playback().await
-                } else {
-                    client.resume_playback().await
-                };
-
-                match result {
-                    Ok(_) => {
-                        self.state.is_playing = !self.state.is_playing;
-                        self.state.auth_message = format!("🎵 {}", if self.state.is_playing { "Resumed" } else { "Paused" });
-
-                        // Sync with Spotify after a short delay
-                        tokio::time::sleep(tokio::time::Duration::from_millis(300)).await;
-                        self.sync_playback_state().await;
+                self.state.auth_message = "🔄 Loading saved albums...".to_string();
+                match client.get_all_saved_albums().await {
+                    Ok(albums) => {
+                        self.state.user_albums = albums;
+                        self.state.auth_message = format!("✅ Loaded {} saved albums", self.state.user_albums.len());
                     },
                     Err(e) => {
-                        self.state.auth_message = format!("❌ Playback error: {}", e);
+                        self.state.auth_message = format!("❌ Failed to load saved albums: {}", e);
                     }
                 }
+            } else {
+                self.state.auth_message = "❌ No Spotify client available".to_string();
             }
+        } else {
+            self.state.auth_message = "❌ Authentication required to load albums".to_string();
         }
     }
 
-    async fn next_track(&mut self) {
-        if self.state.user_authenticated {
-            if let Some(ref client) = self.spotify_client {
-                match client.next_track().await {
-                    Ok(_) => {
-                        self.state.auth_message = "⏭ Next track".to_string();
+    pub async fn load_followed_artists(&mut self) {
+        if !self.state.user_authenticated {
+            self.state.auth_message = "❌ Authentication required to load artists".to_string();
+            return;
+        }
+        let Some(ref client) = self.spotify_client else {
+            self.state.auth_message = "❌ No Spotify client available".to_string();
+            return;
+        };
+        if !client.granted_scopes().iter().any(|s| s == "user-follow-read") {
+            self.state.auth_message = "⚠️ Press 'u' to sign in again and allow reading the artists you follow".to_string();
+            return;
+        }
+        self.state.auth_message = "🔄 Loading followed artists...".to_string();
+        match client.get_all_followed_artists().await {
+            Ok(artists) => {
+                self.state.user_artists = artists;
+                self.state.auth_message = format!("✅ Loaded {} followed artists", self.state.user_artists.len());
+            }
+            Err(e) => {
+                self.state.auth_message = format!("❌ Failed to load followed artists: {}", e);
+            }
+        }
+    }
 
-                        // Sync with Spotify after a delay to allow track change
-                        tokio::time::sleep(tokio::time::Duration::from_millis(800)).await;
-                        self.sync_playback_state().await;
-                    },
-                    Err(e) => {
-                        self.state.auth_message = format!("❌ Next track error: {}", e);
-                    }
+    async fn open_selected_album(&mut self) {
+        let albums = match self.state.current_view {
+            ViewType::ArtistAlbums => &self.state.selected_artist_albums,
+            _ => &self.state.user_albums,
+        };
+        if let Some(album) = self.list_state.selected().and_then(|i| albums.get(i)).cloned() {
+            self.open_album(album).await;
+        }
+    }
+
+    async fn open_album(&mut self, album: Album) {
+        if self.load_album_tracks(&album).await {
+            self.track_views.remove(&ViewType::AlbumTracks);
+            self.navigate(Location::album(album));
+        }
+    }
+
+    async fn load_album_tracks(&mut self, album: &Album) -> bool {
+        if let Some(ref client) = self.spotify_client {
+            self.state.auth_message = format!("🔄 Loading {}...", album.name);
+            match client.get_all_album_tracks(album).await {
+                Ok(tracks) => {
+                    self.state.selected_album_tracks = tracks;
+                    self.state.auth_message = format!("✅ Loaded {} tracks", self.state.selected_album_tracks.len());
+                    return true;
+                },
+                Err(e) => {
+                    self.state.auth_message = format!("❌ Failed to load album tracks: {}", e);
                 }
             }
         } else {
-            self.state.auth_message = "❌ Authentication required".to_string();
+            self.state.auth_message = "❌ No Spotify client available".to_string();
         }
+        false
     }
 
-    async fn previous_track(&mut self) {
-        if self.state.user_authenticated {
-            if let Some(ref client) = self.spotify_client {
-                match client.previous_track().await {
-                    Ok(_) => {
-                        self.state.auth_message = "⏮ Previous track".to_string();
+    async fn open_artist(&mut self, artist: Artist) {
+        if self.load_artist_albums(&artist).await {
+            self.navigate(Location::artist(artist));
+        }
+    }
 
-                        // Sync with Spotify after a delay to allow track change
-                        tokio::time::sleep(tokio::time::Duration::from_millis(800)).await;
-                        self.sync_playback_state().await;
-                    },
-                    Err(e) => {
-                        self.state.auth_message = format!("❌ Previous track error: {}", e);
-                    }
+    async fn load_artist_albums(&mut self, artist: &Artist) -> bool {
+        if let Some(ref client) = self.spotify_client {
+            self.state.auth_message = format!("🔄 Loading albums by {}...", artist.name);
+            match client.get_all_artist_albums(&artist.id).await {
+                Ok(albums) => {
+                    self.state.selected_artist_albums = albums;
+                    self.state.auth_message = format!("✅ Loaded {} albums", self.state.selected_artist_albums.len());
+                    return true;
+                },
+                Err(e) => {
+                    self.state.auth_message = format!("❌ Failed to load artist albums: {}", e);
                 }
             }
         } else {
-            self.state.auth_message = "❌ Authentication required".to_string();
+            self.state.auth_message = "❌ No Spotify client available".to_string();
         }
+        false
     }
 
-    async fn toggle_shuffle(&mut self) {
-        if self.state.user_authenticated {
-            if let Some(ref client) = self.spotify_client {
-                // Cycle through: Off -> On -> SmartShuffle -> Off
-                let (new_mode, result) = match self.state.shuffle_mode {
-                    ShuffleMode::Off => {
-                        (ShuffleMode::On, client.set_shuffle(true).await)
-                    }
-                    ShuffleMode::On => {
-                        (ShuffleMode::SmartShuffle, client.set_smart_shuffle(true).await)
-                    }
-                    ShuffleMode::SmartShuffle => {
-                        (ShuffleMode::Off, client.set_shuffle(false).await)
-                    }
-                };
+    /// `ga`: open the first artist of the selected track.
+    async fn go_to_artist(&mut self) {
+        let artist = self.list_state.selected()
+            .and_then(|row| self.track_at(row))
+            .and_then(|track| track.artists.into_iter().next());
+        match artist {
+            Some(artist) => self.open_artist(artist).await,
+            None => self.state.auth_message = "⚠️ Select a track to go to its artist".to_string(),
+        }
+    }
 
-                match result {
-                    Ok(_) => {
-                        self.state.shuffle_mode = new_mode.clone();
-                        let mode_text = match new_mode {
-                            ShuffleMode::Off => "🔀 Shuffle: Off",
-                            ShuffleMode::On => "🔀 Shuffle: On",
-                            ShuffleMode::SmartShuffle => "🔀 Smart Shuffle: On",
-                        };
-                        self.state.auth_message = mode_text.to_string();
+    /// `gb`: open the album of the selected track.
+    async fn go_to_album(&mut self) {
+        let album = self.list_state.selected()
+            .and_then(|row| self.track_at(row))
+            .and_then(|track| track.album);
+        match album {
+            Some(album) => self.open_album(album).await,
+            None => self.state.auth_message = "⚠️ Select a track to go to its album".to_string(),
+        }
+    }
 
-                        // Sync after a short delay
-                        tokio::time::sleep(tokio::time::Duration::from_millis(300)).await;
-                        self.sync_playback_state().await;
-                    },
-                    Err(e) => {
-                        self.log_error(format!("❌ SHUFFLE ERROR: {}", e));
-                        let error_msg = e.to_string();
-                        if error_msg.contains("NO_ACTIVE_DEVICE") {
-                            self.state.auth_message = "❌ No active device! Open Spotify app first.".to_string();
-                        } else if error_msg.contains("PREMIUM_REQUIRED") {
-                            self.state.auth_message = "❌ Spotify Premium required for shuffle control.".to_string();
-                        } else {
-                            self.state.auth_message = format!("❌ Shuffle error: {}", e);
-                        }
-                    }
-                }
+    /// Shows `location`, remembering the current one so Back can return to it.
+    fn navigate(&mut self, location: Location) {
+        self.history.save_selection(&self.list_state);
+        self.history.visit(location);
+        self.enter_current_location();
+    }
+
+    async fn go_back(&mut self) {
+        self.history.save_selection(&self.list_state);
+        match self.history.back() {
+            Some(location) => {
+                self.ensure_loaded(&location).await;
+                self.enter_current_location();
             }
-        } else {
-            self.state.auth_message = "❌ Authentication required for shuffle control".to_string();
+            None => self.state.auth_message = "⚠️ Nothing to go back to".to_string(),
         }
     }
 
-    async fn toggle_like_selected_track(&mut self) {
-        let user_authenticated = self.state.user_authenticated;
-        let current_view = self.state.current_view.clone();
-        let selected = self.list_state.selected();
+    async fn go_forward(&mut self) {
+        self.history.save_selection(&self.list_state);
+        match self.history.forward() {
+            Some(location) => {
+                self.ensure_loaded(&location).await;
+                self.enter_current_location();
+            }
+            None => self.state.auth_message = "⚠️ Nothing to go forward to".to_string(),
+        }
+    }
 
-        if let Some(selected) = selected {
-            let track = match current_view {
-                ViewType::Search => {
-                    if let Some(ref search_results) = self.state.search_results {
-                        if let Some(ref tracks) = search_results.tracks {
-                            if selected < tracks.items.len() {
-                                Some(tracks.items[selected].clone())
-                            } else {
-                                None
-                            }
-                        } else {
-                            None
-                        }
-                    } else {
-                        if selected < self.state.recently_played.len() {
-                            Some(self.state.recently_played[selected].clone())
-                        } else {
-                            None
-                        }
-                    }
-                }
-                ViewType::PlaylistTracks => {
-                    if selected < self.state.selected_playlist_tracks.len() {
-                        Some(self.state.selected_playlist_tracks[selected].clone())
-                    } else {
-                        None
-                    }
-                }
-                ViewType::LikedSongs => {
-                    if !self.state.liked_songs.is_empty() {
-                        if selected < self.state.liked_songs.len() {
-                            Some(self.state.liked_songs[selected].clone())
-                        } else {
-                            None
-                        }
-                    } else {
-                        if selected < self.state.recently_played.len() {
-                            Some(self.state.recently_played[selected].clone())
-                        } else {
-                            None
-                        }
-                    }
-                }
-                ViewType::Queue => {
-                    if selected < self.state.queue.len() {
-                        Some(self.state.queue[selected].clone())
-                    } else {
-                        None
-                    }
-                }
-                _ => {
-                    if selected < self.state.recently_played.len() {
-                        Some(self.state.recently_played[selected].clone())
-                    } else {
-                        None
-                    }
-                }
-            };
+    /// Reloads a nested view's contents if another playlist, album or
+    /// artist has been opened since it was last shown.
+    async fn ensure_loaded(&mut self, location: &Location) {
+        match location.context {
+            Some(Context::Playlist(ref playlist))
+                if self.state.selected_playlist.as_ref().map(|p| &p.id) != Some(&playlist.id) =>
+            {
+                self.load_selected_playlist_tracks(&playlist.id).await;
+            }
+            Some(Context::Album(ref album))
+                if self.state.selected_album.as_ref().map(|a| &a.id) != Some(&album.id) =>
+            {
+                self.load_album_tracks(album).await;
+            }
+            Some(Context::Artist(ref artist))
+                if self.state.selected_artist.as_ref().map(|a| &a.id) != Some(&artist.id) =>
+            {
+                self.load_artist_albums(artist).await;
+            }
+            _ => {}
+        }
+    }
 
-            if let Some(track) = track {
-                if user_authenticated {
-                    if let Some(client) = self.spotify_client.clone() {
-                        // First check if the track is already liked
-                        match client.check_if_liked(&track.id).await {
-                            Ok(is_liked) => {
-                                let result = if is_liked {
-                                    client.unlike_song(&track.id).await
-                                } else {
-                                    client.like_song(&track.id).await
-                                };
+    /// Makes the history's current location the visible view.
+    fn enter_current_location(&mut self) {
+        let location = self.history.current().clone();
+        self.state.current_view = location.view;
+        match location.context {
+            Some(Context::Playlist(playlist)) => self.state.selected_playlist = Some(playlist),
+            Some(Context::Album(album)) => self.state.selected_album = Some(album),
+            Some(Context::Artist(artist)) => self.state.selected_artist = Some(artist),
+            None => {}
+        }
+        self.list_state = self.history.selection();
+    }
 
-                                match result {
-                                    Ok(_) => {
-                                        let action = if is_liked { "💔 Removed from" } else { "❤️ Added to" };
+    /// Pane title for nested views, e.g. `Playlists › Road Trip › Daft Punk`.
+    fn breadcrumb_title(&self, hints: &str) -> String {
+        format!(
+            "{} {}",
+            self.history.breadcrumbs().join(&self.glyphs.text(" › ")),
+            self.glyphs.text(&format!("({})", hints))
+        )
+    }
+
+    /// The tracks shown by the current view, in display order.
+    fn current_tracks(&self) -> &[Track] {
+        match self.state.current_view {
+            ViewType::Search => match self.state.search_results {
+                Some(ref results) => results.tracks.as_ref().map(|t| t.items.as_slice()).unwrap_or(&[]),
+                None => &self.state.recently_played,
+            },
+            ViewType::LikedSongs => &self.state.liked_songs,
+            ViewType::PlaylistTracks => &self.state.selected_playlist_tracks,
+            ViewType::AlbumTracks => &self.state.selected_album_tracks,
+            ViewType::Queue => &self.state.queue,
+            _ => &[],
+        }
+    }
+
+    /// Library lists that can be sorted and filtered in place.
+    fn has_track_view(&self) -> bool {
+        matches!(
+            self.state.current_view,
+            ViewType::LikedSongs | ViewType::PlaylistTracks | ViewType::AlbumTracks
+        )
+    }
+
+    fn current_track_view(&self) -> Option<&TrackView> {
+        if self.has_track_view() {
+            self.track_views.get(&self.state.current_view)
+        } else {
+            None
+        }
+    }
+
+    fn current_track_view_mut(&mut self) -> &mut TrackView {
+        self.track_views.entry(self.state.current_view.clone()).or_default()
+    }
+
+    /// Positions in `current_tracks` for each displayed row.
+    fn visible_indices(&self) -> Vec<usize> {
+        match self.current_track_view() {
+            Some(view) => view.indices(self.current_tracks()),
+            None => (0..self.current_tracks().len()).collect(),
+        }
+    }
+
+    /// Position in `current_tracks` of the track shown at display row `row`.
+    fn track_index(&self, row: usize) -> Option<usize> {
+        self.visible_indices().get(row).copied()
+    }
+
+    fn track_at(&self, row: usize) -> Option<Track> {
+        self.track_index(row).and_then(|i| self.current_tracks().get(i).cloned())
+    }
+
+    fn cycle_sort(&mut self, reverse: bool) {
+        if !self.has_track_view() {
+            self.state.auth_message = "⚠️ Sorting works in Liked Songs, playlists and albums".to_string();
+            return;
+        }
+
+        // Keep the selected track selected after reordering
+        let selected = self.list_state.selected().and_then(|row| self.track_index(row));
+        let view = self.current_track_view_mut();
+        if reverse {
+            view.reverse_sort();
+        } else {
+            view.cycle_sort();
+        }
+        self.state.auth_message = match view.sort {
+            Some(_) => format!("✅ {}", view.describe().unwrap_or_default()),
+            None => "✅ Original order".to_string(),
+        };
+
+        let row = selected.and_then(|i| self.visible_indices().iter().position(|v| *v == i));
+        self.list_state.select(Some(row.unwrap_or(0)));
+    }
+
+    /// Pasted text goes into whatever is being typed; otherwise it is taken
+    /// as a Spotify link to open.
+    async fn handle_paste(&mut self, text: &str) {
+        let text = text.trim();
+        if let Some(line) = self.command_line.as_mut() {
+            text.chars().for_each(|c| line.push(c));
+        } else if self.filter_input {
+            self.current_track_view_mut().filter.push_str(text);
+        } else if self.input_mode {
+            self.state.search_query.push_str(text);
+        } else {
+            self.open_link(text).await;
+        }
+    }
+
+    fn handle_filter_key(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Enter => {
+                self.filter_input = false;
+            }
+            KeyCode::Esc => {
+                self.filter_input = false;
+                self.current_track_view_mut().filter.clear();
+            }
+            KeyCode::Backspace => {
+                self.current_track_view_mut().filter.pop();
+            }
+            KeyCode::Char(c) => {
+                self.current_track_view_mut().filter.push(c);
+            }
+            _ => return,
+        }
+        self.list_state.select(Some(0));
+    }
+
+    /// Lists whose rows are tracks, where tracks can be marked.
+    fn is_track_list(&self) -> bool {
+        matches!(
+            self.state.current_view,
+            ViewType::Search | ViewType::LikedSongs | ViewType::PlaylistTracks | ViewType::AlbumTracks | ViewType::Queue
+        )
+    }
+
+    /// Drops the marks once another list is shown or this one is reloaded.
+    fn sync_selection(&mut self) {
+        let list = self.history.current().key();
+        let len = if self.is_track_list() { self.current_tracks().len() } else { 0 };
+        self.selection.sync(&list, len);
+    }
+
+    /// Positions in `current_tracks` of the marked tracks, including a range
+    /// still being extended, in display order.
+    fn marked_indices(&self) -> Vec<usize> {
+        if !self.is_track_list() {
+            return Vec::new();
+        }
+        let cursor = self.list_state.selected().unwrap_or(0);
+        self.selection.marked(&self.visible_indices(), cursor)
+    }
+
+    fn has_marks(&self) -> bool {
+        !self.marked_indices().is_empty()
+    }
+
+    /// Tracks a bulk action applies to: the marked ones, or else the selected one.
+    fn action_tracks(&self) -> Vec<Track> {
+        let marked = self.marked_indices();
+        if marked.is_empty() {
+            self.list_state.selected().and_then(|row| self.track_at(row)).into_iter().collect()
+        } else {
+            marked.iter().filter_map(|i| self.current_tracks().get(*i).cloned()).collect()
+        }
+    }
+
+    /// `x`: marks or unmarks the selected track and moves down, or marks
+    /// the range being extended with `v`.
+    fn toggle_mark(&mut self) {
+        if !self.is_track_list() || self.current_tracks().is_empty() {
+            self.state.auth_message = "⚠️ Marking works in track lists".to_string();
+            return;
+        }
+        self.sync_selection();
+        let cursor = self.list_state.selected().unwrap_or(0);
+        if self.selection.in_range_mode() {
+            self.selection.finish_range(&self.visible_indices(), cursor);
+        } else if let Some(index) = self.track_index(cursor) {
+            self.selection.toggle(index);
+            self.apply_list_motion(Motion::Down, None);
+        }
+        self.show_mark_count();
+    }
+
+    /// `v`: starts marking a range at the selected row, or marks the range
+    /// when one is already being extended.
+    fn toggle_range(&mut self) {
+        if !self.is_track_list() || self.current_tracks().is_empty() {
+            self.state.auth_message = "⚠️ Marking works in track lists".to_string();
+            return;
+        }
+        self.sync_selection();
+        let cursor = self.list_state.selected().unwrap_or(0);
+        if self.selection.in_range_mode() {
+            self.selection.finish_range(&self.visible_indices(), cursor);
+            self.show_mark_count();
+        } else {
+            self.selection.start_range(cursor);
+            self.state.auth_message = "Marking a range: move, then v or x to mark it (Esc cancels)".to_string();
+        }
+    }
+
+    fn clear_marks(&mut self) {
+        self.selection.clear();
+        self.state.auth_message = "✅ Cleared marks".to_string();
+    }
+
+    fn show_mark_count(&mut self) {
+        self.state.auth_message = match self.marked_indices().len() {
+            0 => "✅ No tracks marked".to_string(),
+            1 => "✅ 1 track marked (X to clear)".to_string(),
+            n => format!("✅ {} tracks marked (X to clear)", n),
+        };
+    }
+
+    /// `m` (or `M` for play next) with tracks marked: queues them in the
+    /// order shown.
+    fn queue_marked_tracks(&mut self, next: bool) {
+        self.queue_tracks(self.action_tracks(), next);
+    }
+
+    /// Adds `tracks` to the managed queue, in order, to play after what's
+    /// waiting or, with `next`, before it.
+    fn queue_tracks(&mut self, tracks: Vec<Track>, next: bool) {
+        if tracks.is_empty() {
+            return;
+        }
+        let what = match tracks.as_slice() {
+            [track] => track.name.clone(),
+            _ => format!("{} tracks", tracks.len()),
+        };
+        if next {
+            self.managed_queue.play_next(tracks);
+        } else {
+            for track in tracks {
+                self.managed_queue.push_back(track);
+            }
+        }
+        self.save_managed_queue();
+        self.selection.clear();
+        self.state.auth_message = if next { format!("⏭️ Playing next: {}", what) } else { format!("➕ Queued: {}", what) };
+    }
+
+    /// `)` with tracks marked: unlikes them if all are liked, otherwise
+    /// likes the ones that aren't.
+    async fn toggle_like_marked_tracks(&mut self) {
+        if !self.state.user_authenticated {
+            self.state.auth_message = "❌ Authentication required for liking songs".to_string();
+            return;
+        }
+        let tracks = self.action_tracks();
+        if tracks.is_empty() {
+            return;
+        }
+        let ids: Vec<String> = tracks.into_iter().map(|t| t.id).filter(|id| !id.is_empty()).collect();
+        if ids.is_empty() {
+            // Local files have no Spotify id to like
+            self.state.auth_message = "⚠️ Local files can't be liked".to_string();
+            return;
+        }
+        if let Some(client) = self.spotify_client.clone() {
+            let liked = match client.check_liked_tracks(&ids).await {
+                Ok(liked) => liked,
+                Err(e) => {
+                    self.log_error(format!("❌ LIKE CHECK ERROR: {}", e));
+                    self.state.auth_message = format!("❌ Error checking like status: {}", e);
+                    return;
+                }
+            };
+
+            let unlike = liked.iter().all(|l| *l);
+            let result = if unlike {
+                client.unlike_tracks(&ids).await
+            } else {
+                let unliked: Vec<String> = ids.iter().zip(&liked).filter(|(_, l)| !**l).map(|(id, _)| id.clone()).collect();
+                client.like_tracks(&unliked).await
+            };
+
+            match result {
+                Ok(_) => {
+                    self.selection.clear();
+                    if unlike {
+                        self.state.auth_message = format!("💔 Removed {} tracks from liked songs", ids.len());
+                        if self.state.current_view == ViewType::LikedSongs {
+                            self.load_liked_songs().await;
+                        }
+                    } else {
+                        self.state.auth_message = format!("❤️ Added {} tracks to liked songs", liked.iter().filter(|l| !**l).count());
+                    }
+                }
+                Err(e) => {
+                    self.log_error(format!("❌ LIKE ERROR: {}", e));
+                    self.state.auth_message = format!("❌ Like error: {}", e);
+                }
+            }
+        }
+    }
+
+    /// `a`: asks which playlist to add the marked (or selected) tracks to.
+    async fn open_playlist_picker(&mut self) {
+        let uris = track_uris(&self.action_tracks());
+        self.open_playlist_picker_for(uris).await;
+    }
+
+    async fn open_playlist_picker_for(&mut self, uris: Vec<SpotifyUri>) {
+        if uris.is_empty() {
+            self.state.auth_message = "❌ No track selected".to_string();
+            return;
+        }
+        if !self.state.user_authenticated {
+            self.state.auth_message = "❌ Authentication required to edit playlists".to_string();
+            return;
+        }
+        if self.state.user_playlists.is_empty() {
+            self.load_user_playlists().await;
+        }
+        if self.state.user_playlists.is_empty() {
+            self.state.auth_message = "⚠️ No playlists to add to".to_string();
+            return;
+        }
+        self.playlist_picker = Some(PlaylistPicker {
+            uris,
+            state: ListState::default().with_selected(Some(0)),
+        });
+    }
+
+    async fn handle_picker_key(&mut self, code: KeyCode) {
+        let len = self.state.user_playlists.len();
+        let picker = match self.playlist_picker.as_mut() {
+            Some(picker) => picker,
+            None => return,
+        };
+        let selected = picker.state.selected().unwrap_or(0);
+        match code {
+            KeyCode::Up | KeyCode::Char('k') => picker.state.select(Some(selected.saturating_sub(1))),
+            KeyCode::Down | KeyCode::Char('j') => picker.state.select(Some((selected + 1).min(len.saturating_sub(1)))),
+            KeyCode::Home | KeyCode::Char('g') => picker.state.select(Some(0)),
+            KeyCode::End | KeyCode::Char('G') => picker.state.select(Some(len.saturating_sub(1))),
+            KeyCode::Esc | KeyCode::Char('q') => {
+                self.playlist_picker = None;
+                self.state.auth_message.clear();
+            }
+            KeyCode::Enter => {
+                let uris = std::mem::take(&mut picker.uris);
+                self.playlist_picker = None;
+                if let Some(playlist) = self.state.user_playlists.get(selected).cloned() {
+                    self.add_tracks_to_playlist(playlist, uris).await;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    async fn add_tracks_to_playlist(&mut self, playlist: Playlist, uris: Vec<SpotifyUri>) {
+        if let Some(client) = self.spotify_client.clone() {
+            match client.add_tracks_to_playlist(&playlist.id, &uris).await {
+                Ok(_) => {
+                    self.selection.clear();
+                    // Show the new tracks if that playlist is open
+                    if self.state.current_view == ViewType::PlaylistTracks
+                        && self.state.selected_playlist.as_ref().map(|p| &p.id) == Some(&playlist.id)
+                    {
+                        self.load_selected_playlist_tracks(&playlist.id).await;
+                    }
+                    self.state.auth_message = format!("✅ Added {} tracks to {}", uris.len(), playlist.name);
+                }
+                Err(e) => {
+                    self.log_error(format!("❌ PLAYLIST ERROR: {}", e));
+                    self.state.auth_message = format!("❌ Could not add to {}: {}", playlist.name, e);
+                }
+            }
+        }
+    }
+
+    /// `d` in a playlist: removes the marked (or selected) tracks after a
+    /// second `d` confirms it.
+    async fn remove_from_playlist(&mut self, confirmed: bool) {
+        let playlist = match self.state.selected_playlist.clone() {
+            Some(playlist) if self.state.current_view == ViewType::PlaylistTracks => playlist,
+            _ => {
+                self.state.auth_message = "⚠️ Removing tracks works inside a playlist".to_string();
+                return;
+            }
+        };
+        let mut uris = track_uris(&self.action_tracks());
+        if uris.is_empty() {
+            self.state.auth_message = "❌ No track selected".to_string();
+            return;
+        }
+        if !confirmed {
+            self.confirm_remove = true;
+            self.state.auth_message = format!("⚠️ Remove {} tracks from {}? Press d again to confirm", uris.len(), playlist.name);
+            return;
+        }
+
+        // Spotify removes every occurrence of a URI, so each only needs sending once
+        let mut seen = HashSet::new();
+        uris.retain(|uri| seen.insert(uri.clone()));
+        if let Some(client) = self.spotify_client.clone() {
+            match client.remove_tracks_from_playlist(&playlist.id, &uris).await {
+                Ok(_) => {
+                    self.selection.clear();
+                    self.load_selected_playlist_tracks(&playlist.id).await;
+                    self.state.auth_message = format!("✅ Removed {} tracks from {}", uris.len(), playlist.name);
+                }
+                Err(e) => {
+                    self.log_error(format!("❌ PLAYLIST ERROR: {}", e));
+                    self.state.auth_message = format!("❌ Could not remove from {}: {}", playlist.name, e);
+                }
+            }
+        }
+    }
+
+    /// `y`: copies the marked (or selected) tracks' URIs, one per line.
+    fn copy_track_uris(&mut self) {
+        let uris: Vec<String> = self.action_tracks().into_iter().map(|t| t.uri).collect();
+        if uris.is_empty() {
+            self.state.auth_message = "❌ No track selected".to_string();
+            return;
+        }
+        match clipboard::copy(&uris.join("\n")) {
+            Ok(_) => {
+                self.selection.clear();
+                self.state.auth_message = format!("📋 Copied {} URIs to the clipboard", uris.len());
+            }
+            Err(e) => self.state.auth_message = format!("❌ Could not copy: {}", e),
+        }
+    }
+
+    /// The item under the cursor, for the context menu.
+    fn menu_target(&self) -> Option<MenuTarget> {
+        if self.focused() == Focus::Sidebar {
+            return self.sidebar_selected
+                .checked_sub(NAV_ENTRIES.len())
+                .and_then(|i| self.state.user_playlists.get(i))
+                .cloned()
+                .map(MenuTarget::Playlist);
+        }
+        let selected = self.list_state.selected()?;
+        match self.state.current_view {
+            ViewType::Playlists => self.state.user_playlists.get(selected).cloned().map(MenuTarget::Playlist),
+            ViewType::Albums => self.state.user_albums.get(selected).cloned().map(MenuTarget::Album),
+            ViewType::ArtistAlbums => self.state.selected_artist_albums.get(selected).cloned().map(MenuTarget::Album),
+            ViewType::Artists => self.state.user_artists.get(selected).cloned().map(MenuTarget::Artist),
+            _ if self.is_track_list() => self.track_at(selected).map(MenuTarget::Track),
+            _ => None,
+        }
+    }
+
+    /// What the account may do, looking up its subscription the first time.
+    async fn capabilities(&mut self) -> Capabilities {
+        let profile = self.user_profile().await;
+        Capabilities {
+            authenticated: self.state.user_authenticated,
+            scopes: self.spotify_client.as_ref().map(|c| c.granted_scopes()).unwrap_or_default(),
+            premium: profile
+                .and_then(|p| p.product)
+                .map(|product| product == "premium"),
+        }
+    }
+
+    /// `e`: lists everything that can be done with the selected item.
+    async fn open_context_menu(&mut self) {
+        match self.menu_target() {
+            Some(target) => {
+                let capabilities = self.capabilities().await;
+                self.context_menu = Some(ContextMenu::new(target, &capabilities));
+            }
+            None => self.state.auth_message = "⚠️ Nothing selected".to_string(),
+        }
+    }
+
+    async fn handle_menu_key(&mut self, code: KeyCode) {
+        let menu = match self.context_menu.as_mut() {
+            Some(menu) => menu,
+            None => return,
+        };
+        match code {
+            KeyCode::Up | KeyCode::Char('k') => menu.step(false),
+            KeyCode::Down | KeyCode::Char('j') => menu.step(true),
+            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('e') => {
+                self.context_menu = None;
+            }
+            KeyCode::Enter => {
+                let item = menu.selected().cloned();
+                self.context_menu = None;
+                if let Some(item) = item {
+                    match item.disabled {
+                        Some(reason) => self.state.auth_message = format!("⚠️ {}: {}", item.label, reason),
+                        None => self.run_menu_action(item.action, item.target).await,
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Runs a context menu entry. Track entries act on the selected row, so
+    /// they go through the same methods as the single-key shortcuts.
+    async fn run_menu_action(&mut self, action: MenuAction, target: MenuTarget) {
+        match (action, target) {
+            (MenuAction::Play, MenuTarget::Track(_)) => self.play_selected_track().await,
+            (MenuAction::PlayNext, MenuTarget::Track(_)) => self.add_selected_to_queue(true),
+            (MenuAction::Queue, MenuTarget::Track(_)) => self.add_selected_to_queue(false),
+            (MenuAction::Like, MenuTarget::Track(_)) => self.toggle_like_selected_track().await,
+            (MenuAction::AddToPlaylist, MenuTarget::Track(track)) => self.open_playlist_picker_for(track_uris(&[track])).await,
+            (MenuAction::GoToArtist, MenuTarget::Track(_)) => self.go_to_artist().await,
+            (MenuAction::GoToAlbum, MenuTarget::Track(_)) => self.go_to_album().await,
+            (MenuAction::StartRadio, MenuTarget::Track(track)) => self.start_radio(track).await,
+            (MenuAction::ShowPlaylists, MenuTarget::Track(track)) => self.show_playlists_containing(track).await,
+            (MenuAction::Like, MenuTarget::Album(album)) => self.toggle_save_album(album).await,
+            (MenuAction::Open, MenuTarget::Album(album)) => self.open_album(album).await,
+            (MenuAction::Open, MenuTarget::Artist(artist)) => self.open_artist(artist).await,
+            (MenuAction::Open, MenuTarget::Playlist(playlist)) => self.open_playlist(playlist).await,
+            (MenuAction::GoToArtist, MenuTarget::Album(album)) => {
+                if let Some(artist) = album.artists.into_iter().next() {
+                    self.open_artist(artist).await;
+                }
+            }
+            (MenuAction::CopyLink, target) => self.copy_link(&target),
+            (MenuAction::Play, target) => self.play_context(&target).await,
+            (MenuAction::Queue, target) => {
+                if let Some(tracks) = self.context_tracks(&target).await {
+                    self.queue_tracks(tracks, false);
+                }
+            }
+            (MenuAction::AddToPlaylist, target) => {
+                if let Some(tracks) = self.context_tracks(&target).await {
+                    self.open_playlist_picker_for(track_uris(&tracks)).await;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// All tracks of an album or playlist.
+    async fn context_tracks(&mut self, target: &MenuTarget) -> Option<Vec<Track>> {
+        let client = self.spotify_client.clone()?;
+        let result = match target {
+            MenuTarget::Album(album) => client.get_all_album_tracks(album).await,
+            MenuTarget::Playlist(playlist) => client
+                .get_all_playlist_tracks(&playlist.id)
+                .await
+                .map(|items| items.into_iter().filter_map(|item| item.into_track()).collect()),
+            MenuTarget::Track(track) => Ok(vec![track.clone()]),
+            MenuTarget::Artist(_) => Ok(Vec::new()),
+        };
+        match result {
+            Ok(tracks) => Some(tracks),
+            Err(e) => {
+                self.state.auth_message = format!("❌ Failed to load tracks of {}: {}", target.name(), e);
+                None
+            }
+        }
+    }
+
+    /// Plays an album, artist or playlist from its first track.
+    async fn play_context(&mut self, target: &MenuTarget) {
+        let uri = match target.spotify_uri() {
+            Ok(uri) => uri,
+            Err(e) => {
+                self.state.auth_message = format!("❌ Can't play {}: {}", target.name(), e);
+                return;
+            }
+        };
+        if let Some(client) = self.spotify_client.clone() {
+            match client.play_context(&uri).await {
+                Ok(_) => {
+                    self.state.auth_message = format!("▶ Playing {}", target.name());
+                    self.state.is_playing = true;
+                    tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
+                    self.sync_playback_state().await;
+                }
+                Err(e) => {
+                    self.log_error(format!("❌ PLAY ERROR: {}", e));
+                    self.state.auth_message = if e.to_string().contains("NO_ACTIVE_DEVICE") {
+                        "❌ No active device! Open Spotify app first.".to_string()
+                    } else {
+                        format!("❌ Play error: {}", e)
+                    };
+                }
+            }
+        }
+    }
+
+    async fn start_radio(&mut self, track: Track) {
+        let uri = match track.spotify_uri() {
+            Ok(uri) => uri,
+            Err(_) => {
+                self.state.auth_message = format!("❌ Can't start radio from {}", track.name);
+                return;
+            }
+        };
+        if let Some(client) = self.spotify_client.clone() {
+            match client.play_track(&uri).await {
+                Ok(_) => {
+                    self.start_station(track.clone());
+                    self.state.auth_message = format!("📻 Starting radio: {} (Building playlist with similar tracks...)", track.name);
+                    self.state.current_track = Some(track);
+                    self.state.is_playing = true;
+                }
+                Err(e) => {
+                    self.log_error(format!("❌ RADIO ERROR: {}", e));
+                    self.state.auth_message = format!("❌ Radio error: {}", e);
+                }
+            }
+        }
+    }
+
+    /// Starts a station around `seed`, replacing any other. It tops the
+    /// queue up from the run loop.
+    fn start_station(&mut self, seed: Track) {
+        self.stop_radio();
+        let Some(client) = self.spotify_client.clone() else {
+            return;
+        };
+        self.log_radio(format!("📻 Radio started from {}", seed.name));
+        self.radio = Some(crate::radio::spawn(Station::new(&self.config.radio, seed), client));
+    }
+
+    /// Stops the station, dropping the tracks it queued that haven't gone
+    /// to Spotify yet. Returns whether one was on.
+    fn stop_radio(&mut self) -> bool {
+        let Some(station) = self.radio.take() else {
+            return false;
+        };
+        self.managed_queue.retain(|track| !station.picked.contains(&track.id));
+        self.save_managed_queue();
+        self.log_radio(format!("⏹️ Radio from {} stopped", station.name));
+        true
+    }
+
+    /// Handles what the station sent, and asks it for more when the queue
+    /// runs low.
+    fn drive_radio(&mut self) {
+        let Some(mut station) = self.radio.take() else {
+            return;
+        };
+        while let Some(event) = station.try_recv() {
+            match event {
+                StationEvent::Log(line) => self.log_radio(line),
+                StationEvent::Picked(tracks) if tracks.is_empty() => {
+                    self.log_radio("⏹️ Radio ran out of tracks".to_string());
+                    self.state.auth_message = format!("📻 Radio from {} ran out of tracks", station.name);
+                    return;
+                }
+                StationEvent::Picked(tracks) => {
+                    for track in tracks {
+                        self.managed_queue.push_back(track);
+                    }
+                    self.save_managed_queue();
+                }
+            }
+        }
+        if !station.topping_up && self.managed_queue.tracks.len() < self.config.radio.keep_queued {
+            station.send(StationCommand::TopUp(RADIO_TOP_UP));
+        }
+        self.radio = Some(station);
+    }
+
+    /// `t`: more like the playing track. `T`: no more of its artists, and
+    /// skip it.
+    async fn thumbs(&mut self, up: bool) {
+        let Some(track) = self.state.current_track.clone() else {
+            return;
+        };
+        let Some(station) = self.radio.as_mut() else {
+            self.state.auth_message = "⚠️ Radio is off; play a single track to start it".to_string();
+            return;
+        };
+        if up {
+            station.send(StationCommand::ThumbsUp(track.clone()));
+            self.log_radio(format!("👍 More like {}", track.name));
+            self.state.auth_message = format!("👍 More like {}", track.name);
+            return;
+        }
+        station.send(StationCommand::ThumbsDown(track.clone()));
+        let artists: HashSet<String> = track.artists.iter().map(|a| a.id.clone()).collect();
+        let picked = &station.picked;
+        self.managed_queue.retain(|t| !picked.contains(&t.id) || !t.artists.iter().any(|a| artists.contains(&a.id)));
+        self.save_managed_queue();
+        self.log_radio(format!("👎 No more {}", artist_names(&track)));
+        self.next_track().await;
+        self.state.auth_message = format!("👎 No more {}", artist_names(&track));
+    }
+
+    async fn toggle_save_album(&mut self, album: Album) {
+        if let Some(client) = self.spotify_client.clone() {
+            let result = match client.check_if_album_saved(&album.id).await {
+                Ok(true) => client.remove_saved_album(&album.id).await.map(|_| "💔 Removed from"),
+                Ok(false) => client.save_album(&album.id).await.map(|_| "❤️ Saved to"),
+                Err(e) => Err(e),
+            };
+            match result {
+                Ok(action) => self.state.auth_message = format!("{} your albums: {}", action, album.name),
+                Err(e) => {
+                    self.log_error(format!("❌ ALBUM SAVE ERROR: {}", e));
+                    self.state.auth_message = format!("❌ Could not update your albums: {}", e);
+                }
+            }
+        }
+    }
+
+    /// Looks through the user's playlists and lists the ones with `track`.
+    async fn show_playlists_containing(&mut self, track: Track) {
+        if self.state.user_playlists.is_empty() {
+            self.load_user_playlists().await;
+        }
+        let client = match self.spotify_client.clone() {
+            Some(client) => client,
+            None => return,
+        };
+
+        let mut found = Vec::new();
+        for playlist in self.state.user_playlists.clone() {
+            match client.get_all_playlist_tracks(&playlist.id).await {
+                Ok(items) => {
+                    if items.iter().any(|item| item.track.as_ref().is_some_and(|t| t.id == track.id)) {
+                        found.push(playlist);
+                    }
+                }
+                Err(e) => self.log_error(format!("❌ Could not check {}: {}", playlist.name, e)),
+            }
+        }
+
+        if found.is_empty() {
+            self.state.auth_message = format!("{} isn't in any of your playlists", track.name);
+            return;
+        }
+        let title = format!("{} is in {} playlists (Enter to open)", track.name, found.len());
+        let items = found
+            .into_iter()
+            .map(|playlist| MenuItem {
+                label: playlist.name.clone(),
+                action: MenuAction::Open,
+                target: MenuTarget::Playlist(playlist),
+                disabled: None,
+            })
+            .collect();
+        self.context_menu = Some(ContextMenu::with_items(title, items));
+    }
+
+    fn copy_link(&mut self, target: &MenuTarget) {
+        let link = match target.spotify_uri() {
+            Ok(uri) => uri.url(),
+            Err(e) => {
+                self.state.auth_message = format!("❌ No link for {}: {}", target.name(), e);
+                return;
+            }
+        };
+        match clipboard::copy(&link) {
+            Ok(_) => self.state.auth_message = format!("📋 Copied link to {}", target.name()),
+            Err(e) => self.state.auth_message = format!("❌ Could not copy: {}", e),
+        }
+    }
+
+    /// `:`: opens the command line.
+    fn open_command_line(&mut self) {
+        self.command_line = Some(CommandLine::default());
+        self.device_names.clear();
+    }
+
+    /// Handles a key while the command line is open. Returns true when the
+    /// command asked to quit.
+    async fn handle_command_line_key(&mut self, key: KeyEvent) -> bool {
+        let line = match self.command_line.as_mut() {
+            Some(line) => line,
+            None => return false,
+        };
+        match key.code {
+            KeyCode::Esc => self.command_line = None,
+            KeyCode::Backspace if line.input.is_empty() => self.command_line = None,
+            KeyCode::Backspace => line.pop(),
+            KeyCode::Up => line.browse(&self.command_history, true),
+            KeyCode::Down => line.browse(&self.command_history, false),
+            KeyCode::Tab | KeyCode::BackTab => {
+                let wants_devices = line.wants_devices();
+                if wants_devices && self.device_names.is_empty() {
+                    self.load_device_names().await;
+                }
+                let playlists: Vec<String> = self.state.user_playlists.iter().map(|p| p.name.clone()).collect();
+                if let Some(line) = self.command_line.as_mut() {
+                    line.complete(key.code == KeyCode::Tab, &self.device_names, &playlists);
+                }
+            }
+            KeyCode::Enter => {
+                let input = line.input.trim().to_string();
+                self.command_line = None;
+                if input.is_empty() {
+                    return false;
+                }
+                self.command_history.push(&input);
+                if let Err(e) = self.command_history.save() {
+                    self.log_error(format!("Failed to save command history: {}", e));
+                }
+                match Command::parse(&input) {
+                    Ok(command) => return self.execute_command(command).await,
+                    Err(e) => self.state.auth_message = format!("❌ {}", e),
+                }
+            }
+            KeyCode::Char(c) => line.push(c),
+            _ => {}
+        }
+        false
+    }
+
+    async fn load_device_names(&mut self) {
+        if let Some(client) = self.spotify_client.clone() {
+            match client.get_available_devices().await {
+                Ok(list) => self.device_names = list.devices.into_iter().map(|d| d.name).collect(),
+                Err(e) => self.state.auth_message = format!("❌ Could not list devices: {}", e),
+            }
+        }
+    }
+
+    /// Runs a command through the same methods the key bindings use.
+    /// Returns true for `quit`.
+    async fn execute_command(&mut self, command: Command) -> bool {
+        match command {
+            Command::Volume(volume) => self.set_volume_to(volume).await,
+            Command::Seek(position_ms) => self.seek_to(position_ms).await,
+            Command::Play(None) => {
+                if !self.state.is_playing {
+                    self.toggle_playback().await;
+                }
+            }
+            Command::Play(Some(uri)) => self.play_uri(&uri).await,
+            Command::Pause => {
+                if self.state.is_playing {
+                    self.toggle_playback().await;
+                }
+            }
+            Command::Next => self.next_track().await,
+            Command::Previous => self.previous_track().await,
+            Command::Device(name) => self.switch_device(&name).await,
+            Command::Shuffle(mode) => self.set_shuffle_mode(mode).await,
+            Command::Repeat(mode) => self.set_repeat_mode(mode).await,
+            Command::PlaylistNew(name) => self.create_playlist(&name).await,
+            Command::PlaylistOpen(name) => {
+                if let Some(playlist) = self.find_playlist(&name).await {
+                    self.open_playlist(playlist).await;
+                }
+            }
+            Command::PlaylistAdd(name) => {
+                if let Some(playlist) = self.find_playlist(&name).await {
+                    let uris = track_uris(&self.action_tracks());
+                    self.add_tracks_to_playlist(playlist, uris).await;
+                }
+            }
+            Command::QueueClearLocal => {
+                self.managed_queue.clear();
+                self.save_managed_queue();
+                self.state.auth_message = "✅ Cleared the local queue (tracks already sent to Spotify stay there)".to_string();
+            }
+            Command::Search(query) => {
+                if self.state.current_view != ViewType::Search {
+                    self.navigate(Location::view(ViewType::Search));
+                }
+                self.state.search_query = query;
+                self.trigger_search().await;
+            }
+            Command::Party => self.start_party(),
+            Command::RadioStop => {
+                self.state.auth_message = if self.stop_radio() {
+                    "⏹️ Radio stopped".to_string()
+                } else {
+                    "⚠️ Radio isn't on".to_string()
+                };
+            }
+            Command::PartyStop => {
+                if self.party.take().is_some() {
+                    self.log_error("🎉 Party mode stopped".to_string());
+                    self.state.auth_message = "✅ Party over: the guest page is closed".to_string();
+                }
+            }
+            Command::Like if self.has_marks() => self.toggle_like_marked_tracks().await,
+            Command::Like => self.toggle_like_selected_track().await,
+            Command::Quit => return true,
+        }
+        false
+    }
+
+    /// Plays a track or episode on its own, anything else as a context.
+    async fn play_uri(&mut self, uri: &SpotifyUri) {
+        let request = Request::Play { target: Some(uri.to_string()) };
+        let result = self
+            .send_control(request, async |client| {
+                if uri.is_context() {
+                    client.play_context(uri).await
+                } else {
+                    client.play_track(uri).await
+                }
+            })
+            .await;
+        match result {
+            Ok(_) => {
+                self.state.auth_message = format!("▶ Playing {}", uri);
+                self.state.is_playing = true;
+                tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
+                self.sync_playback_state().await;
+            }
+            Err(e) => {
+                self.log_error(format!("❌ PLAY ERROR: {}", e));
+                self.state.auth_message = format!("❌ Play error: {}", e);
+            }
+        }
+    }
+
+    /// A pasted `spotify:` URI or open.spotify.com link: albums, artists and
+    /// playlists open in their view, tracks, shows and episodes start playing.
+    async fn open_link(&mut self, text: &str) {
+        let uri = match SpotifyUri::parse(text) {
+            Ok(uri) => uri,
+            Err(_) => {
+                self.state.auth_message = "⚠️ Paste a Spotify link or URI to open it".to_string();
+                return;
+            }
+        };
+        let client = match self.spotify_client.clone() {
+            Some(client) => client,
+            None => return,
+        };
+        let id = uri.id().as_str();
+        let opened = match uri.kind() {
+            ItemKind::Album => client.get_album(id).await.map(|album| Some(MenuTarget::Album(album))),
+            ItemKind::Artist => client.get_artist(id).await.map(|artist| Some(MenuTarget::Artist(artist))),
+            ItemKind::Playlist => client.get_playlist(id).await.map(|playlist| Some(MenuTarget::Playlist(playlist))),
+            ItemKind::Track | ItemKind::Show | ItemKind::Episode => Ok(None),
+        };
+        match opened {
+            Ok(Some(MenuTarget::Album(album))) => self.open_album(album).await,
+            Ok(Some(MenuTarget::Artist(artist))) => self.open_artist(artist).await,
+            Ok(Some(MenuTarget::Playlist(playlist))) => self.open_playlist(playlist).await,
+            Ok(_) => self.play_uri(&uri).await,
+            Err(e) => {
+                self.log_error(format!("❌ LINK ERROR: {}", e));
+                self.state.auth_message = format!("❌ Could not open {}: {}", uri, e);
+            }
+        }
+    }
+
+    /// Moves playback to the device whose name matches best: exactly, then
+    /// by prefix, ignoring case.
+    async fn switch_device(&mut self, name: &str) {
+        let client = match self.spotify_client.clone() {
+            Some(client) => client,
+            None => return,
+        };
+        let devices = match client.get_available_devices().await {
+            Ok(list) => list,
+            Err(e) => {
+                self.state.auth_message = format!("❌ Could not list devices: {}", e);
+                return;
+            }
+        };
+        let (device_id, device_name) = match devices.find(name) {
+            Some(device) => (device.id.clone(), device.name.clone()),
+            None => {
+                self.state.auth_message = format!("❌ No device called {}", name);
+                return;
+            }
+        };
+        let device_id = match device_id {
+            Some(id) => id,
+            None => {
+                self.state.auth_message = format!("❌ {} can't be controlled remotely", device_name);
+                return;
+            }
+        };
+        let request = Request::Transfer { device: device_id.clone() };
+        match self.send_control(request, async |client| client.transfer_playback(&device_id).await).await {
+            Ok(_) => {
+                self.state.auth_message = format!("🎧 Playing on {}", device_name);
+                tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
+                self.sync_playback_state().await;
+            }
+            Err(e) => {
+                self.log_error(format!("❌ DEVICE ERROR: {}", e));
+                self.state.auth_message = format!("❌ Could not switch to {}: {}", device_name, e);
+            }
+        }
+    }
+
+    async fn set_repeat_mode(&mut self, mode: RepeatMode) {
+        if !self.state.user_authenticated {
+            self.state.auth_message = "❌ Authentication required for repeat control".to_string();
+            return;
+        }
+        match self.send_control(Request::Repeat { mode }, async |client| client.set_repeat(mode.as_str()).await).await {
+            Ok(_) => {
+                self.state.auth_message = match mode {
+                    RepeatMode::Off => "🔁 Repeat: Off".to_string(),
+                    RepeatMode::Track => "🔂 Repeat: Track".to_string(),
+                    RepeatMode::Context => "🔁 Repeat: On".to_string(),
+                };
+                self.sync_playback_state().await;
+            }
+            Err(e) => {
+                self.log_error(format!("❌ REPEAT ERROR: {}", e));
+                self.state.auth_message = format!("❌ Repeat error: {}", e);
+            }
+        }
+    }
+
+    /// The signed-in account, looked up the first time it's needed.
+    async fn user_profile(&mut self) -> Option<UserProfile> {
+        if self.state.user_authenticated && self.state.user_profile.is_none() {
+            if let Some(client) = self.spotify_client.clone() {
+                match client.get_current_user().await {
+                    Ok(profile) => self.state.user_profile = Some(profile),
+                    Err(e) => self.log_error(format!("❌ PROFILE ERROR: {}", e)),
+                }
+            }
+        }
+        self.state.user_profile.clone()
+    }
+
+    async fn create_playlist(&mut self, name: &str) {
+        let user_id = match self.user_profile().await {
+            Some(profile) => profile.id,
+            None => {
+                self.state.auth_message = "❌ Authentication required to create playlists".to_string();
+                return;
+            }
+        };
+        if let Some(client) = self.spotify_client.clone() {
+            match client.create_playlist(&user_id, name).await {
+                Ok(playlist) => {
+                    self.state.auth_message = format!("✅ Created playlist {}", playlist.name);
+                    self.state.user_playlists.insert(0, playlist);
+                }
+                Err(e) => {
+                    self.log_error(format!("❌ PLAYLIST ERROR: {}", e));
+                    self.state.auth_message = format!("❌ Could not create {}: {}", name, e);
+                }
+            }
+        }
+    }
+
+    /// One of the user's playlists by name, ignoring case.
+    async fn find_playlist(&mut self, name: &str) -> Option<Playlist> {
+        if self.state.user_playlists.is_empty() {
+            self.load_user_playlists().await;
+        }
+        let wanted = name.to_lowercase();
+        let playlist = self.state.user_playlists.iter().find(|p| p.name.to_lowercase() == wanted).cloned();
+        if playlist.is_none() {
+            self.state.auth_message = format!("❌ No playlist called {}", name);
+        }
+        playlist
+    }
+
+    async fn play_selected_track(&mut self) {
+        if let Some(selected) = self.list_state.selected() {
+            let track = self.track_at(selected);
+
+            if let Some(track) = track {
+                let track_uri = match track.spotify_uri() {
+                    Ok(uri) => uri,
+                    Err(_) => {
+                        self.state.auth_message = format!("❌ {} can't be played through Spotify", track.name);
+                        return;
+                    }
+                };
+                if self.state.user_authenticated {
+                    if let Some(ref client) = self.spotify_client {
+                        let client_clone = client.clone(); // Clone early to avoid borrowing issues
+                        let play_result = match self.state.current_view {
+                            ViewType::PlaylistTracks => {
+                                // Play playlist with context for continuous playback
+                                if let Some(playlist_uri) = self.state.selected_playlist.as_ref().and_then(|p| p.spotify_uri().ok()) {
+                                    let position = self.track_index(selected).unwrap_or(selected);
+                                    client.play_context_with_offset(&playlist_uri, position).await
+                                } else {
+                                    // Fallback to playing individual track
+                                    client.play_track(&track_uri).await
+                                }
+                            }
+                            ViewType::LikedSongs => {
+                                // Play liked songs with context, in the order they are shown
+                                let visible: Vec<Track> = self.visible_indices()
+                                    .into_iter()
+                                    .map(|i| self.state.liked_songs[i].clone())
+                                    .collect();
+                                let position = visible[..selected.min(visible.len())]
+                                    .iter()
+                                    .filter(|t| t.spotify_uri().is_ok())
+                                    .count();
+                                client.play_tracks_with_offset(&track_uris(&visible), position).await
+                            }
+                            ViewType::AlbumTracks => {
+                                // Play the album as context so it continues after this track
+                                if let Some(album_uri) = self.state.selected_album.as_ref().and_then(|a| a.spotify_uri().ok()) {
+                                    let position = self.track_index(selected).unwrap_or(selected);
+                                    client.play_context_with_offset(&album_uri, position).await
+                                } else {
+                                    client.play_track(&track_uri).await
+                                }
+                            }
+                            // Single tracks from search, albums, artists, the queue or
+                            // recently played start a radio station once playing
+                            _ => client.play_track(&track_uri).await,
+                        };
+
+                        match play_result {
+                            Ok(_) => {
+                                if matches!(self.state.current_view, ViewType::PlaylistTracks | ViewType::LikedSongs | ViewType::AlbumTracks) {
+                                    self.stop_radio();
+                                } else {
+                                    self.start_station(track.clone());
+                                }
+
+                                // Clear Spotify's queue as shown when starting a new song;
+                                // the managed tracks still play after it
+                                self.spotify_queue.clear();
+                                self.show_queue();
+                                self.log_radio("🔄 Queue cleared - starting fresh".to_string());
+
+                                // Load the new queue after a short delay to allow Spotify to populate it
+                                tokio::spawn({
+                                    let client_for_spawn = client_clone.clone();
+                                    async move {
+                                        // Wait a moment for Spotify to populate the queue with new tracks
+                                        tokio::time::sleep(tokio::time::Duration::from_millis(1500)).await;
+                                        let _ = client_for_spawn.get_queue().await;
+                                    }
+                                });
+
+                                let message = match self.state.current_view {
+                                    ViewType::Search | ViewType::Albums | ViewType::Artists | ViewType::Queue => {
+                                        format!("📻 Starting radio: {} (Building playlist with similar tracks...)", track.name)
+                                    }
+                                    ViewType::PlaylistTracks => {
+                                        format!("▶ Playing from playlist: {}", track.name)
+                                    }
+                                    ViewType::LikedSongs => {
+                                        format!("❤️ Playing from liked songs: {}", track.name)
+                                    }
+                                    ViewType::AlbumTracks => {
+                                        format!("▶ Playing from album: {}", track.name)
+                                    }
+                                    _ => {
+                                        format!("📻 Starting radio: {} (Building playlist with similar tracks...)", track.name)
+                                    }
+                                };
+                                self.state.auth_message = message;
+                                self.state.current_track = Some(track.clone());
+                                self.state.is_playing = true;
+
+                                // Add to recently played storage
+                                self.state.recently_played_storage.add_track(track, None);
+                                let _ = self.state.recently_played_storage.save();
+
+                                // Sync with Spotify after a short delay
+                                tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
+                                self.sync_playback_state().await;
+                            }
+                            Err(e) => {
+                                self.log_error(format!("❌ PLAY ERROR: {}", e));
+                                let error_msg = e.to_string();
+                                if error_msg.contains("NO_ACTIVE_DEVICE") {
+                                    self.state.auth_message = "❌ No active device! Open Spotify app first.".to_string();
+                                } else if error_msg.contains("PREMIUM_REQUIRED") {
+                                    self.state.auth_message = "❌ Spotify Premium required for playback.".to_string();
+                                } else {
+                                    self.state.auth_message = format!("❌ Play error: {}", e);
+                                }
+                            }
+                        }
+                    }
+                } else {
+                    self.state.auth_message = "❌ Authentication required for playback".to_string();
+                }
+            }
+        }
+    }
+
+    async fn toggle_playback(&mut self) {
+        if self.state.user_authenticated {
+            let result = if self.state.is_playing {
+                self.send_control(Request::Pause, async |client| client.pause_playback().await).await
+            } else {
+                self.send_control(Request::Resume, async |client| client.resume_playback().await).await
+            };
+
+            match result {
+                Ok(_) => {
+                    self.state.is_playing = !self.state.is_playing;
+                    self.clock.set_playing(self.state.is_playing);
+                    self.state.auth_message = format!("🎵 {}", if self.state.is_playing { "Resumed" } else { "Paused" });
+
+                    // Sync with Spotify after a short delay
+                    tokio::time::sleep(tokio::time::Duration::from_millis(300)).await;
+                    self.sync_playback_state().await;
+                },
+                Err(e) => {
+                    self.state.auth_message = format!("❌ Playback error: {}", e);
+                }
+            }
+        }
+    }
+
+    async fn next_track(&mut self) {
+        if self.state.user_authenticated {
+            // Otherwise Spotify skips past the tracks the app is holding
+            self.feed_managed_queue(true).await;
+            match self.send_control(Request::Next, async |client| client.next_track().await).await {
+                Ok(_) => {
+                    self.state.auth_message = "⏭ Next track".to_string();
+
+                    // Sync with Spotify after a delay to allow track change
+                    tokio::time::sleep(tokio::time::Duration::from_millis(800)).await;
+                    self.sync_playback_state().await;
+                },
+                Err(e) => {
+                    self.state.auth_message = format!("❌ Next track error: {}", e);
+                }
+            }
+        } else {
+            self.state.auth_message = "❌ Authentication required".to_string();
+        }
+    }
+
+    async fn previous_track(&mut self) {
+        if self.state.user_authenticated {
+            match self.send_control(Request::Previous, async |client| client.previous_track().await).await {
+                Ok(_) => {
+                    self.state.auth_message = "⏮ Previous track".to_string();
+
+                    // Sync with Spotify after a delay to allow track change
+                    tokio::time::sleep(tokio::time::Duration::from_millis(800)).await;
+                    self.sync_playback_state().await;
+                },
+                Err(e) => {
+                    self.state.auth_message = format!("❌ Previous track error: {}", e);
+                }
+            }
+        } else {
+            self.state.auth_message = "❌ Authentication required".to_string();
+        }
+    }
+
+    async fn toggle_shuffle(&mut self) {
+        // Cycle through: Off -> On -> SmartShuffle -> Off
+        let next = match self.state.shuffle_mode {
+            ShuffleMode::Off => ShuffleMode::On,
+            ShuffleMode::On => ShuffleMode::SmartShuffle,
+            ShuffleMode::SmartShuffle => ShuffleMode::Off,
+        };
+        self.set_shuffle_mode(next).await;
+    }
+
+    async fn set_shuffle_mode(&mut self, new_mode: ShuffleMode) {
+        if self.state.user_authenticated {
+            let request = Request::Shuffle { mode: Some(new_mode.clone()) };
+            let result = self
+                .send_control(request, async |client| match new_mode {
+                    ShuffleMode::Off => client.set_shuffle(false).await,
+                    ShuffleMode::On => client.set_shuffle(true).await,
+                    ShuffleMode::SmartShuffle => client.set_smart_shuffle(true).await,
+                })
+                .await;
+
+            match result {
+                Ok(_) => {
+                    self.state.shuffle_mode = new_mode.clone();
+                    let mode_text = match new_mode {
+                        ShuffleMode::Off => "🔀 Shuffle: Off",
+                        ShuffleMode::On => "🔀 Shuffle: On",
+                        ShuffleMode::SmartShuffle => "🔀 Smart Shuffle: On",
+                    };
+                    self.state.auth_message = mode_text.to_string();
+
+                    // Sync after a short delay
+                    tokio::time::sleep(tokio::time::Duration::from_millis(300)).await;
+                    self.sync_playback_state().await;
+                },
+                Err(e) => {
+                    self.log_error(format!("❌ SHUFFLE ERROR: {}", e));
+                    let error_msg = e.to_string();
+                    if error_msg.contains("NO_ACTIVE_DEVICE") {
+                        self.state.auth_message = "❌ No active device! Open Spotify app first.".to_string();
+                    } else if error_msg.contains("PREMIUM_REQUIRED") {
+                        self.state.auth_message = "❌ Spotify Premium required for shuffle control.".to_string();
+                    } else {
+                        self.state.auth_message = format!("❌ Shuffle error: {}", e);
+                    }
+                }
+            }
+        } else {
+            self.state.auth_message = "❌ Authentication required for shuffle control".to_string();
+        }
+    }
+
+    async fn toggle_like_selected_track(&mut self) {
+        let user_authenticated = self.state.user_authenticated;
+        let current_view = self.state.current_view.clone();
+        let selected = self.list_state.selected();
+
+        if let Some(selected) = selected {
+            let track = self.track_at(selected);
+
+            if let Some(track) = track {
+                if user_authenticated {
+                    if let Some(client) = self.spotify_client.clone() {
+                        // First check if the track is already liked
+                        match client.check_if_liked(&track.id).await {
+                            Ok(is_liked) => {
+                                let result = if is_liked {
+                                    client.unlike_song(&track.id).await
+                                } else {
+                                    client.like_song(&track.id).await
+                                };
+
+                                match result {
+                                    Ok(_) => {
+                                        let action = if is_liked { "💔 Removed from" } else { "❤️ Added to" };
                                         self.state.auth_message = format!("{} liked songs: {}", action, track.name);
 
                                         // If we're in liked songs view and we just unliked, refresh the list
@@ -734,122 +2083,173 @@ impl App {
         }
     }
 
-    fn log_error(&mut self, message: String) {
-        let timestamp = chrono::Local::now().format("%H:%M:%S").to_string();
-        let log_entry = format!("[{}] {}", timestamp, message);
-        self.state.error_logs.push(log_entry);
+    /// Starts the guest page unless it's already up, then shows the Party panel.
+    fn start_party(&mut self) {
+        if self.party.is_none() {
+            let Some(client) = self.spotify_client.clone() else {
+                self.state.auth_message = "❌ No Spotify client available".to_string();
+                return;
+            };
+            let Ok(address) = self.config.party.address.parse::<SocketAddr>() else {
+                self.state.auth_message = format!("❌ party.address '{}' isn't an address like 0.0.0.0:8080", self.config.party.address);
+                return;
+            };
+            let party: SharedParty = Arc::new(Mutex::new(Party::new(self.config.party.clone())));
+            party.lock().unwrap().now_playing = self.state.current_track.as_ref().map(|t| format!("{} - {}", t.name, artist_names(t)));
+            match crate::party::serve(address, party.clone(), client) {
+                Ok((bound, server)) => {
+                    // Listening everywhere; show guests the address they can reach
+                    let host = if bound.ip().is_unspecified() { crate::party::lan_address().unwrap_or(bound.ip()) } else { bound.ip() };
+                    let url = format!("http://{}", SocketAddr::new(host, bound.port()));
+                    self.log_error(format!("🎉 Party mode started: guests can open {}", url));
+                    self.state.auth_message = format!("🎉 Party on! Guests can open {}", url);
+                    self.party = Some(PartySession { party, url, server: tokio::spawn(server) });
+                }
+                Err(e) => {
+                    self.log_error(format!("❌ PARTY ERROR: {}", e));
+                    self.state.auth_message = format!("❌ Couldn't start party mode: {}", e);
+                    return;
+                }
+            }
+        }
+        if self.state.current_view != ViewType::Party {
+            self.navigate(Location::view(ViewType::Party));
+        }
+    }
 
-        // Keep only last 100 log entries
-        if self.state.error_logs.len() > 100 {
-            self.state.error_logs.remove(0);
+    /// Id of the submission selected in the Party panel.
+    fn selected_submission(&self) -> Option<u64> {
+        let session = self.party.as_ref()?;
+        let party = session.party.lock().unwrap();
+        party.pending().get(self.list_state.selected()?).map(|s| s.id)
+    }
+
+    fn approve_submission(&mut self) {
+        let (Some(id), Some(session)) = (self.selected_submission(), self.party.as_ref()) else {
+            return;
+        };
+        session.party.lock().unwrap().approve(id);
+        self.state.auth_message = "✅ Approved".to_string();
+    }
+
+    /// Drops the selected submission; with `block`, also everything its
+    /// guest submitted, and refuses them from now on.
+    fn veto_submission(&mut self, block: bool) {
+        let (Some(id), Some(session)) = (self.selected_submission(), self.party.as_ref()) else {
+            return;
+        };
+        let mut party = session.party.lock().unwrap();
+        let message = if block {
+            party.block_submitter(id).map(|guest| format!("🚫 Blocked {}", guest))
+        } else {
+            party.veto(id).map(|s| format!("🗑️ Vetoed {}", s.track.name))
+        };
+        drop(party);
+        if let Some(message) = message {
+            self.log_error(message.clone());
+            self.state.auth_message = message;
         }
     }
 
-    fn log_radio(&mut self, message: String) {
-        let timestamp = chrono::Local::now().format("%H:%M:%S").to_string();
-        let log_entry = format!("[{}] RADIO: {}", timestamp, message);
-        self.state.error_logs.push(log_entry);
+    /// What the Queue view and "Up next" show: the managed tracks, then
+    /// Spotify's own queue.
+    fn show_queue(&mut self) {
+        self.state.queue = self.managed_queue.tracks.iter().chain(&self.spotify_queue).cloned().collect();
+    }
 
-        // Keep only last 100 log entries
-        if self.state.error_logs.len() > 100 {
-            self.state.error_logs.remove(0);
+    /// Stores the managed queue and shows its new state.
+    fn save_managed_queue(&mut self) {
+        if let Err(e) = self.managed_queue.save() {
+            self.log_error(format!("⚠️ Couldn't save the queue: {}", e));
         }
+        self.show_queue();
     }
 
-    async fn add_selected_to_queue(&mut self) {
-        let user_authenticated = self.state.user_authenticated;
-        let current_view = self.state.current_view.clone();
-        let selected = self.list_state.selected();
+    /// Hands Spotify the next managed track shortly before the current one
+    /// ends, or right away when `skipping` it. Party submissions come in one
+    /// at a time, best voted first, so votes count until the last moment.
+    async fn feed_managed_queue(&mut self, skipping: bool) {
+        let Some(track) = self.state.current_track.as_ref().filter(|_| self.state.is_playing) else {
+            return;
+        };
+        let remaining_ms = if skipping { 0 } else { (track.duration_ms as u64).saturating_sub(self.clock.position_ms()) };
+        if !self.managed_queue.is_due(&track.id, remaining_ms) {
+            return;
+        }
+        let playing_id = track.id.clone();
+        if self.managed_queue.tracks.is_empty() {
+            if let Some(next) = self.party.as_ref().and_then(|session| session.party.lock().unwrap().take_next()) {
+                self.managed_queue.push_back(next);
+            }
+        }
+        let Some(next) = self.managed_queue.take_for(&playing_id) else {
+            return;
+        };
+        self.save_managed_queue();
+        let Ok(uri) = next.spotify_uri() else {
+            return;
+        };
+        match self.send_control(Request::Queue { uri: uri.to_string() }, async |client| client.add_to_queue(&uri).await).await {
+            Ok(_) => self.log_error(format!("🎶 Up next: {}", next.name)),
+            Err(e) => self.log_error(format!("❌ QUEUE ERROR: couldn't queue {}: {}", next.name, e)),
+        }
+    }
 
-        if let Some(selected) = selected {
-            let track = match current_view {
-                ViewType::Search => {
-                    if let Some(ref search_results) = self.state.search_results {
-                        if let Some(ref tracks) = search_results.tracks {
-                            if selected < tracks.items.len() {
-                                Some(tracks.items[selected].clone())
-                            } else {
-                                None
-                            }
-                        } else {
-                            None
-                        }
-                    } else {
-                        if selected < self.state.recently_played.len() {
-                            Some(self.state.recently_played[selected].clone())
-                        } else {
-                            None
-                        }
-                    }
-                }
-                ViewType::PlaylistTracks => {
-                    if selected < self.state.selected_playlist_tracks.len() {
-                        Some(self.state.selected_playlist_tracks[selected].clone())
-                    } else {
-                        None
-                    }
-                }
-                ViewType::LikedSongs => {
-                    if !self.state.liked_songs.is_empty() {
-                        // Use actual liked songs
-                        if selected < self.state.liked_songs.len() {
-                            Some(self.state.liked_songs[selected].clone())
-                        } else {
-                            None
-                        }
-                    } else {
-                        // Use sample liked songs from recently played for now
-                        if selected < self.state.recently_played.len() {
-                            Some(self.state.recently_played[selected].clone())
-                        } else {
-                            None
-                        }
-                    }
-                }
-                _ => {
-                    if selected < self.state.recently_played.len() {
-                        Some(self.state.recently_played[selected].clone())
-                    } else {
-                        None
-                    }
-                }
-            };
+    fn log_error(&mut self, message: String) {
+        let timestamp = chrono::Local::now().format("%H:%M:%S").to_string();
+        let log_entry = format!("[{}] {}", timestamp, message);
+        self.state.error_logs.push(log_entry);
+
+        // Keep only last 100 log entries
+        if self.state.error_logs.len() > 100 {
+            self.state.error_logs.remove(0);
+        }
+    }
 
-            if let Some(track) = track {
-                if user_authenticated {
-                    if let Some(client) = self.spotify_client.clone() {
-                        match client.add_to_queue(&track.uri).await {
-                            Ok(_) => {
-                                self.state.auth_message = format!("🚀 Added to queue (high priority): {}", track.name);
-                                self.log_radio(format!("🚀 HIGH PRIORITY: {} added to queue", track.name));
+    fn log_radio(&mut self, message: String) {
+        let timestamp = chrono::Local::now().format("%H:%M:%S").to_string();
+        let log_entry = format!("[{}] RADIO: {}", timestamp, message);
+        self.state.error_logs.push(log_entry);
 
-                                // Refresh the queue after a short delay to get the updated queue
-                                // and move manually added tracks to higher priority
-                                tokio::spawn({
-                                    let client_clone = client.clone();
-                                    async move {
-                                        tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
-                                        let _ = client_clone.get_queue().await;
-                                    }
-                                });
-                            },
-                            Err(e) => {
-                                self.log_error(format!("❌ QUEUE ERROR: {}", e));
-                                let error_msg = e.to_string();
-                                if error_msg.contains("NO_ACTIVE_DEVICE") {
-                                    self.state.auth_message = "❌ No active device! Open Spotify app first.".to_string();
-                                } else if error_msg.contains("PREMIUM_REQUIRED") {
-                                    self.state.auth_message = "❌ Spotify Premium required for queue control.".to_string();
-                                } else {
-                                    self.state.auth_message = format!("❌ Queue error: {}", e);
-                                }
-                            }
-                        }
-                    }
-                } else {
-                    self.state.auth_message = "❌ Authentication required for queue control".to_string();
-                }
+        // Keep only last 100 log entries
+        if self.state.error_logs.len() > 100 {
+            self.state.error_logs.remove(0);
+        }
+    }
+
+    fn add_selected_to_queue(&mut self, next: bool) {
+        if let Some(track) = self.list_state.selected().and_then(|row| self.track_at(row)) {
+            self.queue_tracks(vec![track], next);
+        }
+    }
+
+    /// `d` in the Queue view: takes the selected track out of the managed
+    /// queue. Spotify's own queue can't be edited.
+    fn remove_from_managed_queue(&mut self) {
+        let Some(selected) = self.list_state.selected() else {
+            return;
+        };
+        match self.managed_queue.remove(selected) {
+            Some(track) => {
+                self.save_managed_queue();
+                self.state.auth_message = format!("🗑️ Removed from the queue: {}", track.name);
             }
+            None => self.state.auth_message = "⚠️ Tracks already sent to Spotify can't be removed".to_string(),
+        }
+    }
+
+    /// `K`/`J` in the Queue view: moves the selected managed track up or down.
+    fn move_in_managed_queue(&mut self, up: bool) {
+        let Some(selected) = self.list_state.selected() else {
+            return;
+        };
+        if selected >= self.managed_queue.tracks.len() {
+            self.state.auth_message = "⚠️ Tracks already sent to Spotify can't be moved".to_string();
+            return;
+        }
+        if let Some(moved) = self.managed_queue.move_by(selected, up) {
+            self.save_managed_queue();
+            self.list_state.select(Some(moved));
         }
     }
 
@@ -883,32 +2283,7 @@ impl App {
                 let new_volume = (current_volume as i16 + delta as i16).clamp(0, 100) as u8;
                 self.log_error(format!("Volume change: {} -> {} (delta: {})", current_volume, new_volume, delta));
 
-                match client.set_volume(new_volume).await {
-                    Ok(_) => {
-                        self.log_error(format!("✅ Volume API call successful: set to {}%", new_volume));
-                        self.state.volume = new_volume;
-                        self.state.auth_message = format!("🔊 Volume: {}% ({}{})",
-                            new_volume,
-                            if delta > 0 { "+" } else { "" },
-                            delta
-                        );
-
-                        // Sync after volume change to update display
-                        tokio::time::sleep(tokio::time::Duration::from_millis(200)).await;
-                        self.sync_playback_state().await;
-                    },
-                    Err(e) => {
-                        self.log_error(format!("❌ Volume API call failed: {}", e));
-                        let error_msg = e.to_string();
-                        if error_msg.contains("NO_ACTIVE_DEVICE") {
-                            self.state.auth_message = "❌ No active device! Open Spotify app first.".to_string();
-                        } else if error_msg.contains("PREMIUM_REQUIRED") {
-                            self.state.auth_message = "❌ Spotify Premium required for volume control.".to_string();
-                        } else {
-                            self.state.auth_message = format!("❌ Volume error: {}", e);
-                        }
-                    }
-                }
+                self.set_volume_to(new_volume).await;
             } else {
                 self.state.auth_message = "❌ No Spotify client available".to_string();
             }
@@ -917,80 +2292,271 @@ impl App {
         }
     }
 
+    async fn set_volume_to(&mut self, volume: u8) {
+        if !self.state.user_authenticated {
+            self.state.auth_message = "❌ User authentication required for volume control".to_string();
+            return;
+        }
+        match self.send_control(Request::Volume { change: Adjust::To(volume) }, async |client| client.set_volume(volume).await).await {
+            Ok(_) => {
+                self.log_error(format!("✅ Volume API call successful: set to {}%", volume));
+                self.state.volume = volume;
+                self.state.auth_message = format!("🔊 Volume: {}%", volume);
+
+                // Sync after volume change to update display
+                tokio::time::sleep(tokio::time::Duration::from_millis(200)).await;
+                self.sync_playback_state().await;
+            },
+            Err(e) => {
+                self.log_error(format!("❌ Volume API call failed: {}", e));
+                let error_msg = e.to_string();
+                if error_msg.contains("NO_ACTIVE_DEVICE") {
+                    self.state.auth_message = "❌ No active device! Open Spotify app first.".to_string();
+                } else if error_msg.contains("PREMIUM_REQUIRED") {
+                    self.state.auth_message = "❌ Spotify Premium required for volume control.".to_string();
+                } else {
+                    self.state.auth_message = format!("❌ Volume error: {}", e);
+                }
+            }
+        }
+    }
+
     pub async fn sync_playback_state(&mut self) {
-        if self.state.user_authenticated {
-            if let Some(ref client) = self.spotify_client {
-                match client.get_current_playback().await {
-                    Ok(Some(playback)) => {
-                        self.state.current_playback = Some(playback.clone());
-                        self.state.is_playing = playback.is_playing;
-
-                        // Debug info about progress data
-                        let progress_info = if let Some(progress_ms) = playback.progress_ms {
-                            format!(" [✅Progress: {}ms]", progress_ms)
-                        } else {
-                            " [❌No Progress Data]".to_string()
-                        };
+        self.refresh_playback(true).await;
+    }
 
-                        if let Some(track) = playback.item {
-                            self.state.current_track = Some(track.clone());
-                            if playback.is_playing {
-                                self.state.auth_message = format!("✅ Playing: {}{}", track.name, progress_info);
-                            } else {
-                                self.state.auth_message = format!("✅ Paused: {}{}", track.name, progress_info);
-                            }
+    /// Fetches the playback state and schedules the next poll. Background
+    /// polls (`announce` false) leave the status line alone unless they fail.
+    async fn refresh_playback(&mut self, announce: bool) {
+        self.next_sync = Instant::now() + self.clock.next_poll();
+        if self.state.user_authenticated && self.spotify_client.is_some() {
+            match self.fetch_playback().await {
+                Ok(Some(playback)) => {
+                    let events = self.events.update(Some(&playback));
+                    self.clock.sync(Some(&playback));
+                    self.next_sync = Instant::now() + self.clock.next_poll();
+                    self.state.current_playback = Some(playback.clone());
+                    self.state.is_playing = playback.is_playing;
+
+                    // Debug info about progress data
+                    let progress_info = if let Some(progress_ms) = playback.progress_ms {
+                        format!(" [✅Progress: {}ms]", progress_ms)
+                    } else {
+                        " [❌No Progress Data]".to_string()
+                    };
+
+                    let message = if let Some(track) = playback.item {
+                        self.state.current_track = Some(track.clone());
+                        if playback.is_playing {
+                            format!("✅ Playing: {}{}", track.name, progress_info)
                         } else {
-                            self.state.current_track = None;
-                            self.state.auth_message = if playback.is_playing {
-                                format!("✅ SYNC SUCCESS: ▶ Playing...{}", progress_info)
-                            } else {
-                                format!("✅ SYNC SUCCESS: ⏸️ Paused{}", progress_info)
-                            };
+                            format!("✅ Paused: {}{}", track.name, progress_info)
                         }
-                    }
-                    Ok(None) => {
-                        // No active playback
-                        self.state.current_playback = None;
-                        self.state.is_playing = false;
+                    } else {
                         self.state.current_track = None;
+                        if playback.is_playing {
+                            format!("✅ SYNC SUCCESS: ▶ Playing...{}", progress_info)
+                        } else {
+                            format!("✅ SYNC SUCCESS: ⏸️ Paused{}", progress_info)
+                        }
+                    };
+                    if announce {
+                        self.state.auth_message = message;
+                    }
+                    self.handle_playback_events(events).await;
+                }
+                Ok(None) => {
+                    // No active playback
+                    let events = self.events.update(None);
+                    self.clock.sync(None);
+                    self.next_sync = Instant::now() + self.clock.next_poll();
+                    self.state.current_playback = None;
+                    self.state.is_playing = false;
+                    self.state.current_track = None;
+                    if announce {
                         self.state.auth_message = "⏹️ No active playback - start playing on Spotify first".to_string();
                     }
-                    Err(e) => {
-                        self.log_error(format!("❌ SYNC ERROR: {}", e));
-                        self.state.auth_message = format!("❌ Sync failed: {}", e);
+                    self.handle_playback_events(events).await;
+                }
+                Err(e) => {
+                    self.log_error(format!("❌ SYNC ERROR: {}", e));
+                    self.state.auth_message = format!("❌ Sync failed: {}", e);
+                }
+            }
+        }
+        // Asking the daemon is cheap: it answers from its own cache
+        if self.daemon.is_some() {
+            self.next_sync = self.next_sync.min(Instant::now() + DAEMON_POLL);
+        }
+    }
+
+    /// Current playback from the daemon when attached, otherwise from Spotify.
+    /// A daemon that stops answering is dropped and Spotify is asked instead.
+    async fn fetch_playback(&mut self) -> anyhow::Result<Option<CurrentPlayback>> {
+        if let Some(daemon) = self.daemon.as_mut() {
+            match daemon.request(Request::Playback).await {
+                Ok(data) => return Ok(serde_json::from_value(data)?),
+                Err(Failure::Daemon { message, .. }) => return Err(anyhow::anyhow!(message)),
+                Err(e) => {
+                    self.daemon = None;
+                    self.log_error(format!("⚠️ Daemon went away, polling Spotify directly: {}", e));
+                }
+            }
+        }
+        match self.spotify_client.clone() {
+            Some(client) => client.get_current_playback().await,
+            None => Ok(None),
+        }
+    }
+
+    /// Sends a playback command through the attached daemon, so its cache
+    /// and its other clients see the change at once. Without a daemon, or
+    /// once it stops answering, `direct` asks Spotify instead.
+    async fn send_control(
+        &mut self,
+        request: Request,
+        direct: impl AsyncFnOnce(SpotifyClient) -> anyhow::Result<()>,
+    ) -> anyhow::Result<()> {
+        if let Some(daemon) = self.daemon.as_mut() {
+            match daemon.request(request).await {
+                Ok(_) => return Ok(()),
+                Err(Failure::Daemon { message, .. }) => return Err(anyhow::anyhow!(message)),
+                Err(e) => {
+                    self.daemon = None;
+                    self.log_error(format!("⚠️ Daemon went away, sending commands to Spotify directly: {}", e));
+                }
+            }
+        }
+        match self.spotify_client.clone() {
+            Some(client) => direct(client).await,
+            None => Err(anyhow::anyhow!("No Spotify client available")),
+        }
+    }
+
+    /// The app's own reactions to playback changes, whoever caused them.
+    async fn handle_playback_events(&mut self, events: Vec<PlaybackEvent>) {
+        for event in events {
+            if let (PlaybackEvent::TrackChanged { current, .. }, Some(session)) = (&event, &self.party) {
+                session.party.lock().unwrap().now_playing = current.as_ref().map(|t| format!("{} - {}", t.name, artist_names(t)));
+            }
+            match event {
+                PlaybackEvent::TrackChanged { current: Some(track), .. } => {
+                    // Tracks started on other devices count as played too
+                    self.state.recently_played_storage.add_track(*track, None);
+                    let _ = self.state.recently_played_storage.save();
+                    // Keep "Up next" current while the Now Playing view is open
+                    if self.state.current_view == ViewType::Player {
+                        if let Some(client) = self.spotify_client.clone() {
+                            if let Ok(response) = client.get_queue().await {
+                                self.spotify_queue = response.queue;
+                                self.show_queue();
+                            }
+                        }
                     }
                 }
+                PlaybackEvent::VolumeChanged { volume } => self.state.volume = volume,
+                PlaybackEvent::ContextChanged { .. } => self.resolve_playing_context().await,
+                _ => {}
             }
         }
     }
 
     pub async fn run<B: ratatui::backend::Backend>(&mut self, terminal: &mut Terminal<B>) -> io::Result<()> {
-        use std::time::{Duration, Instant};
-        let mut last_sync = Instant::now();
-        let sync_interval = Duration::from_secs(3); // Sync every 3 seconds
+        use std::time::Duration;
 
         loop {
             terminal.draw(|f| self.ui(f))?;
 
-            // Auto-sync every 3 seconds if playing
-            if self.state.is_playing && self.state.user_authenticated && last_sync.elapsed() >= sync_interval {
-                self.sync_playback_state().await;
-                last_sync = Instant::now();
+            // Poll on the clock's schedule, which lands right after the track should end
+            if self.state.user_authenticated && Instant::now() >= self.next_sync {
+                self.refresh_playback(false).await;
+            }
+            if self.state.user_authenticated {
+                self.drive_radio();
+                self.feed_managed_queue(false).await;
+            }
+
+            // A digit nobody followed up on is a view shortcut
+            if let Some(digit) = self.navigator.take_expired_digit() {
+                self.select_view_shortcut(digit).await;
             }
 
             // Poll for events with timeout to allow periodic syncing
             if event::poll(Duration::from_millis(100))? {
-                if let Event::Key(key) = event::read()? {
+                let event = event::read()?;
+                if let Event::Mouse(mouse) = event {
+                    if self.playlist_picker.is_none() && self.context_menu.is_none() {
+                        self.handle_mouse(mouse).await;
+                    }
+                    continue;
+                }
+                if let Event::Paste(text) = event {
+                    if self.playlist_picker.is_none() && self.context_menu.is_none() {
+                        self.handle_paste(&text).await;
+                    }
+                    continue;
+                }
+                if let Event::Key(key) = event {
                 if key.kind == KeyEventKind::Press {
+                    if self.playlist_picker.is_some() {
+                        self.handle_picker_key(key.code).await;
+                        continue;
+                    }
+                    if self.context_menu.is_some() {
+                        self.handle_menu_key(key.code).await;
+                        continue;
+                    }
+                    if self.command_line.is_some() {
+                        if self.handle_command_line_key(key).await {
+                            return Ok(());
+                        }
+                        continue;
+                    }
+                    let confirm_remove = std::mem::take(&mut self.confirm_remove);
+                    if self.filter_input {
+                        self.handle_filter_key(key.code);
+                        continue;
+                    }
+                    if !self.input_mode {
+                        match self.navigator.handle_key(&key) {
+                            NavResult::Move { motion, count } => {
+                                self.apply_motion(motion, count);
+                                continue;
+                            }
+                            NavResult::Jump(Jump::Artist) => {
+                                self.go_to_artist().await;
+                                continue;
+                            }
+                            NavResult::Jump(Jump::Album) => {
+                                self.go_to_album().await;
+                                continue;
+                            }
+                            NavResult::Pending => continue,
+                            NavResult::Unhandled(digit) => {
+                                if let Some(digit) = digit {
+                                    self.select_view_shortcut(digit).await;
+                                }
+                            }
+                        }
+                    }
                     match key.code {
                         KeyCode::Char('q') => return Ok(()),
+                        KeyCode::Char('/') if !self.input_mode && self.has_track_view() => {
+                            // Filter the current list instead of searching Spotify
+                            self.filter_input = true;
+                            self.current_track_view_mut();
+                        }
                         KeyCode::Char('/') => {
                             self.input_mode = true;
-                            self.state.current_view = ViewType::Search;
+                            if self.state.current_view != ViewType::Search {
+                                self.navigate(Location::view(ViewType::Search));
+                            }
                         }
-                        KeyCode::Char('d') if key.modifiers.contains(crossterm::event::KeyModifiers::CONTROL) => {
-                            // Prevent accidental Ctrl+D termination - show warning instead
-                            self.state.auth_message = "⚠️ Use 'q' to quit or '/' to search (not Ctrl+D)".to_string();
+                        KeyCode::Left if key.modifiers.contains(crossterm::event::KeyModifiers::ALT) => {
+                            self.go_back().await;
+                        }
+                        KeyCode::Right if key.modifiers.contains(crossterm::event::KeyModifiers::ALT) => {
+                            self.go_forward().await;
                         }
                         KeyCode::Left if key.modifiers.contains(crossterm::event::KeyModifiers::CONTROL) => {
                             // Switch to previous tab
@@ -1008,78 +2574,51 @@ impl App {
                             // Alt+T: Next track
                             self.next_track().await;
                         }
-                        KeyCode::Up => {
-                            if !self.input_mode {
-                                if let Some(selected) = self.list_state.selected() {
-                                    if selected > 0 {
-                                        self.list_state.select(Some(selected - 1));
-                                    }
-                                }
-                            }
+                        KeyCode::Tab if !self.input_mode => {
+                            self.cycle_focus(true);
                         }
-                        KeyCode::Down => {
-                            if !self.input_mode {
-                                let item_count = match self.state.current_view {
-                                    ViewType::Search => {
-                                        if let Some(ref search_results) = self.state.search_results {
-                                            if let Some(ref tracks) = search_results.tracks {
-                                                tracks.items.len()
-                                            } else {
-                                                self.state.recently_played.len()
-                                            }
-                                        } else {
-                                            self.state.recently_played.len()
-                                        }
-                                    }
-                                    ViewType::LikedSongs => {
-                                        if !self.state.liked_songs.is_empty() {
-                                            self.state.liked_songs.len()
-                                        } else {
-                                            9 // Number of sample items shown
-                                        }
-                                    }
-                                    ViewType::Playlists => self.state.user_playlists.len().max(10), // Sample playlists
-                                    ViewType::PlaylistTracks => self.state.selected_playlist_tracks.len(),
-                                    ViewType::Queue => self.state.queue.len().max(2), // At least show "No tracks" message
-                                    ViewType::Albums => self.state.user_albums.len(),
-                                    ViewType::Artists => self.state.user_artists.len(),
-                                    ViewType::Errors => self.state.error_logs.len(),
-                                    _ => 0,
-                                };
-
-                                if let Some(selected) = self.list_state.selected() {
-                                    if selected + 1 < item_count {
-                                        self.list_state.select(Some(selected + 1));
-                                    }
-                                }
-                            }
+                        KeyCode::BackTab if !self.input_mode => {
+                            self.cycle_focus(false);
+                        }
+                        KeyCode::Menu if !self.input_mode => {
+                            self.open_context_menu().await;
+                        }
+                        KeyCode::F(2) => {
+                            // F2: Toggle ASCII / screen reader friendly rendering
+                            self.glyphs.toggle();
+                            self.state.auth_message = if self.glyphs.is_ascii() {
+                                "Accessible ASCII mode on (F2 to turn off)".to_string()
+                            } else {
+                                "✅ Unicode mode on (F2 for accessible ASCII mode)".to_string()
+                            };
                         }
                         KeyCode::Enter => {
                             if self.input_mode {
                                 self.input_mode = false;
                                 self.trigger_search().await;
+                            } else if self.focused() == Focus::Sidebar {
+                                self.activate_sidebar().await;
                             } else {
-                                match self.state.current_view {
-                                    ViewType::Playlists => {
-                                        self.open_selected_playlist().await;
-                                    }
-                                    _ => {
-                                        self.play_selected_track().await;
-                                    }
-                                }
+                                self.activate_selected().await;
                             }
                         }
+                        KeyCode::Esc if !self.input_mode && self.selection.in_range_mode() => {
+                            self.selection.cancel_range();
+                            self.state.auth_message.clear();
+                        }
+                        KeyCode::Esc if !self.input_mode && self.current_track_view().is_some_and(|v| !v.filter.is_empty()) => {
+                            // First Esc drops the filter, the next one leaves the list
+                            self.current_track_view_mut().filter.clear();
+                            self.list_state.select(Some(0));
+                        }
                         KeyCode::Esc => {
                             if self.input_mode {
                                 self.input_mode = false;
                             } else {
                                 match self.state.current_view {
-                                    ViewType::PlaylistTracks => {
-                                        // Go back to playlists view
-                                        self.state.current_view = ViewType::Playlists;
-                                        self.state.selected_playlist = None;
-                                        self.state.selected_playlist_tracks.clear();
-                                        self.list_state.select(Some(0)); // Reset selection
+                                    ViewType::PlaylistTracks | ViewType::AlbumTracks | ViewType::ArtistAlbums => {
+                                        // Back to wherever this list was opened from
+                                        self.go_back().await;
                                         self.state.auth_message.clear();
                                     }
                                     _ => {
@@ -1106,43 +2645,6 @@ impl App {
                                 self.state.search_query.push(c);
                             } else {
                                 match c {
-                                    '1' => {
-                                        self.state.current_view = ViewType::Search;
-                                        self.state.auth_message.clear();
-                                        self.list_state.select(Some(0));
-                                    }
-                                    '2' => {
-                                        self.state.current_view = ViewType::LikedSongs;
-                                        self.state.auth_message.clear();
-                                        self.list_state.select(Some(0));
-                                    }
-                                    '3' => {
-                                        self.state.current_view = ViewType::Playlists;
-                                        self.state.auth_message.clear();
-                                        self.list_state.select(Some(0));
-                                    }
-                                    '4' => {
-                                        self.state.current_view = ViewType::Queue;
-                                        self.state.auth_message.clear();
-                                        self.list_state.select(Some(0));
-                                        // Auto-load queue when switching to queue view
-                                        self.load_queue().await;
-                                    }
-                                    '5' => {
-                                        self.state.current_view = ViewType::Albums;
-                                        self.state.auth_message.clear();
-                                        self.list_state.select(Some(0));
-                                    }
-                                    '6' => {
-                                        self.state.current_view = ViewType::Artists;
-                                        self.state.auth_message.clear();
-                                        self.list_state.select(Some(0));
-                                    }
-                                    '7' => {
-                                        self.state.current_view = ViewType::Errors;
-                                        self.state.auth_message.clear();
-                                        self.list_state.select(Some(0));
-                                    }
                                     ' ' => {
                                         self.toggle_playback().await;
                                     }
@@ -1175,31 +2677,82 @@ impl App {
                                         self.state.auth_message = "🔉 Volume Down pressed...".to_string();
                                         self.volume_down().await;
                                     }
+                                    'm' | 'M' if self.has_marks() => {
+                                        self.queue_marked_tracks(c == 'M');
+                                    }
                                     'm' | 'M' => {
-                                        self.log_error("🎵 'm' key pressed - adding selected track to queue".to_string());
-                                        self.add_selected_to_queue().await;
+                                        self.add_selected_to_queue(c == 'M');
                                     }
                                     's' | 'S' => {
                                         self.state.auth_message = "🔄 Syncing with Spotify...".to_string();
                                         self.log_error("🔄 's' key pressed - syncing playback state".to_string());
                                         self.sync_playback_state().await;
                                     }
+                                    ')' if self.has_marks() => {
+                                        self.toggle_like_marked_tracks().await;
+                                    }
                                     ')' => {
                                         self.toggle_like_selected_track().await;
                                     }
+                                    'x' => {
+                                        self.toggle_mark();
+                                    }
+                                    'v' => {
+                                        self.toggle_range();
+                                    }
+                                    'X' => {
+                                        self.clear_marks();
+                                    }
+                                    'a' => {
+                                        self.open_playlist_picker().await;
+                                    }
+                                    'd' if self.state.current_view == ViewType::Party => {
+                                        self.veto_submission(false);
+                                    }
+                                    'D' if self.state.current_view == ViewType::Party => {
+                                        self.veto_submission(true);
+                                    }
+                                    't' | 'T' => {
+                                        self.thumbs(c == 't').await;
+                                    }
+                                    'd' if self.state.current_view == ViewType::Queue => {
+                                        self.remove_from_managed_queue();
+                                    }
+                                    'K' | 'J' if self.state.current_view == ViewType::Queue => {
+                                        self.move_in_managed_queue(c == 'K');
+                                    }
+                                    'd' => {
+                                        self.remove_from_playlist(confirm_remove).await;
+                                    }
+                                    'y' => {
+                                        self.copy_track_uris();
+                                    }
+                                    'e' => {
+                                        self.open_context_menu().await;
+                                    }
+                                    ':' => {
+                                        self.open_command_line();
+                                    }
                                     ']' => {
                                         self.toggle_shuffle().await;
                                     }
+                                    'o' => {
+                                        self.cycle_sort(false);
+                                    }
+                                    'O' => {
+                                        self.cycle_sort(true);
+                                    }
                                     _ => {
                                         self.state.auth_message.clear();
                                     }
                                 }
                             }
                         }
+                        KeyCode::Backspace if self.input_mode => {
+                            self.state.search_query.pop();
+                        }
                         KeyCode::Backspace => {
-                            if self.input_mode {
-                                self.state.search_query.pop();
-                            }
+                            self.go_back().await;
                         }
                         _ => {}
                     }
@@ -1209,116 +2762,540 @@ impl App {
         }
     }
 
-    async fn switch_tab(&mut self, direction: i32) {
-        let tabs = [
-            ViewType::Search,
-            ViewType::LikedSongs,
-            ViewType::Playlists,
-            ViewType::Queue,
-            ViewType::Albums,
-            ViewType::Artists,
-            ViewType::Errors,
-        ];
+    /// Enter on the selected row: open playlists and albums, play tracks.
+    async fn activate_selected(&mut self) {
+        match self.state.current_view {
+            ViewType::Playlists => {
+                self.open_selected_playlist().await;
+            }
+            ViewType::Albums | ViewType::ArtistAlbums => {
+                self.open_selected_album().await;
+            }
+            ViewType::Artists => {
+                if let Some(artist) = self.list_state.selected().and_then(|i| self.state.user_artists.get(i)).cloned() {
+                    self.open_artist(artist).await;
+                }
+            }
+            ViewType::Party => self.approve_submission(),
+            _ => {
+                self.play_selected_track().await;
+            }
+        }
+    }
+
+    async fn handle_mouse(&mut self, mouse: MouseEvent) {
+        let (column, row) = (mouse.column, mouse.row);
+        match mouse.kind {
+            MouseEventKind::ScrollDown => self.apply_motion(Motion::Down, Some(3)),
+            MouseEventKind::ScrollUp => self.apply_motion(Motion::Up, Some(3)),
+            MouseEventKind::Down(MouseButton::Left) => {
+                if let Some(visible_row) = self.hit_areas.list_row(column, row) {
+                    self.focus = Focus::Main;
+                    let clicked = self.list_state.offset() + visible_row;
+                    if clicked < self.list_labels().len() {
+                        self.list_state.select(Some(clicked));
+                        if self.clicks.click(clicked) {
+                            self.activate_selected().await;
+                        }
+                    }
+                } else if let Some(entry) = self.hit_areas.sidebar_row(column, row) {
+                    if entry < NAV_ENTRIES.len() {
+                        self.focus = Focus::Sidebar;
+                        self.sidebar_selected = entry;
+                        self.activate_sidebar().await;
+                    }
+                } else if let Some(visible_row) = self.hit_areas.sidebar_playlist_row(column, row) {
+                    let index = self.sidebar_playlists_state.offset() + visible_row;
+                    if index < self.state.user_playlists.len() {
+                        self.sidebar_selected = NAV_ENTRIES.len() + index;
+                        self.activate_sidebar().await;
+                    }
+                } else if let Some(ratio) = self.hit_areas.seek_ratio(column, row) {
+                    let duration_ms = self.state.current_track.as_ref().map(|t| t.duration_ms as u64).unwrap_or(0);
+                    if duration_ms > 0 {
+                        self.seek_to((duration_ms as f64 * ratio) as u64).await;
+                    }
+                } else if let Some(ratio) = self.hit_areas.volume_ratio(column, row) {
+                    self.volume_drag = Some((ratio * 100.0).round() as u8);
+                }
+            }
+            MouseEventKind::Down(MouseButton::Right) => {
+                if let Some(visible_row) = self.hit_areas.list_row(column, row) {
+                    self.focus = Focus::Main;
+                    let clicked = self.list_state.offset() + visible_row;
+                    if clicked < self.list_labels().len() {
+                        self.list_state.select(Some(clicked));
+                        self.open_context_menu().await;
+                    }
+                }
+            }
+            MouseEventKind::Drag(MouseButton::Left) if self.volume_drag.is_some() => {
+                if let Some(ratio) = self.hit_areas.volume_drag_ratio(column) {
+                    self.volume_drag = Some((ratio * 100.0).round() as u8);
+                }
+            }
+            MouseEventKind::Up(MouseButton::Left) => {
+                if let Some(volume) = self.volume_drag.take() {
+                    self.set_volume_to(volume).await;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    async fn seek_to(&mut self, position_ms: u64) {
+        if !self.state.user_authenticated {
+            self.state.auth_message = "❌ Authentication required for playback".to_string();
+            return;
+        }
+        match self.send_control(Request::Seek { change: Adjust::To(position_ms) }, async |client| client.seek(position_ms).await).await {
+            Ok(_) => {
+                if let Some(ref mut playback) = self.state.current_playback {
+                    playback.progress_ms = Some(position_ms);
+                }
+                self.clock.seek(position_ms);
+                self.state.auth_message = format!("⏩ Seeked to {}", format_duration(position_ms));
+            }
+            Err(e) => {
+                let error_msg = e.to_string();
+                if error_msg.contains("NO_ACTIVE_DEVICE") {
+                    self.state.auth_message = "❌ No active device! Open Spotify app first.".to_string();
+                } else if error_msg.contains("PREMIUM_REQUIRED") {
+                    self.state.auth_message = "❌ Spotify Premium required for seeking.".to_string();
+                } else {
+                    self.state.auth_message = format!("❌ Seek error: {}", e);
+                }
+            }
+        }
+    }
+
+    /// `1`-`8` jump straight to a view.
+    async fn select_view_shortcut(&mut self, digit: char) {
+        let view = match digit.to_digit(10).and_then(|d| NAV_ENTRIES.get((d as usize).wrapping_sub(1))) {
+            Some((_, view)) => view.clone(),
+            None => return,
+        };
+
+        self.navigate(Location::view(view));
+        self.state.auth_message.clear();
+
+        match self.state.current_view {
+            // Auto-load queue when switching to queue view
+            ViewType::Queue | ViewType::Player => self.load_queue().await,
+            ViewType::Albums if self.state.user_albums.is_empty() => self.load_saved_albums().await,
+            ViewType::Artists if self.state.user_artists.is_empty() => self.load_followed_artists().await,
+            _ => {}
+        }
+    }
+
+    /// Text of each row in the current view, used for item counts and jump-to-letter.
+    fn list_labels(&self) -> Vec<String> {
+        match self.state.current_view {
+            ViewType::Search | ViewType::LikedSongs | ViewType::PlaylistTracks | ViewType::AlbumTracks | ViewType::Queue => {
+                let tracks = self.current_tracks();
+                self.visible_indices().into_iter().map(|i| tracks[i].name.clone()).collect()
+            }
+            ViewType::Playlists => self.state.user_playlists.iter().map(|p| p.name.clone()).collect(),
+            ViewType::Albums => self.state.user_albums.iter().map(|a| a.name.clone()).collect(),
+            ViewType::ArtistAlbums => self.state.selected_artist_albums.iter().map(|a| a.name.clone()).collect(),
+            ViewType::Artists => self.state.user_artists.iter().map(|a| a.name.clone()).collect(),
+            // Logs are shown newest first
+            ViewType::Errors => self.state.error_logs.iter().rev().cloned().collect(),
+            ViewType::Player => Vec::new(),
+            ViewType::Party => match &self.party {
+                Some(session) => session.party.lock().unwrap().pending().iter().map(|s| s.track.name.clone()).collect(),
+                None => Vec::new(),
+            },
+        }
+    }
+
+    fn apply_motion(&mut self, motion: Motion, count: Option<usize>) {
+        match self.focused() {
+            Focus::Sidebar => self.apply_sidebar_motion(motion, count),
+            Focus::Preview => self.apply_preview_motion(motion, count),
+            Focus::Main => self.apply_list_motion(motion, count),
+        }
+    }
+
+    fn apply_list_motion(&mut self, motion: Motion, count: Option<usize>) {
+        self.preview_scroll = 0;
+        let labels = self.list_labels();
+        if labels.is_empty() {
+            return;
+        }
+        let selected = self.list_state.selected().unwrap_or(0).min(labels.len() - 1);
+
+        let row = match motion {
+            Motion::Find(letter) => navigation::find_next(&labels, selected, letter),
+            Motion::Playing => {
+                let row = self.playing_row();
+                if row.is_none() {
+                    self.state.auth_message = "⚠️ The playing track isn't in this list".to_string();
+                }
+                row
+            }
+            _ => Some(navigation::target(motion, count, selected, labels.len(), self.page_size)),
+        };
+
+        if let Some(row) = row {
+            self.list_state.select(Some(row));
+        }
+    }
+
+    fn apply_sidebar_motion(&mut self, motion: Motion, count: Option<usize>) {
+        let labels = self.sidebar_labels();
+        let selected = self.sidebar_selected.min(labels.len() - 1);
+        let row = match motion {
+            Motion::Find(letter) => navigation::find_next(&labels, selected, letter),
+            Motion::Playing => self.playing_playlist().map(|i| i + NAV_ENTRIES.len()),
+            _ => Some(navigation::target(motion, count, selected, labels.len(), self.page_size)),
+        };
+        if let Some(row) = row {
+            self.sidebar_selected = row;
+        }
+    }
+
+    fn apply_preview_motion(&mut self, motion: Motion, count: Option<usize>) {
+        let times = count.unwrap_or(1) as u16;
+        let page = self.page_size as u16;
+        self.preview_scroll = match motion {
+            Motion::Up => self.preview_scroll.saturating_sub(times),
+            Motion::Down => self.preview_scroll.saturating_add(times),
+            Motion::PageUp => self.preview_scroll.saturating_sub(page.saturating_mul(times)),
+            Motion::PageDown => self.preview_scroll.saturating_add(page.saturating_mul(times)),
+            Motion::HalfPageUp => self.preview_scroll.saturating_sub(page / 2 * times),
+            Motion::HalfPageDown => self.preview_scroll.saturating_add(page / 2 * times),
+            Motion::Top => 0,
+            _ => self.preview_scroll,
+        };
+    }
+
+    /// Navigation entry names followed by the user's playlists.
+    fn sidebar_labels(&self) -> Vec<String> {
+        NAV_ENTRIES
+            .iter()
+            .map(|(name, _)| name.to_string())
+            .chain(self.state.user_playlists.iter().map(|p| p.name.clone()))
+            .collect()
+    }
+
+    /// Enter in the sidebar: switch view or open the playlist, then hand focus to it.
+    async fn activate_sidebar(&mut self) {
+        let row = self.sidebar_selected;
+        if row < NAV_ENTRIES.len() {
+            if let Some(digit) = char::from_digit(row as u32 + 1, 10) {
+                self.select_view_shortcut(digit).await;
+            }
+        } else {
+            self.open_playlist_at(row - NAV_ENTRIES.len()).await;
+        }
+        self.focus = Focus::Main;
+    }
+
+    /// The preview pane only exists next to the search results.
+    fn has_preview(&self) -> bool {
+        self.state.current_view == ViewType::Search
+    }
 
-        let current_index = tabs.iter().position(|tab| *tab == self.state.current_view).unwrap_or(0);
-        let new_index = if direction > 0 {
-            (current_index + 1) % tabs.len()
+    fn focused(&self) -> Focus {
+        if self.focus == Focus::Preview && !self.has_preview() {
+            Focus::Main
+        } else {
+            self.focus
+        }
+    }
+
+    fn cycle_focus(&mut self, forward: bool) {
+        let mut panes = vec![Focus::Sidebar, Focus::Main];
+        if self.has_preview() {
+            panes.push(Focus::Preview);
+        }
+        let current = panes.iter().position(|p| *p == self.focused()).unwrap_or(1);
+        let next = if forward {
+            (current + 1) % panes.len()
         } else {
-            if current_index == 0 { tabs.len() - 1 } else { current_index - 1 }
+            (current + panes.len() - 1) % panes.len()
         };
+        self.focus = panes[next];
+        self.preview_scroll = 0;
+    }
 
-        self.state.current_view = tabs[new_index].clone();
-        self.state.auth_message.clear();
-        self.list_state.select(Some(0));
+    /// URI of the playlist, album or collection Spotify is playing from.
+    fn playing_context_uri(&self) -> Option<&str> {
+        self.state.current_playback.as_ref()?.context.as_ref().map(|c| c.uri.as_str())
+    }
 
-        // Auto-load content for specific views when switching to them
-        match self.state.current_view {
-            ViewType::Queue => {
-                // Auto-load queue when switching to queue view
-                self.load_queue().await;
+    /// Names the playlist, album or artist being played from, fetching it
+    /// when it isn't loaded already.
+    async fn resolve_playing_context(&mut self) {
+        let context = match self.state.current_playback.as_ref().and_then(|p| p.context.clone()) {
+            Some(context) => context,
+            None => {
+                self.playing_context = None;
+                return;
             }
-            _ => {}
+        };
+        if self.playing_context.as_ref().is_some_and(|c| c.uri == context.uri) {
+            return;
+        }
+
+        let name = match SpotifyUri::parse(&context.uri) {
+            Ok(uri) => self.context_name(&uri).await,
+            // Liked Songs plays as `spotify:user:<id>:collection`
+            Err(_) if context.context_type == "collection" => Some("Liked Songs".to_string()),
+            Err(_) => None,
+        };
+        let kind = match context.context_type.as_str() {
+            "collection" => "Library".to_string(),
+            other => other.get(..1).map(|c| c.to_uppercase() + &other[1..]).unwrap_or_default(),
+        };
+        self.playing_context = Some(PlayingContext {
+            kind,
+            name: name.unwrap_or_else(|| context.uri.clone()),
+            uri: context.uri,
+        });
+    }
+
+    async fn context_name(&self, uri: &SpotifyUri) -> Option<String> {
+        let id = uri.id().as_str();
+        match uri.kind() {
+            ItemKind::Playlist => {
+                let known = self.state.user_playlists.iter().chain(self.state.selected_playlist.as_ref());
+                if let Some(playlist) = known.into_iter().find(|p| p.id == id) {
+                    return Some(playlist.name.clone());
+                }
+                self.spotify_client.as_ref()?.get_playlist(id).await.ok().map(|p| p.name)
+            }
+            ItemKind::Album => {
+                let album = self.state.current_track.as_ref().and_then(|t| t.album.as_ref());
+                if let Some(album) = album.filter(|a| a.id == id) {
+                    return Some(album.name.clone());
+                }
+                self.spotify_client.as_ref()?.get_album(id).await.ok().map(|a| a.name)
+            }
+            ItemKind::Artist => self.spotify_client.as_ref()?.get_artist(id).await.ok().map(|a| a.name),
+            _ => None,
+        }
+    }
+
+    /// Index in `user_playlists` of the playlist being played from.
+    fn playing_playlist(&self) -> Option<usize> {
+        let context = self.playing_context_uri()?;
+        self.state.user_playlists.iter().position(|p| {
+            p.spotify_uri().is_ok_and(|uri| uri.to_string() == context)
+        })
+    }
+
+    /// Display row of the currently playing track in the current view.
+    fn playing_row(&self) -> Option<usize> {
+        let playing_id = &self.state.current_track.as_ref()?.id;
+        let tracks = self.current_tracks();
+        self.visible_indices().iter().position(|i| tracks[*i].id == *playing_id)
+    }
+
+    async fn switch_tab(&mut self, direction: i32) {
+        // Nested views count as the tab they were opened from
+        let open_view = match self.state.current_view {
+            ViewType::PlaylistTracks => ViewType::Playlists,
+            ViewType::AlbumTracks => ViewType::Albums,
+            ViewType::ArtistAlbums => ViewType::Artists,
+            ref view => view.clone(),
+        };
+        let current_index = NAV_ENTRIES.iter().position(|(_, view)| *view == open_view).unwrap_or(0);
+        let new_index = if direction > 0 {
+            (current_index + 1) % NAV_ENTRIES.len()
+        } else {
+            (current_index + NAV_ENTRIES.len() - 1) % NAV_ENTRIES.len()
+        };
+
+        if let Some(digit) = char::from_digit(new_index as u32 + 1, 10) {
+            self.select_view_shortcut(digit).await;
         }
     }
 
     fn ui(&mut self, f: &mut Frame) {
         let chunks = Layout::default()
             .direction(Direction::Vertical)
-            .constraints([Constraint::Min(0), Constraint::Length(3)])
+            .constraints([Constraint::Min(0), Constraint::Length(1), Constraint::Length(3)])
             .split(f.area());
 
+        self.sync_selection();
+
         let main_chunks = Layout::default()
             .direction(Direction::Horizontal)
             .constraints([Constraint::Percentage(25), Constraint::Percentage(75)])
             .split(chunks[0]);
 
+        self.drawing_pane = Some(Focus::Sidebar);
         self.render_sidebar(f, main_chunks[0]);
+        self.drawing_pane = Some(Focus::Main);
         self.render_main_content(f, main_chunks[1]);
-        self.render_player(f, chunks[1]);
+        self.drawing_pane = None;
+        self.render_transport(f, chunks[1]);
+        self.render_player(f, chunks[2]);
+
+        if self.context_menu.is_some() {
+            self.render_context_menu(f);
+        }
+        if self.playlist_picker.is_some() {
+            self.render_playlist_picker(f);
+        }
+    }
+
+    fn render_context_menu(&mut self, f: &mut Frame) {
+        let (title, items) = match self.context_menu {
+            Some(ref menu) => {
+                let items: Vec<ListItem> = menu.items
+                    .iter()
+                    .map(|item| match item.disabled {
+                        Some(ref reason) => self.item(format!("{} ({})", item.label, reason)).style(self.theme.muted_style()),
+                        None => self.item(&item.label),
+                    })
+                    .collect();
+                (menu.title.clone(), items)
+            }
+            None => return,
+        };
+
+        let width = self.context_menu.as_ref().map(|menu| {
+            let longest = menu.items.iter().map(|item| {
+                text::display_width(&item.label) + item.disabled.as_ref().map(|r| text::display_width(r) + 3).unwrap_or(0)
+            });
+            longest.chain([text::display_width(&title)]).max().unwrap_or(0) + 4
+        }).unwrap_or(0);
+        let [area] = Layout::vertical([Constraint::Length(items.len() as u16 + 2)]).flex(Flex::Center).areas(f.area());
+        let [area] = Layout::horizontal([Constraint::Length(width as u16)]).flex(Flex::Center).areas(area);
+
+        let list = List::new(items)
+            .block(self.theme.block(title).border_style(self.theme.focused_border_style()))
+            .style(self.theme.text_style())
+            .highlight_style(self.theme.highlight_style());
+
+        f.render_widget(Clear, area);
+        if let Some(menu) = self.context_menu.as_mut() {
+            f.render_stateful_widget(list, area, &mut menu.state);
+        }
     }
 
-    fn render_sidebar(&self, f: &mut Frame, area: Rect) {
+    fn render_playlist_picker(&mut self, f: &mut Frame) {
+        let [area] = Layout::vertical([Constraint::Percentage(60)]).flex(Flex::Center).areas(f.area());
+        let [area] = Layout::horizontal([Constraint::Percentage(50)]).flex(Flex::Center).areas(area);
+
+        let count = self.playlist_picker.as_ref().map(|p| p.uris.len()).unwrap_or(0);
+        let items: Vec<ListItem> = self.state.user_playlists.iter().map(|p| self.data_item(&p.name)).collect();
+        let title = format!("Add {} tracks to playlist (Enter to add, Esc to cancel)", count);
+        let list = List::new(items)
+            .block(self.theme.block(self.glyphs.text(&title)).border_style(self.theme.focused_border_style()))
+            .style(self.theme.text_style())
+            .highlight_style(self.theme.highlight_style());
+
+        f.render_widget(Clear, area);
+        if let Some(picker) = self.playlist_picker.as_mut() {
+            f.render_stateful_widget(list, area, &mut picker.state);
+        }
+    }
+
+    fn render_sidebar(&mut self, f: &mut Frame, area: Rect) {
         let sidebar_chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
-                Constraint::Length(8),
+                Constraint::Length(NAV_ENTRIES.len() as u16 + 2),
                 Constraint::Length(8),
                 Constraint::Min(0),
             ])
             .split(area);
 
-        // Navigation section
-        let library_items = vec![
-            ListItem::new("1. Search"),
-            ListItem::new("2. Liked Songs"),
-            ListItem::new("3. Playlists"),
-            ListItem::new("4. Queue"),
-            ListItem::new("5. Albums"),
-            ListItem::new("6. Artists"),
-            ListItem::new("7. Errors/Logs"),
-        ];
+        let sidebar_focused = self.focused() == Focus::Sidebar;
+        let context_uri = self.playing_context_uri().unwrap_or_default().to_string();
+        let context_type = self.state.current_playback.as_ref()
+            .and_then(|p| p.context.as_ref())
+            .map(|c| c.context_type.clone())
+            .unwrap_or_default();
+
+        // Navigation section: ▶ marks the open view, ♫ what Spotify is playing from
+        let open_view = match self.state.current_view {
+            ViewType::PlaylistTracks => ViewType::Playlists,
+            ViewType::AlbumTracks => ViewType::Albums,
+            ViewType::ArtistAlbums => ViewType::Artists,
+            ref view => view.clone(),
+        };
+        let nav_items: Vec<ListItem> = NAV_ENTRIES
+            .iter()
+            .enumerate()
+            .map(|(i, (name, view))| {
+                let marker = if *view == open_view { "▶" } else { " " };
+                let playing = match view {
+                    ViewType::LikedSongs => context_type == "collection",
+                    ViewType::Playlists => context_type == "playlist",
+                    ViewType::Albums => context_type == "album",
+                    _ => false,
+                };
+                let text = format!("{} {}. {}{}", marker, i + 1, name, if playing { " ♫" } else { "" });
+                if *view == open_view {
+                    self.item(text).style(self.theme.accent_style())
+                } else {
+                    self.item(text)
+                }
+            })
+            .collect();
 
-        let library_list = List::new(library_items)
-            .block(Block::default().title("Navigation").borders(Borders::ALL))
-            .style(Style::default().fg(Color::White))
-            .highlight_style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD));
+        let nav_list = List::new(nav_items)
+            .block(self.block("Navigation"))
+            .style(self.theme.text_style())
+            .highlight_style(self.theme.highlight_style());
 
-        f.render_widget(library_list, sidebar_chunks[0]);
+        let mut nav_state = ListState::default();
+        if sidebar_focused && self.sidebar_selected < NAV_ENTRIES.len() {
+            nav_state.select(Some(self.sidebar_selected));
+        }
+        self.hit_areas.sidebar_nav = Some(self.block("").inner(sidebar_chunks[0]));
+        f.render_stateful_widget(nav_list, sidebar_chunks[0], &mut nav_state);
 
         // Playlists section
+        let open_playlist = match self.state.current_view {
+            ViewType::PlaylistTracks => self.state.selected_playlist.as_ref().map(|p| p.id.clone()),
+            _ => None,
+        };
         let playlist_items: Vec<ListItem> = if self.state.user_playlists.is_empty() {
             vec![
-                ListItem::new("Metallica Greatest Hits"),
-                ListItem::new("Metallica: Essentials"),
-                ListItem::new("Metallica: Live"),
-                ListItem::new("Metallica: Complete"),
-                ListItem::new("METALLICA live"),
-                ListItem::new("Metallica: Studio Albums"),
-                ListItem::new("Metallica: Live Sao Paulo '99"),
-                ListItem::new("Metallica - Black Album"),
-                ListItem::new("Metallica - Whisky in the Jar"),
-                ListItem::new("METALLICA Pallavicini"),
-                ListItem::new("Metallica 2002 Soliloquy 2019"),
-                ListItem::new("Metallica Family Playlist"),
-                ListItem::new("Metallica Load / Reload (Good Ones)"),
-                ListItem::new("Metallica Chile 15 Abril 2020 - Estadio Nacional"),
+                self.item("No playlists loaded").style(self.theme.muted_style()),
+                self.item("Press 'u' to sign in").style(self.theme.muted_style()),
             ]
         } else {
             self.state.user_playlists
                 .iter()
-                .map(|p| ListItem::new(p.name.clone()))
+                .map(|p| {
+                    let is_open = open_playlist.as_deref() == Some(p.id.as_str());
+                    let is_playing = p.spotify_uri().is_ok_and(|uri| uri.to_string() == *context_uri);
+                    let text = format!("{}{}{}",
+                        if is_open { self.glyphs.text("▶ ") } else { String::new() },
+                        p.name,
+                        if is_playing { self.glyphs.text(" ♫") } else { String::new() });
+                    if is_playing {
+                        self.data_item(text).style(self.theme.playing_style())
+                    } else {
+                        self.data_item(text)
+                    }
+                })
                 .collect()
         };
 
         let playlists_list = List::new(playlist_items)
-            .block(Block::default().title("Playlists").borders(Borders::ALL))
-            .style(Style::default().fg(Color::White))
-            .highlight_style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD));
-
-        f.render_widget(playlists_list, sidebar_chunks[2]);
+            .block(self.block("Playlists"))
+            .style(self.theme.text_style())
+            .highlight_style(self.theme.highlight_style());
+
+        let selected_playlist = self.sidebar_selected.checked_sub(NAV_ENTRIES.len())
+            .filter(|_| sidebar_focused && !self.state.user_playlists.is_empty());
+        self.sidebar_playlists_state.select(selected_playlist);
+        self.hit_areas.sidebar_playlists = Some(self.block("").inner(sidebar_chunks[2]));
+        f.render_stateful_widget(playlists_list, sidebar_chunks[2], &mut self.sidebar_playlists_state);
     }
 
     fn render_main_content(&mut self, f: &mut Frame, area: Rect) {
+        // Each list view registers its rows while drawing
+        self.hit_areas.list_rows = None;
+
         match self.state.current_view {
             ViewType::Search => self.render_search(f, area),
             ViewType::LikedSongs => self.render_library(f, area),
@@ -1326,9 +3303,12 @@ impl App {
             ViewType::PlaylistTracks => self.render_playlist_tracks(f, area),
             ViewType::Queue => self.render_queue(f, area),
             ViewType::Albums => self.render_albums(f, area),
+            ViewType::AlbumTracks => self.render_album_tracks(f, area),
+            ViewType::ArtistAlbums => self.render_artist_albums(f, area),
             ViewType::Artists => self.render_artists(f, area),
             ViewType::Errors => self.render_errors(f, area),
-            ViewType::Player => self.render_player_detail(f, area),
+            ViewType::Player => self.render_now_playing(f, area),
+            ViewType::Party => self.render_party(f, area),
         }
     }
 
@@ -1346,92 +3326,39 @@ impl App {
 
         // Search input
         let search_style = if self.input_mode {
-            Style::default().fg(Color::Yellow)
+            self.theme.input_style()
         } else {
-            Style::default().fg(Color::White)
+            self.theme.text_style()
         };
 
         let search_input = Paragraph::new(self.state.search_query.as_str())
             .style(search_style)
-            .block(Block::default().borders(Borders::ALL).title("Search"));
+            .block(self.block("Search"));
 
         f.render_widget(search_input, search_chunks[0]);
 
         // Show tracks - either search results or recently played
-        if let Some(ref results) = self.state.search_results {
-            // Show search results
-            if let Some(ref tracks) = results.tracks {
-                let track_items: Vec<ListItem> = tracks
-                    .items
-                    .iter()
-                    .enumerate()
-                    .map(|(i, track)| {
-                        let artist_names: String = track
-                            .artists
-                            .iter()
-                            .map(|a| a.name.clone())
-                            .collect::<Vec<_>>()
-                            .join(", ");
-
-                        let item_text = format!("{}. {} - {}", i + 1, track.name, artist_names);
-                        ListItem::new(item_text)
-                    })
-                    .collect();
-
-                let tracks_list = List::new(track_items)
-                    .block(Block::default().title("🔍 Search Results (↑↓ to navigate, Enter to play)").borders(Borders::ALL))
-                    .style(Style::default().fg(Color::White))
-                    .highlight_style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD));
-
-                f.render_stateful_widget(tracks_list, content_chunks[0], &mut self.list_state);
-            }
+        if self.state.search_results.is_some() {
+            self.render_track_table(f, content_chunks[0], &self.glyphs.text("🔍 Search Results (↑↓ to navigate, Enter to play)"), self.theme.highlight_style());
         } else if !self.state.search_query.is_empty() && self.input_mode {
             // Show "type to search" when in input mode
-            let searching_text = Paragraph::new("🔍 Type your search and press Enter...")
-                .block(Block::default().title("Search (press '/' to search)").borders(Borders::ALL));
+            let searching_text = Paragraph::new(self.glyphs.text("🔍 Type your search and press Enter..."))
+                .block(self.block("Search (press '/' to search)"));
             f.render_widget(searching_text, content_chunks[0]);
         } else {
-            // Show recently played tracks when not searching
-            let recent_items: Vec<ListItem> = self.state.recently_played
-                .iter()
-                .enumerate()
-                .map(|(i, track)| {
-                    let artist_names: String = track
-                        .artists
-                        .iter()
-                        .map(|a| a.name.clone())
-                        .collect::<Vec<_>>()
-                        .join(", ");
-
-                    let item_text = format!("{}. {} - {}", i + 1, track.name, artist_names);
-                    ListItem::new(item_text)
-                })
-                .collect();
-
-            let tracks_list = List::new(recent_items)
-                .block(Block::default().title("🎵 Recently Played (↑↓ to navigate, Enter to play)").borders(Borders::ALL))
-                .style(Style::default().fg(Color::White))
-                .highlight_style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD));
-
-            f.render_stateful_widget(tracks_list, content_chunks[0], &mut self.list_state);
+            self.render_track_table(f, content_chunks[0], &self.glyphs.text("🎵 Recently Played (↑↓ to navigate, Enter to play)"), self.theme.highlight_style());
         }
 
         // Render preview panel
+        self.drawing_pane = Some(Focus::Preview);
         self.render_track_preview(f, content_chunks[1]);
+        self.drawing_pane = Some(Focus::Main);
     }
 
     fn render_track_preview(&self, f: &mut Frame, area: Rect) {
-        let preview_text = if let Some(selected) = self.list_state.selected() {
+        let preview_text: Vec<Line> = if let Some(selected) = self.list_state.selected() {
             // Get the selected track
-            let track = if let Some(ref results) = self.state.search_results {
-                if let Some(ref tracks) = results.tracks {
-                    tracks.items.get(selected)
-                } else {
-                    None
-                }
-            } else {
-                self.state.recently_played.get(selected)
-            };
+            let track = self.track_index(selected).and_then(|i| self.current_tracks().get(i));
 
             if let Some(track) = track {
                 let artist_names = track
@@ -1450,174 +3377,181 @@ impl App {
                 let duration_seconds = track.duration_ms / 1000;
                 let duration_formatted = format!("{}:{:02}", duration_seconds / 60, duration_seconds % 60);
 
-                let mut preview_info = format!(
-                    "🎵 {}\n\n👤 Artist(s):\n{}\n\n💿 Album:\n{}\n\n⏱️ Duration:\n{}\n\n🎚️ Popularity:\n{}/100\n\n🆔 Track ID:\n{}",
-                    track.name,
-                    artist_names,
-                    album_name,
-                    duration_formatted,
-                    track.popularity,
-                    track.id
-                );
+                // Labels go through the glyph filter, the track's own data doesn't
+                let label = |text: &str| self.glyphs.text(text);
+                let mut preview_info: Vec<Line> = vec![
+                    Line::from(format!("{}{}", label("🎵 "), track.name)),
+                    Line::from(""),
+                    Line::from(label("👤 Artist(s):")),
+                    Line::from(artist_names),
+                    Line::from(""),
+                    Line::from(label("💿 Album:")),
+                    Line::from(album_name),
+                    Line::from(""),
+                    Line::from(label("⏱️ Duration:")),
+                    Line::from(duration_formatted),
+                    Line::from(""),
+                    Line::from(label("🎚️ Popularity:")),
+                    Line::from(format!("{}/100", track.popularity)),
+                    Line::from(""),
+                    Line::from(label("🆔 Track ID:")),
+                    Line::from(track.id.clone()),
+                ];
 
                 // Always add a progress section at the very bottom
-                preview_info.push_str("\n\n\n═══ PLAYBACK STATUS ═══");
+                preview_info.push(Line::from(""));
+                preview_info.push(Line::from(""));
+                preview_info.push(Line::styled(label("═══ PLAYBACK STATUS ═══"), self.theme.title_style()));
 
                 // Check if this track is currently playing and add progress info
                 if let Some(ref current_track) = self.state.current_track {
                     if current_track.id == track.id {
-                        preview_info.push_str("\n🎵 CURRENTLY PLAYING 🎵");
+                        preview_info.push(Line::styled(label("🎵 CURRENTLY PLAYING 🎵"), self.theme.playing_style()));
 
                         if let Some(ref playback) = self.state.current_playback {
                             let status_icon = if playback.is_playing { "▶" } else { "⏸️" };
-                            preview_info.push_str(&format!("\n{} Status: {}", status_icon,
-                                if playback.is_playing { "Playing" } else { "Paused" }));
+                            preview_info.push(Line::from(label(&format!("{} Status: {}", status_icon,
+                                if playback.is_playing { "Playing" } else { "Paused" }))));
 
                             // Add progress info
-                            if let Some(progress_ms) = playback.progress_ms {
+                            if playback.progress_ms.is_some() {
+                                let progress_ms = self.clock.position_ms();
                                 let progress_sec = progress_ms / 1000;
                                 let progress_min = progress_sec / 60;
                                 let progress_sec_remainder = progress_sec % 60;
                                 let duration_min = duration_seconds / 60;
                                 let duration_sec_remainder = duration_seconds % 60;
 
-                                preview_info.push_str(&format!("\n⏱️ Progress: {}:{:02} / {}:{:02}",
+                                preview_info.push(Line::from(label(&format!("⏱️ Progress: {}:{:02} / {}:{:02}",
                                     progress_min, progress_sec_remainder,
-                                    duration_min, duration_sec_remainder));
-
-                                // Add progress bar
-                                let progress_percentage = (progress_ms as f64 / track.duration_ms as f64 * 100.0) as u8;
-                                let bar_width = 20; // Bigger bar for better visibility
-                                let filled = (progress_percentage as f64 / 100.0 * bar_width as f64) as usize;
-                                let bar = "█".repeat(filled) + &"░".repeat(bar_width - filled);
-                                preview_info.push_str(&format!("\n[{}] {}%", bar, progress_percentage));
+                                    duration_min, duration_sec_remainder))));
+
+                                // Add progress bar (bigger bar for better visibility)
+                                preview_info.push(self.progress_bar_line(progress_ms, track.duration_ms as u64, 20));
                             } else {
-                                preview_info.push_str("\n⏱️ Progress: Unavailable");
-                                let bar = "░".repeat(20);
-                                preview_info.push_str(&format!("\n[{}] No data", bar));
+                                preview_info.push(Line::from(label("⏱️ Progress: Unavailable")));
+                                let bar = self.glyphs.bar_empty().repeat(20);
+                                preview_info.push(Line::styled(format!("[{}] No data", bar), self.theme.muted_style()));
                             }
 
-                            preview_info.push_str(&format!("\n🎧 Device: {}", playback.device.name));
-                            preview_info.push_str("\n═════════════════════");
+                            preview_info.push(Line::from(format!("{}{}", label("🎧 Device: "), playback.device.name)));
+                            preview_info.push(Line::styled(label("═════════════════════"), self.theme.title_style()));
                         } else {
-                            preview_info.push_str("\n❌ No playback data");
-                            preview_info.push_str("\n═════════════════════");
+                            preview_info.push(Line::styled(label("❌ No playback data"), self.theme.error_style()));
+                            preview_info.push(Line::styled(label("═════════════════════"), self.theme.title_style()));
                         }
                     } else {
-                        preview_info.push_str("\n⏹️ Not currently playing");
-                        preview_info.push_str("\n💡 Press 's' to sync, then");
-                        preview_info.push_str("\n   select the playing track");
-                        preview_info.push_str("\n═════════════════════");
+                        preview_info.push(Line::from(label("⏹️ Not currently playing")));
+                        preview_info.push(Line::styled(label("💡 Press 's' to sync, then"), self.theme.muted_style()));
+                        preview_info.push(Line::styled(label("   select the playing track"), self.theme.muted_style()));
+                        preview_info.push(Line::styled(label("═════════════════════"), self.theme.title_style()));
                     }
                 } else {
-                    preview_info.push_str("\n⏹️ No active playback");
-                    preview_info.push_str("\n💡 Start music on Spotify");
-                    preview_info.push_str("\n   then press 's' to sync");
-                    preview_info.push_str("\n═════════════════════");
+                    preview_info.push(Line::from(label("⏹️ No active playback")));
+                    preview_info.push(Line::styled(label("💡 Start music on Spotify"), self.theme.muted_style()));
+                    preview_info.push(Line::styled(label("   then press 's' to sync"), self.theme.muted_style()));
+                    preview_info.push(Line::styled(label("═════════════════════"), self.theme.title_style()));
                 }
 
                 preview_info
             } else {
-                "No track selected".to_string()
+                vec![Line::styled("No track selected", self.theme.muted_style())]
             }
         } else {
-            "Select a track to see preview".to_string()
+            vec![Line::styled("Select a track to see preview", self.theme.muted_style())]
         };
 
         let preview_widget = Paragraph::new(preview_text)
-            .block(Block::default().borders(Borders::ALL).title("🔍 Track Preview"))
+            .block(self.block("🔍 Track Preview"))
             .wrap(Wrap { trim: true })
-            .style(Style::default().fg(Color::Cyan));
+            .scroll((self.preview_scroll, 0))
+            .style(self.theme.accent_style());
 
         f.render_widget(preview_widget, area);
     }
 
     fn render_library(&mut self, f: &mut Frame, area: Rect) {
-        let library_items: Vec<ListItem> = if self.state.liked_songs.is_empty() {
-            vec![
-                ListItem::new("No liked songs loaded"),
-                ListItem::new("Press 'L' to load your liked songs"),
-                ListItem::new(""),
-                ListItem::new("Sample Liked Songs:"),
-                ListItem::new("♥ Bohemian Rhapsody - Queen"),
-                ListItem::new("♥ Hotel California - Eagles"),
-                ListItem::new("♥ Stairway to Heaven - Led Zeppelin"),
-                ListItem::new("♥ Sweet Child O' Mine - Guns N' Roses"),
-                ListItem::new("♥ Imagine - John Lennon"),
-            ]
-        } else {
-            self.state.liked_songs
-                .iter()
-                .enumerate()
-                .map(|(i, track)| {
-                    let artists = track.artists.iter()
-                        .map(|a| a.name.clone())
-                        .collect::<Vec<String>>()
-                        .join(", ");
-                    ListItem::new(format!("{}. ♥ {} - {}", i + 1, track.name, artists))
-                })
-                .collect()
-        };
+        if !self.state.liked_songs.is_empty() {
+            self.render_track_table(f, area, &self.glyphs.text("🎵 Liked Songs (↑↓ to navigate, Enter to play, L to load)"), self.theme.liked_highlight_style());
+            return;
+        }
+
+        let library_items = vec![
+            self.item("No liked songs loaded").style(self.theme.muted_style()),
+            self.item("Press 'L' to load your liked songs").style(self.theme.muted_style()),
+            self.item(""),
+            self.item("Sample Liked Songs:").style(self.theme.muted_style()),
+            self.item("♥ Bohemian Rhapsody - Queen"),
+            self.item("♥ Hotel California - Eagles"),
+            self.item("♥ Stairway to Heaven - Led Zeppelin"),
+            self.item("♥ Sweet Child O' Mine - Guns N' Roses"),
+            self.item("♥ Imagine - John Lennon"),
+        ];
 
         let library_list = List::new(library_items)
-            .block(Block::default().title("🎵 Liked Songs (↑↓ to navigate, Enter to play, L to load)").borders(Borders::ALL))
-            .style(Style::default().fg(Color::White))
-            .highlight_style(Style::default().fg(Color::Red).add_modifier(Modifier::BOLD));
+            .block(self.block("🎵 Liked Songs (↑↓ to navigate, Enter to play, L to load)"))
+            .style(self.theme.text_style())
+            .highlight_style(self.theme.liked_highlight_style());
+        self.register_list_area(area, 0);
         f.render_stateful_widget(library_list, area, &mut self.list_state);
     }
 
     fn render_playlists(&mut self, f: &mut Frame, area: Rect) {
         let playlist_items: Vec<ListItem> = if self.state.user_playlists.is_empty() {
             vec![
-                ListItem::new("Metallica - Metallica"),
-                ListItem::new("Master Of Puppets (Remastered) - Metallica"),
-                ListItem::new("Metallica Through The Never (Music From The Motion Picture"),
-                ListItem::new("...And Justice For All - Metallica"),
-                ListItem::new("Ride The Lightning (Remastered) - Metallica"),
-                ListItem::new("Kill 'Em All (Remastered) - Metallica"),
-                ListItem::new("Hardwired...To Self-Destruct - Metallica"),
-                ListItem::new("Death Magnetic - Metallica"),
-                ListItem::new("Load - Metallica"),
-                ListItem::new("Hardwired...To Self-Destruct (Deluxe) - Metallica"),
+                self.item("No playlists loaded").style(self.theme.muted_style()),
+                self.item("Press 'u' to authenticate and load your playlists").style(self.theme.muted_style()),
             ]
         } else {
             self.state.user_playlists
                 .iter()
-                .map(|p| ListItem::new(p.name.clone()))
+                .map(|p| self.data_item(&p.name))
                 .collect()
         };
 
         let playlists_list = List::new(playlist_items)
-            .block(Block::default().title("🎵 Playlists (↑↓ to navigate, Enter to open)").borders(Borders::ALL))
-            .style(Style::default().fg(Color::White))
-            .highlight_style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD));
+            .block(self.block("🎵 Playlists (↑↓ to navigate, Enter to open)"))
+            .style(self.theme.text_style())
+            .highlight_style(self.theme.highlight_style());
 
+        self.register_list_area(area, 0);
         f.render_stateful_widget(playlists_list, area, &mut self.list_state);
     }
 
     fn render_playlist_tracks(&mut self, f: &mut Frame, area: Rect) {
-        let title = if let Some(ref playlist) = self.state.selected_playlist {
-            format!("🎵 {} (↑↓ to navigate, Enter to play, Esc to go back)", playlist.name)
+        let title = self.breadcrumb_title("Enter to play, Esc to go back");
+
+        self.render_track_table(f, area, &title, self.theme.highlight_style());
+    }
+
+    fn render_album_tracks(&mut self, f: &mut Frame, area: Rect) {
+        let title = self.breadcrumb_title("Enter to play, Esc to go back");
+        self.render_track_table(f, area, &title, self.theme.highlight_style());
+    }
+
+    fn render_artist_albums(&mut self, f: &mut Frame, area: Rect) {
+        let album_items: Vec<ListItem> = if self.state.selected_artist_albums.is_empty() {
+            vec![self.item("No albums found").style(self.theme.muted_style())]
         } else {
-            "🎵 Playlist Tracks".to_string()
+            self.state.selected_artist_albums
+                .iter()
+                .map(|album| {
+                    let year = album.release_date.as_deref().map(|d| d.chars().take(4).collect::<String>());
+                    match year {
+                        Some(year) => self.data_item(format!("{} ({})", album.name, year)),
+                        None => self.data_item(&album.name),
+                    }
+                })
+                .collect()
         };
 
-        let track_items: Vec<ListItem> = self.state.selected_playlist_tracks
-            .iter()
-            .map(|track| {
-                let artists = track.artists.iter()
-                    .map(|a| a.name.clone())
-                    .collect::<Vec<String>>()
-                    .join(", ");
-                ListItem::new(format!("{} - {}", track.name, artists))
-            })
-            .collect();
+        let albums_list = List::new(album_items)
+            .block(self.framed(self.breadcrumb_title("Enter to open, Esc to go back")))
+            .style(self.theme.text_style())
+            .highlight_style(self.theme.highlight_style());
 
-        let tracks_list = List::new(track_items)
-            .block(Block::default().title(title).borders(Borders::ALL))
-            .style(Style::default().fg(Color::White))
-            .highlight_style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD));
-        f.render_stateful_widget(tracks_list, area, &mut self.list_state);
+        self.register_list_area(area, 0);
+        f.render_stateful_widget(albums_list, area, &mut self.list_state);
     }
 
     fn render_queue(&mut self, f: &mut Frame, area: Rect) {
@@ -1634,133 +3568,53 @@ impl App {
             });
         }
 
-        let queue_items: Vec<ListItem> = if self.state.queue.is_empty() {
+        let queue_items: Vec<ListItem> = if self.state.queue.is_empty() && self.glyphs.is_ascii() {
+            // Plain sentences instead of the banner so screen readers read it in order
             vec![
-                ListItem::new("╔══════════════════════════════════════════════╗"),
-                ListItem::new("║             🎵 QUEUE IS EMPTY                ║"),
-                ListItem::new("╠══════════════════════════════════════════════╣"),
-                ListItem::new("║  • Press 'Q' to refresh queue                ║"),
-                ListItem::new("║  • Press 'm' on any track to add to queue    ║"),
-                ListItem::new("║  • Play songs from Search/Recently Played    ║"),
-                ListItem::new("║    to auto-populate similar tracks           ║"),
-                ListItem::new("╚══════════════════════════════════════════════╝"),
+                self.item("Queue is empty."),
+                self.item("Press 'Q' to refresh the queue."),
+                self.item("Press 'm' on any track to add it to the queue."),
+                self.item("Play songs from Search or Recently Played to fill it with similar tracks."),
+            ]
+        } else if self.state.queue.is_empty() {
+            vec![
+                self.item("╔══════════════════════════════════════════════╗"),
+                self.item("║             🎵 QUEUE IS EMPTY                ║"),
+                self.item("╠══════════════════════════════════════════════╣"),
+                self.item("║  • Press 'Q' to refresh queue                ║"),
+                self.item("║  • Press 'm' on any track to add to queue    ║"),
+                self.item("║  • Play songs from Search/Recently Played    ║"),
+                self.item("║    to auto-populate similar tracks           ║"),
+                self.item("╚══════════════════════════════════════════════╝"),
             ]
         } else {
-            let mut items = vec![];
-
-            // Calculate maximum song name length to determine column width first
-            let base_track_width = 33; // minimum width
-            let max_track_width = 50; // maximum width to prevent too much expansion
-            let mut actual_track_width = base_track_width;
-
-            // Find the longest track name that would need more space
-            for track in &self.state.queue {
-                let display_len = if track.name.len() > max_track_width - 3 {
-                    max_track_width
-                } else {
-                    track.name.len().max(base_track_width)
-                };
-                actual_track_width = actual_track_width.max(display_len);
-            }
-
-            let artist_width = 20;
-            let time_width = 4;
-
-            // Add header section with dynamic widths
-            items.push(ListItem::new(""));
-
-            // Simple clean table design
-            let track_col_width = actual_track_width + 4; // +4 for "  # " or " ▶ " prefix
-
-            // Build the header without outer borders
-            items.push(ListItem::new("              UP NEXT              "));
-            items.push(ListItem::new(""));
-
-            let column_header = format!("  # {:<width$} │ {:<artist_width$} │ {:^time_width$}",
-                "TRACK NAME",
-                "ARTIST",
-                "TIME",
-                width = actual_track_width,
-                artist_width = artist_width,
-                time_width = time_width);
-            items.push(ListItem::new(column_header));
-
-            // Make separator match the exact column spacing of data rows
-            let separator = format!("{}─┼─{}─┼─{}",
-                "─".repeat(track_col_width),
-                "─".repeat(artist_width),
-                "─".repeat(time_width));
-            items.push(ListItem::new(separator));
-
-            // Add queue items with dynamic formatting
-            for (i, track) in self.state.queue.iter().enumerate() {
-                let artist_names: String = track
-                    .artists
-                    .iter()
-                    .map(|a| a.name.clone())
-                    .collect::<Vec<_>>()
-                    .join(", ");
-
-                let duration_sec = track.duration_ms / 1000;
-                let duration_formatted = format!("{}:{:02}", duration_sec / 60, duration_sec % 60);
-
-                // Use dynamic track name formatting - don't truncate unless absolutely necessary
-                let track_name = if track.name.len() > actual_track_width {
-                    format!("{}...", &track.name[..actual_track_width - 3])
-                } else {
-                    track.name.clone()
-                };
-
-                let artists_display = if artist_names.len() > artist_width {
-                    format!("{}...", &artist_names[..artist_width - 3])
-                } else {
-                    artist_names
-                };
-
-                let item_text = if i == 0 {
-                    format!(" ▶ {:<width$} │ {:<artist_width$} │ {:>time_width$}",
-                           track_name,
-                           artists_display,
-                           duration_formatted,
-                           width = actual_track_width,
-                           artist_width = artist_width,
-                           time_width = time_width)
-                } else {
-                    format!("{:>2}. {:<width$} │ {:<artist_width$} │ {:>time_width$}",
-                           i + 1,
-                           track_name,
-                           artists_display,
-                           duration_formatted,
-                           width = actual_track_width,
-                           artist_width = artist_width,
-                           time_width = time_width)
-                };
-                items.push(ListItem::new(item_text));
-            }
-
-            // No footer border needed
-            items.push(ListItem::new(""));
-            let total_tracks = self.state.queue.len();
             let total_duration: u32 = self.state.queue.iter().map(|t| t.duration_ms).sum();
-            let total_minutes = (total_duration / 1000) / 60;
-            items.push(ListItem::new(format!("📊 {} tracks • ~{} minutes total", total_tracks, total_minutes)));
-
-            items
+            let held = self.managed_queue.tracks.len();
+            let title = if held == 0 {
+                format!("🎵 Queue - {} tracks, ~{} min (Enter to play, Q to refresh)",
+                    self.state.queue.len(), total_duration / 1000 / 60)
+            } else {
+                format!("🎵 Queue - {} held here, then {} in Spotify, ~{} min (d remove, K/J move, M play next, Q to refresh)",
+                    held, self.spotify_queue.len(), total_duration / 1000 / 60)
+            };
+            self.render_track_table(f, area, &self.glyphs.text(&title), self.theme.highlight_style());
+            return;
         };
 
         let queue_list = List::new(queue_items)
-            .block(Block::default().title("🎵 Queue (↑↓ to navigate, Enter to play, Q to refresh)").borders(Borders::ALL))
-            .style(Style::default().fg(Color::White))
-            .highlight_style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD));
+            .block(self.block("🎵 Queue (↑↓ to navigate, Enter to play, Q to refresh)"))
+            .style(self.theme.text_style())
+            .highlight_style(self.theme.highlight_style());
 
+        self.register_list_area(area, 0);
         f.render_stateful_widget(queue_list, area, &mut self.list_state);
     }
 
     fn render_errors(&mut self, f: &mut Frame, area: Rect) {
         let error_items: Vec<ListItem> = if self.state.error_logs.is_empty() {
             vec![
-                ListItem::new("No errors or radio logs yet"),
-                ListItem::new("Play tracks from search/recently played to see radio logs here"),
+                self.item("No errors or radio logs yet").style(self.theme.muted_style()),
+                self.item("Play tracks from search/recently played to see radio logs here").style(self.theme.muted_style()),
             ]
         } else {
             // Show logs in reverse order (newest first)
@@ -1769,83 +3623,363 @@ impl App {
                 .rev()
                 .enumerate()
                 .map(|(i, log)| {
-                    ListItem::new(format!("{}. {}", i + 1, log))
+                    self.item(format!("{}. {}", i + 1, log)).style(self.theme.message_style(log))
                 })
                 .collect()
         };
 
         let errors_list = List::new(error_items)
-            .block(Block::default().title("📻 Radio Logs & Errors (Press '7' to view, newest first)").borders(Borders::ALL))
-            .style(Style::default().fg(Color::White))
-            .highlight_style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD));
+            .block(self.block("📻 Radio Logs & Errors (Press '7' to view, newest first)"))
+            .style(self.theme.text_style())
+            .highlight_style(self.theme.highlight_style());
 
+        self.register_list_area(area, 0);
         f.render_stateful_widget(errors_list, area, &mut self.list_state);
     }
 
-    fn render_albums(&self, f: &mut Frame, area: Rect) {
+    fn render_party(&mut self, f: &mut Frame, area: Rect) {
+        let (title, party_items) = match &self.party {
+            None => (
+                "🎉 Party".to_string(),
+                vec![
+                    self.item("Party mode is off.").style(self.theme.muted_style()),
+                    self.item("Type :party to let guests on your network search and submit tracks from their phones.").style(self.theme.muted_style()),
+                ],
+            ),
+            Some(session) => {
+                let party = session.party.lock().unwrap();
+                let title = format!(
+                    "🎉 Party · {} · {} awaiting approval (Enter approve, d veto, D block guest)",
+                    session.url,
+                    party.awaiting_approval()
+                );
+                let mut items: Vec<ListItem> = party
+                    .pending()
+                    .into_iter()
+                    .map(|s| {
+                        let line = format!(
+                            "{}{} {} - {}{}{}",
+                            self.glyphs.text("▲"),
+                            s.votes.len(),
+                            s.track.name,
+                            artist_names(&s.track),
+                            self.glyphs.text(" · from "),
+                            s.from
+                        );
+                        if s.approved {
+                            self.data_item(line).style(self.theme.text_style())
+                        } else {
+                            self.data_item(format!("{}{}", line, self.glyphs.text(" · awaiting approval"))).style(self.theme.warning_style())
+                        }
+                    })
+                    .collect();
+                if items.is_empty() {
+                    items.push(self.item(format!("No submissions yet. Guests can open {}", session.url)).style(self.theme.muted_style()));
+                }
+                (title, items)
+            }
+        };
+
+        let party_list = List::new(party_items)
+            .block(self.block(&title))
+            .style(self.theme.text_style())
+            .highlight_style(self.theme.highlight_style());
+
+        self.register_list_area(area, 0);
+        f.render_stateful_widget(party_list, area, &mut self.list_state);
+    }
+
+    fn render_albums(&mut self, f: &mut Frame, area: Rect) {
         let album_items: Vec<ListItem> = if self.state.user_albums.is_empty() {
             vec![
-                ListItem::new("Metallica"),
-                ListItem::new("Spartan Metallican Visionary"),
-                ListItem::new("Metallicash"),
-                ListItem::new("Metallica: Burbón"),
-                ListItem::new("Metallica Tribute Band"),
-                ListItem::new("Doce Penas do Metallica"),
+                self.item("No saved albums loaded").style(self.theme.muted_style()),
+                self.item("Press 'u' to authenticate, then open this tab again").style(self.theme.muted_style()),
             ]
         } else {
             self.state.user_albums
                 .iter()
-                .map(|a| ListItem::new(a.name.clone()))
+                .map(|album| {
+                    let artists = album.artists.iter()
+                        .map(|a| a.name.clone())
+                        .collect::<Vec<String>>()
+                        .join(", ");
+                    self.data_item(format!("{} - {}", album.name, artists))
+                })
                 .collect()
         };
 
         let albums_list = List::new(album_items)
-            .block(Block::default().title("Artists").borders(Borders::ALL))
-            .style(Style::default().fg(Color::White))
-            .highlight_style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD));
+            .block(self.block("💿 Albums (↑↓ to navigate, Enter to open)"))
+            .style(self.theme.text_style())
+            .highlight_style(self.theme.highlight_style());
 
-        f.render_widget(albums_list, area);
+        self.register_list_area(area, 0);
+        f.render_stateful_widget(albums_list, area, &mut self.list_state);
     }
 
-    fn render_artists(&self, f: &mut Frame, area: Rect) {
+    fn render_artists(&mut self, f: &mut Frame, area: Rect) {
         let artist_items: Vec<ListItem> = if self.state.user_artists.is_empty() {
             vec![
-                ListItem::new("Metallica Greatest Hits"),
-                ListItem::new("Metallica: Essentials"),
-                ListItem::new("Metallica: Live"),
-                ListItem::new("Metallica: Complete"),
-                ListItem::new("METALLICA live"),
-                ListItem::new("Metallica: Studio Albums"),
-                ListItem::new("Metallica: Live São Paulo '99"),
-                ListItem::new("Metallica - Black Album"),
-                ListItem::new("Metallica - Whisky in the Jar"),
-                ListItem::new("METALLICA Pallavicini"),
-                ListItem::new("Metallica 2002 Soliloquy 2019"),
-                ListItem::new("Metallica Family Playlist"),
-                ListItem::new("Metallica Load / Reload (Good Ones)"),
-                ListItem::new("Metallica Chile 15 Abril 2020 - Estadio Nacional"),
+                self.item("No followed artists loaded").style(self.theme.muted_style()),
+                self.item("Press 'u' to authenticate, then open this tab again").style(self.theme.muted_style()),
             ]
         } else {
             self.state.user_artists
                 .iter()
-                .map(|a| ListItem::new(a.name.clone()))
+                .map(|a| self.data_item(&a.name))
                 .collect()
         };
 
         let artists_list = List::new(artist_items)
-            .block(Block::default().title("Playlists").borders(Borders::ALL))
-            .style(Style::default().fg(Color::White))
-            .highlight_style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD));
+            .block(self.block("🎤 Artists (↑↓ to navigate, Enter to open)"))
+            .style(self.theme.text_style())
+            .highlight_style(self.theme.highlight_style());
 
-        f.render_widget(artists_list, area);
+        self.register_list_area(area, 0);
+        f.render_stateful_widget(artists_list, area, &mut self.list_state);
     }
 
-    fn render_player_detail(&self, f: &mut Frame, area: Rect) {
-        let player_text = Paragraph::new("Player Details\n\nTrack information and controls will appear here.")
-            .block(Block::default().title("Player").borders(Borders::ALL))
-            .wrap(Wrap { trim: true });
+    /// Full-screen view of the current track, where it plays from and what
+    /// comes next.
+    fn render_now_playing(&self, f: &mut Frame, area: Rect) {
+        let block = self.block("Now Playing");
+        let inner = block.inner(area);
+        f.render_widget(block, area);
+
+        let track = match self.state.current_track {
+            Some(ref track) => track,
+            None => {
+                let hint = Paragraph::new(self.glyphs.text("Nothing playing. Start something in Spotify, then press s to sync."))
+                    .style(self.theme.muted_style())
+                    .wrap(Wrap { trim: true });
+                f.render_widget(hint, inner);
+                return;
+            }
+        };
+
+        let [info_area, gauge_area, details_area, queue_area] = Layout::vertical([
+            Constraint::Length(4),
+            Constraint::Length(2),
+            Constraint::Length(4),
+            Constraint::Min(0),
+        ])
+        .areas(inner);
+
+        let artists = track.artists.iter().map(|a| a.name.as_str()).collect::<Vec<_>>().join(", ");
+        // Icons go through the glyph filter, names are shown as they are
+        let icon = |text: &str| self.glyphs.text(text);
+        let mut info = vec![
+            Line::styled(format!("{}{}", icon("🎵 "), track.name), self.theme.playing_style()),
+            Line::from(format!("{}{}", icon("🎤 "), artists)),
+        ];
+        if let Some(ref album) = track.album {
+            let year = album.release_date.as_deref().and_then(|d| d.get(..4));
+            info.push(Line::from(match year {
+                Some(year) => format!("{}{} ({})", icon("💿 "), album.name, year),
+                None => format!("{}{}", icon("💿 "), album.name),
+            }));
+        }
+        f.render_widget(Paragraph::new(info).style(self.theme.text_style()), info_area);
+
+        let playback = self.state.current_playback.as_ref();
+        let duration_ms = track.duration_ms as u64;
+        let progress_ms = self.clock.position_ms().min(duration_ms);
+        let ratio = if duration_ms > 0 { progress_ms as f64 / duration_ms as f64 } else { 0.0 };
+        let label = format!("{} / {}", format_duration(progress_ms), format_duration(duration_ms));
+        let gauge_area = Rect { height: 1, ..gauge_area };
+        if self.glyphs.is_ascii() {
+            let mut line = self.gauge_line(ratio, gauge_area.width.saturating_sub(label.len() as u16 + 1) as usize);
+            line.spans.push(Span::raw(format!(" {}", label)));
+            f.render_widget(Paragraph::new(line), gauge_area);
+        } else {
+            let gauge = Gauge::default()
+                .gauge_style(self.theme.progress_style())
+                .ratio(ratio)
+                .label(label)
+                .use_unicode(true);
+            f.render_widget(gauge, gauge_area);
+        }
+
+        let on_off = |on: bool| if on { "on" } else { "off" };
+        let mut details = vec![Line::from(format!(
+            "{} {}",
+            icon(if self.state.is_playing { "▶ Playing" } else { "⏸️ Paused" }),
+            self.playing_context.as_ref().map(|c| format!("from {}{}{}", c.kind, icon(" · "), c.name)).unwrap_or_default(),
+        ))];
+        if let Some(playback) = playback {
+            details.push(Line::from(format!("{}{} ({})", icon("🎧 "), playback.device.name, playback.device.device_type)));
+            details.push(Line::from(icon(&format!(
+                "🔀 Shuffle {}   🔁 Repeat {}   🔊 Volume {}%",
+                on_off(playback.shuffle_state),
+                playback.repeat_state,
+                playback.device.volume_percent.unwrap_or(self.state.volume),
+            ))));
+        }
+        f.render_widget(Paragraph::new(details).style(self.theme.text_style()), details_area);
+
+        let upcoming: Vec<ListItem> = self
+            .state
+            .queue
+            .iter()
+            .take(queue_area.height.saturating_sub(1) as usize)
+            .enumerate()
+            .map(|(i, t)| {
+                let artists = t.artists.iter().map(|a| a.name.as_str()).collect::<Vec<_>>().join(", ");
+                self.data_item(format!("{:>2}. {} - {}  {}", i + 1, t.name, artists, format_duration(t.duration_ms as u64)))
+            })
+            .collect();
+        let title = if upcoming.is_empty() { "Up next: nothing queued" } else { "Up next" };
+        let queue = List::new(upcoming)
+            .block(self.theme.block(self.glyphs.text(title)).borders(Borders::TOP).border_set(self.glyphs.border_set()))
+            .style(self.theme.text_style());
+        f.render_widget(queue, queue_area);
+    }
+
+    /// Draws the current view's tracks with the configured columns. `title`
+    /// is drawn as given, so callers filter their own text through the glyphs.
+    fn render_track_table(&mut self, f: &mut Frame, area: Rect, title: &str, highlight_style: Style) {
+        let playing_id = self.state.current_track.as_ref().map(|t| t.id.clone());
+        let rows = self.visible_indices();
+
+        let mut title = title.to_string();
+        let view = self.current_track_view();
+        if let Some(view) = view.filter(|_| self.filter_input) {
+            title = format!("{} /{}_ ({} of {})", title, view.filter, rows.len(), self.current_tracks().len());
+        } else if let Some(description) = view.and_then(|v| v.describe()) {
+            title = format!("{} [{}] ({} of {})", title, self.glyphs.text(&description), rows.len(), self.current_tracks().len());
+        }
+        let sort = view.and_then(|v| v.sort);
+
+        let marked = self.marked_indices();
+        if !marked.is_empty() {
+            title = format!("{} [{} marked]", title, marked.len());
+        }
+
+        self.register_list_area(area, 1);
+
+        // The table borrows the track list from `self`, so hand it the selection separately
+        let mut list_state = std::mem::take(&mut self.list_state);
+        TrackTable::new(self.current_tracks(), &self.config.track_columns, &self.theme, &self.glyphs)
+            .block(self.framed(title))
+            .playing(playing_id.as_deref())
+            .highlight_style(highlight_style)
+            .rows(rows)
+            .sort(sort)
+            .marked(marked)
+            .render(f, area, &mut list_state);
+        self.list_state = list_state;
+    }
+
+    /// Records where the main list's rows are drawn, for paging and mouse clicks.
+    fn register_list_area(&mut self, area: Rect, header_rows: u16) {
+        let inner = self.block("").inner(area);
+        let rows = Rect {
+            y: inner.y + header_rows.min(inner.height),
+            height: inner.height.saturating_sub(header_rows),
+            ..inner
+        };
+        self.page_size = (rows.height as usize).max(1);
+        self.hit_areas.list_rows = Some(rows);
+    }
+
+    /// A themed block whose title goes through the glyph filter.
+    fn block(&self, title: &str) -> Block<'static> {
+        self.framed(self.glyphs.text(title))
+    }
+
+    /// A themed block with `title` drawn as given. ASCII mode only draws a
+    /// plain top rule so screen readers get one line per pane.
+    fn framed(&self, title: String) -> Block<'static> {
+        let focused = self.drawing_pane.is_some() && self.drawing_pane == Some(self.focused());
+        if self.glyphs.is_ascii() {
+            // No border color to rely on, so say it in the title
+            let title = if focused { format!("> {}", title) } else { title };
+            self.theme.block(title).borders(Borders::TOP).border_set(self.glyphs.border_set())
+        } else if focused {
+            self.theme.block(title).border_style(self.theme.focused_border_style())
+        } else {
+            self.theme.block(title)
+        }
+    }
+
+    /// A list item of the app's own text, run through the glyph filter.
+    fn item(&self, text: impl AsRef<str>) -> ListItem<'static> {
+        ListItem::new(self.glyphs.text(text.as_ref()))
+    }
+
+    /// A list item of Spotify data (names, titles), drawn as it is so
+    /// ASCII mode never rewrites what the user saved.
+    fn data_item(&self, text: impl AsRef<str>) -> ListItem<'static> {
+        ListItem::new(text.as_ref().to_string())
+    }
+
+    /// A `[████░░░░] 42%` bar with the filled part in the theme's progress color.
+    fn progress_bar_line(&self, progress_ms: u64, duration_ms: u64, bar_width: usize) -> Line<'static> {
+        let progress_percentage = if duration_ms > 0 {
+            ((progress_ms as f64 / duration_ms as f64) * 100.0).min(100.0) as u8
+        } else {
+            0
+        };
+        let filled = (progress_percentage as f64 / 100.0 * bar_width as f64) as usize;
+
+        Line::from(vec![
+            Span::raw("["),
+            Span::styled(self.glyphs.bar_filled().repeat(filled), self.theme.progress_style()),
+            Span::styled(self.glyphs.bar_empty().repeat(bar_width - filled), self.theme.muted_style()),
+            Span::raw(format!("] {}%", progress_percentage)),
+        ])
+    }
+
+    /// One-line seek bar and volume bar above the player, both clickable.
+    fn render_transport(&mut self, f: &mut Frame, area: Rect) {
+        if let Some(ref line) = self.command_line {
+            let mut spans = vec![
+                Span::styled(format!(":{}", line.input), self.theme.input_style()),
+                Span::styled("_", self.theme.muted_style()),
+            ];
+            let names = line.completion_labels();
+            if names.len() > 1 {
+                spans.push(Span::styled(format!("   Tab: {}", names.join(" | ")), self.theme.muted_style()));
+            }
+            f.render_widget(Paragraph::new(Line::from(spans)), area);
+            self.hit_areas.seek_bar = None;
+            self.hit_areas.volume_bar = None;
+            return;
+        }
+
+        let chunks = Layout::horizontal([
+            Constraint::Length(14),
+            Constraint::Min(10),
+            Constraint::Length(7),
+            Constraint::Length(15),
+            Constraint::Length(5),
+        ])
+        .split(area);
+
+        let duration_ms = self.state.current_track.as_ref().map(|t| t.duration_ms as u64).unwrap_or(0);
+        let progress_ms = self.clock.position_ms();
+        let ratio = if duration_ms > 0 { progress_ms as f64 / duration_ms as f64 } else { 0.0 };
+        let time = format!(" {} / {}", format_duration(progress_ms), format_duration(duration_ms));
+
+        let volume = self.volume_drag.unwrap_or(self.state.volume);
+
+        f.render_widget(Paragraph::new(time).style(self.theme.text_style()), chunks[0]);
+        f.render_widget(Paragraph::new(self.gauge_line(ratio, chunks[1].width as usize)), chunks[1]);
+        f.render_widget(Paragraph::new(self.glyphs.text("  🔊 ")).style(self.theme.muted_style()), chunks[2]);
+        f.render_widget(Paragraph::new(self.gauge_line(volume as f64 / 100.0, chunks[3].width as usize)), chunks[3]);
+        f.render_widget(Paragraph::new(format!(" {:>3}%", volume)).style(self.theme.text_style()), chunks[4]);
+
+        self.hit_areas.seek_bar = Some(chunks[1]);
+        self.hit_areas.volume_bar = Some(chunks[3]);
+    }
 
-        f.render_widget(player_text, area);
+    /// A bar exactly `width` cells wide, filled up to `ratio`.
+    fn gauge_line(&self, ratio: f64, width: usize) -> Line<'static> {
+        let filled = ((ratio.clamp(0.0, 1.0) * width as f64).round() as usize).min(width);
+        Line::from(vec![
+            Span::styled(self.glyphs.bar_filled().repeat(filled), self.theme.progress_style()),
+            Span::styled(self.glyphs.bar_empty().repeat(width - filled), self.theme.muted_style()),
+        ])
     }
 
     fn render_player(&self, f: &mut Frame, area: Rect) {
@@ -1858,8 +3992,10 @@ impl App {
             ])
             .split(area);
 
-        // Current track info with enhanced details
-        let track_info = if let Some(ref track) = self.state.current_track {
+        // Current track info with enhanced details. Labels go through the
+        // glyph filter, names from Spotify are shown as they are.
+        let label = |text: &str| self.glyphs.text(text);
+        let track_info: Vec<Line> = if let Some(ref track) = self.state.current_track {
             let artist_names: String = track
                 .artists
                 .iter()
@@ -1867,21 +4003,22 @@ impl App {
                 .collect::<Vec<_>>()
                 .join(", ");
 
-            let mut info = format!("🎵 {} - {}", track.name, artist_names);
+            let mut info = vec![Line::styled(format!("{}{} - {}", label("🎵 "), track.name, artist_names), self.theme.playing_style())];
 
             // Add album info if available
             if let Some(ref album) = track.album {
-                info.push_str(&format!("\n💿 Album: {}", album.name));
+                info.push(Line::from(format!("{}{}", label("💿 Album: "), album.name)));
             }
 
             // Add playback status and progress if available
             if let Some(ref playback) = self.state.current_playback {
                 let status_icon = if playback.is_playing { "▶" } else { "⏸️" };
-                info.push_str(&format!("\n{} Status: {}", status_icon,
-                    if playback.is_playing { "Playing" } else { "Paused" }));
+                info.push(Line::from(label(&format!("{} Status: {}", status_icon,
+                    if playback.is_playing { "Playing" } else { "Paused" }))));
 
                 // Add progress info - always show something
-                if let Some(progress_ms) = playback.progress_ms {
+                if playback.progress_ms.is_some() {
+                    let progress_ms = self.clock.position_ms();
                     let duration_ms = track.duration_ms;
                     let progress_sec = progress_ms / 1000;
                     let duration_sec = duration_ms / 1000;
@@ -1890,63 +4027,69 @@ impl App {
                     let duration_min = duration_sec / 60;
                     let duration_sec_remainder = duration_sec % 60;
 
-                    info.push_str(&format!("\n⏱️  Progress: {}:{:02} / {}:{:02}",
+                    info.push(Line::from(label(&format!("⏱️  Progress: {}:{:02} / {}:{:02}",
                         progress_min, progress_sec_remainder,
-                        duration_min, duration_sec_remainder));
+                        duration_min, duration_sec_remainder))));
 
                     // Add progress bar
-                    let progress_percentage = (progress_ms as f64 / duration_ms as f64 * 100.0) as u8;
-                    let bar_width = 20;
-                    let filled = (progress_percentage as f64 / 100.0 * bar_width as f64) as usize;
-                    let bar = "█".repeat(filled) + &"░".repeat(bar_width - filled);
-                    info.push_str(&format!("\n[{}] {}%", bar, progress_percentage));
+                    info.push(self.progress_bar_line(progress_ms, duration_ms as u64, 20));
                 } else {
                     // Show duration even if no progress data
                     let duration_sec = track.duration_ms / 1000;
                     let duration_min = duration_sec / 60;
                     let duration_sec_remainder = duration_sec % 60;
-                    info.push_str(&format!("\n⏱️  Duration: {}:{:02} (Progress unavailable)",
-                        duration_min, duration_sec_remainder));
+                    info.push(Line::from(label(&format!("⏱️  Duration: {}:{:02} (Progress unavailable)",
+                        duration_min, duration_sec_remainder))));
 
                     // Show empty progress bar
-                    let bar = "░".repeat(20);
-                    info.push_str(&format!("\n[{}] No progress data", bar));
+                    let bar = self.glyphs.bar_empty().repeat(20);
+                    info.push(Line::styled(format!("[{}] No progress data", bar), self.theme.muted_style()));
                 }
 
-                // Add device info
-                info.push_str(&format!("\n🎧 Device: {}", playback.device.name));
-
-                // Add shuffle/repeat status
+                // Add device info with shuffle/repeat status
+                let mut device_info = format!("{}{}", label("🎧 Device: "), playback.device.name);
                 if playback.shuffle_state {
-                    info.push_str(" 🔀");
+                    device_info.push_str(&label(" 🔀"));
                 }
                 match playback.repeat_state.as_str() {
-                    "track" => info.push_str(" 🔂"),
-                    "context" => info.push_str(" 🔁"),
+                    "track" => device_info.push_str(&label(" 🔂")),
+                    "context" => device_info.push_str(&label(" 🔁")),
                     _ => {}
                 }
+                info.push(Line::from(device_info));
             } else {
-                info.push_str(&format!("\n{} Status: {}",
+                info.push(Line::from(label(&format!("{} Status: {}",
                     if self.state.is_playing { "▶" } else { "⏸️" },
-                    if self.state.is_playing { "Playing" } else { "Paused" }));
+                    if self.state.is_playing { "Playing" } else { "Paused" }))));
             }
 
-            info.push_str(&format!("\n{}",
-                if self.state.user_authenticated { "✅ Authenticated" } else { "❌ Not authenticated" }));
+            info.push(if self.state.user_authenticated {
+                Line::styled(label("✅ Authenticated"), self.theme.success_style())
+            } else {
+                Line::styled(label("❌ Not authenticated"), self.theme.error_style())
+            });
 
             if !self.state.auth_message.is_empty() {
-                info.push_str(&format!("\n{}", self.state.auth_message));
+                info.push(Line::styled(label(&self.state.auth_message), self.theme.message_style(&self.state.auth_message)));
             }
 
             info
         } else {
-            format!("No track playing\n{}\n{}",
-                if self.state.user_authenticated { "✅ Authenticated for playback" } else { "❌ Press 's' to sync or 'u' to authenticate" },
-                if !self.state.auth_message.is_empty() { &self.state.auth_message } else { "" })
+            let mut info = vec![Line::styled("No track playing", self.theme.muted_style())];
+            info.push(if self.state.user_authenticated {
+                Line::styled(label("✅ Authenticated for playback"), self.theme.success_style())
+            } else {
+                Line::styled(label("❌ Press 's' to sync or 'u' to authenticate"), self.theme.warning_style())
+            });
+            if !self.state.auth_message.is_empty() {
+                info.push(Line::styled(label(&self.state.auth_message), self.theme.message_style(&self.state.auth_message)));
+            }
+            info
         };
 
         let track_widget = Paragraph::new(track_info)
-            .block(Block::default().borders(Borders::ALL).title("Now Playing"));
+            .style(self.theme.text_style())
+            .block(self.block("Now Playing"));
 
         f.render_widget(track_widget, player_chunks[0]);
 
@@ -1962,39 +4105,72 @@ impl App {
             ShuffleMode::On => " 🔀",
             ShuffleMode::SmartShuffle => " 🔀✨",
         };
-        let controls = format!("⏮ Prev | {} | Next ⏭{}            {}\n\nControls:\nEnter: Play | m: Add to Queue | s: Sync | ]: Shuffle\nSpace: Play/Pause | /: Search | ↑↓: Navigate\nn: Next | p: Previous | Alt+R: Prev | Alt+T: Next | q: Quit\n+/-: Volume | u: Auth | r: Refresh Recent | L: Load Liked Songs | Q: Refresh Queue\n1-7: Switch Views | Ctrl+←→: Switch Tabs (7=Errors/Logs)", play_status, shuffle_icon, shuffle_status);
-        let controls_color = if self.state.user_authenticated { Color::Green } else { Color::Yellow };
-        let controls_widget = Paragraph::new(controls)
-            .block(Block::default().borders(Borders::ALL).title("Controls"))
-            .style(Style::default().fg(controls_color));
+        let controls = format!("⏮ Prev | {} | Next ⏭{}            {}\n\nControls:\nEnter: Play | e: Actions Menu | :: Command Line | m: Add to Queue | s: Sync | ]: Shuffle | x/v: Mark | X: Clear Marks | a: Add to Playlist | d: Remove | y: Copy URIs\nSpace: Play/Pause | /: Search (Filter in lists) | o/O: Sort | ↑↓ j/k g/G f<letter>: Navigate\nn: Next | p: Previous | Alt+R: Prev | Alt+T: Next | q: Quit\n+/-: Volume | u: Auth | r: Refresh Recent | L: Load Liked Songs | Q: Refresh Queue\n1-8: Switch Views (8=Now Playing) | Ctrl+←→: Switch Tabs (7=Errors/Logs) | Tab/Shift+Tab: Focus Pane | Alt+←→: Back/Forward | ga/gb: Artist/Album | F2: ASCII Mode", play_status, shuffle_icon, shuffle_status);
+        let controls_style = if self.state.user_authenticated { self.theme.success_style() } else { self.theme.warning_style() };
+        let controls_widget = Paragraph::new(self.glyphs.text(&controls))
+            .block(self.block("Controls"))
+            .style(controls_style);
 
         f.render_widget(controls_widget, player_chunks[1]);
 
         // Volume and status
-        let mut status_info = format!("Volume: {}%\nStatus: {}\nMode: {}",
-            self.state.volume,
-            if self.state.is_playing { "Playing" } else { "Paused" },
-            if self.state.user_authenticated { "Premium" } else { "Browse Only" }
-        );
+        let mut status_info = vec![
+            Line::from(format!("Volume: {}%", self.state.volume)),
+            Line::from(format!("Status: {}", if self.state.is_playing { "Playing" } else { "Paused" })),
+            Line::from(format!("Mode: {}", if self.state.user_authenticated { "Premium" } else { "Browse Only" })),
+            Line::from(""),
+        ];
+
+        let marked = self.marked_indices().len();
+        if marked > 0 {
+            status_info.insert(3, Line::styled(format!("Marked: {} (X to clear)", marked), self.theme.accent_style()));
+        }
+        if let Some(station) = &self.radio {
+            let radio = format!(
+                "{}{}{}",
+                label("📻 "),
+                station.name,
+                label(&format!(" 👍 {} 👎 {} (t/T, :radio stop)", station.thumbs_up, station.thumbs_down))
+            );
+            status_info.insert(3, Line::styled(radio, self.theme.accent_style()));
+        }
 
         // Add auth message (always show something for testing)
         if !self.state.auth_message.is_empty() {
-            status_info.push_str(&format!("\n\nMESSAGE: {}", self.state.auth_message));
+            status_info.push(Line::styled(
+                label(&format!("MESSAGE: {}", self.state.auth_message)),
+                self.theme.message_style(&self.state.auth_message),
+            ));
         } else {
-            status_info.push_str("\n\nTEST: Press 's' to sync");
+            status_info.push(Line::styled("TEST: Press 's' to sync", self.theme.muted_style()));
         }
         let status_widget = Paragraph::new(status_info)
-            .block(Block::default().borders(Borders::ALL).title("Status"))
+            .style(self.theme.text_style())
+            .block(self.block(&match self.navigator.pending() {
+                Some(keys) => format!("Status [{}]", keys),
+                None => "Status".to_string(),
+            }))
             .wrap(Wrap { trim: true });
 
         f.render_widget(status_widget, player_chunks[2]);
     }
 }
 
+/// URIs of `tracks` that Spotify can play; local files have none.
+fn track_uris(tracks: &[Track]) -> Vec<SpotifyUri> {
+    tracks.iter().filter_map(|track| track.spotify_uri().ok()).collect()
+}
+
+/// `m:ss` for a position or length in milliseconds.
+fn format_duration(ms: u64) -> String {
+    let seconds = ms / 1000;
+    format!("{}:{:02}", seconds / 60, seconds % 60)
+}
+
 pub fn setup_terminal() -> io::Result<Terminal<CrosstermBackend<io::Stdout>>> {
     enable_raw_mode()?;
     let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen)?;
+    execute!(stdout, EnterAlternateScreen, EnableMouseCapture, EnableBracketedPaste)?;
     let backend = CrosstermBackend::new(stdout);
     let terminal = Terminal::new(backend)?;
     Ok(terminal)
@@ -2002,7 +4178,7 @@ pub fn setup_terminal() -> io::Result<Terminal<CrosstermBackend<io::Stdout>>> {
 
 pub fn restore_terminal(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>) -> io::Result<()> {
     disable_raw_mode()?;
-    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+    execute!(terminal.backend_mut(), LeaveAlternateScreen, DisableMouseCapture, DisableBracketedPaste)?;
     terminal.show_cursor()?;
     Ok(())
 }
\ No newline at end of file
@@ -0,0 +1,92 @@
+use std::time::{Duration, Instant};
+
+use ratatui::layout::{Position, Rect};
+
+/// Two clicks on the same row within this window count as a double-click.
+const DOUBLE_CLICK: Duration = Duration::from_millis(400);
+
+/// Screen regions recorded during the last draw, used to hit-test mouse events.
+#[derive(Debug, Clone, Default)]
+pub struct HitAreas {
+    /// Rows of the main list or table, below its border and header.
+    pub list_rows: Option<Rect>,
+    /// Entries of the sidebar navigation list, one view per row.
+    pub sidebar_nav: Option<Rect>,
+    /// Rows of the sidebar playlist list, before scrolling is applied.
+    pub sidebar_playlists: Option<Rect>,
+    pub seek_bar: Option<Rect>,
+    pub volume_bar: Option<Rect>,
+}
+
+impl HitAreas {
+    /// Row index inside the main list under the cursor, before scrolling is applied.
+    pub fn list_row(&self, column: u16, row: u16) -> Option<usize> {
+        row_in(self.list_rows?, column, row)
+    }
+
+    pub fn sidebar_row(&self, column: u16, row: u16) -> Option<usize> {
+        row_in(self.sidebar_nav?, column, row)
+    }
+
+    pub fn sidebar_playlist_row(&self, column: u16, row: u16) -> Option<usize> {
+        row_in(self.sidebar_playlists?, column, row)
+    }
+
+    /// Position along the seek bar, from 0.0 to 1.0.
+    pub fn seek_ratio(&self, column: u16, row: u16) -> Option<f64> {
+        ratio_in(self.seek_bar?, column, row)
+    }
+
+    /// Position along the volume bar, from 0.0 to 1.0.
+    pub fn volume_ratio(&self, column: u16, row: u16) -> Option<f64> {
+        ratio_in(self.volume_bar?, column, row)
+    }
+
+    /// Like `volume_ratio` but ignores the row and clamps, so a drag can leave the bar.
+    pub fn volume_drag_ratio(&self, column: u16) -> Option<f64> {
+        let bar = self.volume_bar?;
+        Some(column_ratio(bar, column.clamp(bar.x, bar.right().saturating_sub(1))))
+    }
+}
+
+fn row_in(area: Rect, column: u16, row: u16) -> Option<usize> {
+    if area.contains(Position::new(column, row)) {
+        Some((row - area.y) as usize)
+    } else {
+        None
+    }
+}
+
+fn ratio_in(area: Rect, column: u16, row: u16) -> Option<f64> {
+    if area.contains(Position::new(column, row)) {
+        Some(column_ratio(area, column))
+    } else {
+        None
+    }
+}
+
+/// Clicking the last cell means 100%.
+fn column_ratio(area: Rect, column: u16) -> f64 {
+    if area.width <= 1 {
+        return 1.0;
+    }
+    (column - area.x) as f64 / (area.width - 1) as f64
+}
+
+/// Remembers the last click so a second one on the same row can be
+/// recognised as a double-click.
+#[derive(Debug, Default)]
+pub struct ClickTracker {
+    last: Option<(Instant, usize)>,
+}
+
+impl ClickTracker {
+    /// Records a click on `row` and returns whether it completes a double-click.
+    pub fn click(&mut self, row: usize) -> bool {
+        let now = Instant::now();
+        let double = matches!(self.last, Some((at, last_row)) if last_row == row && now.duration_since(at) <= DOUBLE_CLICK);
+        // A third click starts over instead of chaining into another double-click
+        self.last = if double { None } else { Some((now, row)) };
+        double
+    }
+}
@@ -0,0 +1,190 @@
+use std::time::{Duration, Instant};
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+/// How long a lone digit waits for a motion before it counts as a view shortcut.
+const COUNT_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// Upper bound for count prefixes, so a held-down digit can't overflow.
+const MAX_COUNT: usize = 99_999;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Motion {
+    Up,
+    Down,
+    PageUp,
+    PageDown,
+    HalfPageUp,
+    HalfPageDown,
+    /// `gg`/`Home`, or row `count` when a count was typed.
+    Top,
+    /// `G`/`End`, or row `count` when a count was typed.
+    Bottom,
+    /// `f<letter>`: next item starting with the letter, wrapping around.
+    Find(char),
+    /// `gp`: the currently playing track.
+    Playing,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NavResult {
+    /// Move the selection; `count` is the typed prefix, if any.
+    Move { motion: Motion, count: Option<usize> },
+    /// `ga`/`gb`: open the selected track's artist or album.
+    Jump(Jump),
+    /// Part of a multi-key motion was typed; wait for the rest.
+    Pending,
+    /// Not a navigation key. A lone digit typed just before it is handed
+    /// back so the caller can still treat it as a view shortcut.
+    Unhandled(Option<char>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Jump {
+    Artist,
+    Album,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Prefix {
+    G,
+    Find,
+}
+
+/// Vim-style list navigation shared by every list and table view.
+///
+/// It only turns key presses into motions; the caller knows the item
+/// count and page height for the current view and resolves the motion
+/// with [`target`] and [`find_next`].
+#[derive(Debug, Default)]
+pub struct ListNavigator {
+    count: Option<usize>,
+    count_digits: usize,
+    count_started: Option<Instant>,
+    prefix: Option<Prefix>,
+}
+
+impl ListNavigator {
+    pub fn handle_key(&mut self, key: &KeyEvent) -> NavResult {
+        let control = key.modifiers.contains(KeyModifiers::CONTROL);
+        let alt = key.modifiers.contains(KeyModifiers::ALT);
+
+        if let Some(prefix) = self.prefix.take() {
+            let count = self.count.take();
+            self.count_digits = 0;
+            self.count_started = None;
+            return match (prefix, key.code) {
+                (Prefix::Find, KeyCode::Char(c)) if !control && !alt => NavResult::Move { motion: Motion::Find(c), count },
+                (Prefix::G, KeyCode::Char('g')) => NavResult::Move { motion: Motion::Top, count },
+                (Prefix::G, KeyCode::Char('p')) => NavResult::Move { motion: Motion::Playing, count },
+                (Prefix::G, KeyCode::Char('a')) => NavResult::Jump(Jump::Artist),
+                (Prefix::G, KeyCode::Char('b')) => NavResult::Jump(Jump::Album),
+                // Esc only cancels the half-typed motion
+                (_, KeyCode::Esc) => NavResult::Pending,
+                _ => NavResult::Unhandled(None),
+            };
+        }
+
+        let motion = match key.code {
+            KeyCode::Char(c @ '0'..='9') if !control && !alt && (c != '0' || self.count.is_some()) => {
+                let digit = c.to_digit(10).unwrap_or(0) as usize;
+                self.count = Some((self.count.unwrap_or(0) * 10 + digit).min(MAX_COUNT));
+                self.count_digits += 1;
+                self.count_started = Some(Instant::now());
+                return NavResult::Pending;
+            }
+            KeyCode::Char('g') if !control && !alt => {
+                self.prefix = Some(Prefix::G);
+                return NavResult::Pending;
+            }
+            KeyCode::Char('f') if !control && !alt => {
+                self.prefix = Some(Prefix::Find);
+                return NavResult::Pending;
+            }
+            KeyCode::Char('j') if !control && !alt => Motion::Down,
+            KeyCode::Char('k') if !control && !alt => Motion::Up,
+            KeyCode::Char('G') if !control && !alt => Motion::Bottom,
+            KeyCode::Char('d') if control => Motion::HalfPageDown,
+            KeyCode::Char('u') if control => Motion::HalfPageUp,
+            KeyCode::Down => Motion::Down,
+            KeyCode::Up => Motion::Up,
+            KeyCode::PageDown => Motion::PageDown,
+            KeyCode::PageUp => Motion::PageUp,
+            KeyCode::Home => Motion::Top,
+            KeyCode::End => Motion::Bottom,
+            _ => return NavResult::Unhandled(self.take_lone_digit()),
+        };
+
+        let count = self.count.take();
+        self.count_digits = 0;
+        self.count_started = None;
+        NavResult::Move { motion, count }
+    }
+
+    /// Returns a lone digit once nothing followed it for a moment.
+    pub fn take_expired_digit(&mut self) -> Option<char> {
+        match self.count_started {
+            Some(started) if self.prefix.is_none() && started.elapsed() >= COUNT_TIMEOUT => self.take_lone_digit(),
+            _ => None,
+        }
+    }
+
+    /// The count being typed, for display in the status bar.
+    pub fn pending(&self) -> Option<String> {
+        let count = self.count.map(|c| c.to_string()).unwrap_or_default();
+        let prefix = match self.prefix {
+            Some(Prefix::G) => "g",
+            Some(Prefix::Find) => "f",
+            None => "",
+        };
+        if count.is_empty() && prefix.is_empty() {
+            None
+        } else {
+            Some(format!("{}{}", count, prefix))
+        }
+    }
+
+    fn take_lone_digit(&mut self) -> Option<char> {
+        let count = self.count.take();
+        let digits = std::mem::take(&mut self.count_digits);
+        self.count_started = None;
+        match count {
+            Some(n) if digits == 1 => char::from_digit(n as u32, 10),
+            _ => None,
+        }
+    }
+}
+
+/// Row selected after applying `motion` to a list of `len` rows showing
+/// `page` rows at a time. `Find` and `Playing` need the list's contents
+/// and are left where they are here.
+pub fn target(motion: Motion, count: Option<usize>, selected: usize, len: usize, page: usize) -> usize {
+    if len == 0 {
+        return 0;
+    }
+    let last = len - 1;
+    let page = page.max(1);
+    let times = count.unwrap_or(1);
+
+    let row = match motion {
+        Motion::Up => selected.saturating_sub(times),
+        Motion::Down => selected.saturating_add(times),
+        Motion::PageUp => selected.saturating_sub(page.saturating_mul(times)),
+        Motion::PageDown => selected.saturating_add(page.saturating_mul(times)),
+        Motion::HalfPageUp => selected.saturating_sub((page / 2).max(1).saturating_mul(times)),
+        Motion::HalfPageDown => selected.saturating_add((page / 2).max(1).saturating_mul(times)),
+        Motion::Top => count.map(|n| n.saturating_sub(1)).unwrap_or(0),
+        Motion::Bottom => count.map(|n| n.saturating_sub(1)).unwrap_or(last),
+        Motion::Find(_) | Motion::Playing => selected,
+    };
+    row.min(last)
+}
+
+/// Next label after `selected` starting with `letter` (case-insensitive), wrapping around.
+pub fn find_next(labels: &[String], selected: usize, letter: char) -> Option<usize> {
+    let letter = letter.to_lowercase().to_string();
+    let len = labels.len();
+    (1..=len)
+        .map(|step| (selected + step) % len)
+        .find(|i| labels[*i].trim_start().to_lowercase().starts_with(&letter))
+}
@@ -0,0 +1,92 @@
+use std::time::{Duration, Instant};
+
+use crate::models::CurrentPlayback;
+
+/// Poll interval while a track is playing.
+const PLAYING_POLL: Duration = Duration::from_secs(5);
+/// Poll faster in the last stretch of a track, where skips and track changes happen.
+const NEAR_END_POLL: Duration = Duration::from_secs(2);
+const NEAR_END_WINDOW: Duration = Duration::from_secs(15);
+/// Paused playback can still be resumed from another device.
+const PAUSED_POLL: Duration = Duration::from_secs(10);
+/// Nothing is playing anywhere.
+const IDLE_POLL: Duration = Duration::from_secs(30);
+/// How long after the predicted end of a track to ask for the next one,
+/// giving Spotify a moment to switch.
+const BOUNDARY_SLACK: Duration = Duration::from_millis(500);
+
+/// Playback position between polls, advanced locally from the last sync.
+///
+/// The position is anchored to when the response arrived rather than to
+/// `CurrentPlayback.timestamp`, which records the last state change (a skip
+/// or a pause), not when `progress_ms` was sampled.
+#[derive(Debug, Clone, Default)]
+pub struct PlaybackClock {
+    progress_ms: u64,
+    duration_ms: u64,
+    playing: bool,
+    synced_at: Option<Instant>,
+}
+
+impl PlaybackClock {
+    /// Re-anchors the clock to a fresh playback state.
+    pub fn sync(&mut self, playback: Option<&CurrentPlayback>) {
+        *self = match playback {
+            Some(playback) => Self {
+                progress_ms: playback.progress_ms.unwrap_or(0),
+                duration_ms: playback.item.as_ref().map(|t| t.duration_ms as u64).unwrap_or(0),
+                playing: playback.is_playing,
+                synced_at: Some(Instant::now()),
+            },
+            None => Self::default(),
+        };
+    }
+
+    /// Where playback should be now.
+    pub fn position_ms(&self) -> u64 {
+        let elapsed = match self.synced_at {
+            Some(synced_at) if self.playing => synced_at.elapsed().as_millis() as u64,
+            _ => 0,
+        };
+        let position = self.progress_ms + elapsed;
+        if self.duration_ms > 0 {
+            position.min(self.duration_ms)
+        } else {
+            position
+        }
+    }
+
+    pub fn seek(&mut self, position_ms: u64) {
+        self.progress_ms = position_ms;
+        self.synced_at = Some(Instant::now());
+    }
+
+    /// Pausing freezes the position where it is; resuming continues from it.
+    pub fn set_playing(&mut self, playing: bool) {
+        self.progress_ms = self.position_ms();
+        self.playing = playing;
+        self.synced_at = Some(Instant::now());
+    }
+
+    /// Time left in the current track, if one is playing.
+    fn remaining(&self) -> Option<Duration> {
+        if !self.playing || self.duration_ms == 0 {
+            return None;
+        }
+        Some(Duration::from_millis(self.duration_ms.saturating_sub(self.position_ms())))
+    }
+
+    /// How long to wait before asking Spotify again: faster near the end of
+    /// a track, slower while paused or idle, and never past the moment the
+    /// track should end.
+    pub fn next_poll(&self) -> Duration {
+        match self.remaining() {
+            Some(remaining) => {
+                let interval = if remaining <= NEAR_END_WINDOW { NEAR_END_POLL } else { PLAYING_POLL };
+                interval.min(remaining + BOUNDARY_SLACK)
+            }
+            None if self.synced_at.is_some() => PAUSED_POLL,
+            None => IDLE_POLL,
+        }
+    }
+}
@@ -0,0 +1,75 @@
+use std::collections::BTreeSet;
+
+/// Tracks marked for a bulk action in the current list.
+///
+/// Marks are positions in the list as loaded (not display rows), so sorting
+/// or filtering keeps them on the same tracks. They belong to one list and
+/// are dropped when another list is shown or this one is reloaded.
+#[derive(Debug, Default)]
+pub struct Selection {
+    list: String,
+    len: usize,
+    marked: BTreeSet<usize>,
+    /// Display row where `v` started a range, while one is being extended.
+    anchor: Option<usize>,
+}
+
+impl Selection {
+    /// Forgets the marks if `list` isn't the one they were made in, or if
+    /// its length changed since (it was reloaded).
+    pub fn sync(&mut self, list: &str, len: usize) {
+        if self.list != list || self.len != len {
+            self.list = list.to_string();
+            self.len = len;
+            self.clear();
+        }
+    }
+
+    pub fn toggle(&mut self, index: usize) {
+        if !self.marked.remove(&index) {
+            self.marked.insert(index);
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.marked.clear();
+        self.anchor = None;
+    }
+
+    pub fn start_range(&mut self, row: usize) {
+        self.anchor = Some(row);
+    }
+
+    pub fn in_range_mode(&self) -> bool {
+        self.anchor.is_some()
+    }
+
+    pub fn cancel_range(&mut self) {
+        self.anchor = None;
+    }
+
+    /// Marks every row between the anchor and `cursor`. `rows` maps display
+    /// rows to list positions.
+    pub fn finish_range(&mut self, rows: &[usize], cursor: usize) {
+        let range = self.range_rows(cursor);
+        self.marked.extend(range.filter_map(|row| rows.get(row).copied()));
+        self.anchor = None;
+    }
+
+    /// Marked positions plus the range being extended, in display order.
+    pub fn marked(&self, rows: &[usize], cursor: usize) -> Vec<usize> {
+        let range = self.range_rows(cursor);
+        rows.iter()
+            .enumerate()
+            .filter(|(row, index)| self.marked.contains(index) || range.contains(row))
+            .map(|(_, index)| *index)
+            .collect()
+    }
+
+    fn range_rows(&self, cursor: usize) -> std::ops::Range<usize> {
+        match self.anchor {
+            Some(anchor) => anchor.min(cursor)..anchor.max(cursor) + 1,
+            None => 0..0,
+        }
+    }
+}
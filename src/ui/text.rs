@@ -0,0 +1,41 @@
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+/// Number of terminal columns `text` occupies. CJK characters and most emoji
+/// take two columns, combining marks and variation selectors take none.
+pub fn display_width(text: &str) -> usize {
+    UnicodeWidthStr::width(text)
+}
+
+/// Cuts `text` so it fits in `width` columns, ending in `ellipsis` when
+/// anything had to be dropped. Never splits a wide character.
+pub fn truncate_to_width(text: &str, width: usize, ellipsis: &str) -> String {
+    if display_width(text) <= width {
+        return text.to_string();
+    }
+
+    let ellipsis_width = display_width(ellipsis);
+    if width <= ellipsis_width {
+        return ellipsis.chars().take(width).collect();
+    }
+
+    let budget = width - ellipsis_width;
+    let mut used = 0;
+    let mut result = String::new();
+    for c in text.chars() {
+        let char_width = UnicodeWidthChar::width(c).unwrap_or(0);
+        if used + char_width > budget {
+            break;
+        }
+        used += char_width;
+        result.push(c);
+    }
+    result.push_str(ellipsis);
+    result
+}
+
+/// Truncates, then pads with spaces on the left to exactly `width` columns.
+pub fn pad_left_to_width(text: &str, width: usize, ellipsis: &str) -> String {
+    let truncated = truncate_to_width(text, width, ellipsis);
+    let padding = width.saturating_sub(display_width(&truncated));
+    format!("{}{}", " ".repeat(padding), truncated)
+}
@@ -0,0 +1,446 @@
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::widgets::block::Title;
+use ratatui::widgets::{Block, Borders};
+use std::env;
+use std::str::FromStr;
+
+use crate::config::{Config, ThemeConfig};
+
+/// How many colors the terminal can show, detected from the environment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorSupport {
+    NoColor,
+    Basic16,
+    Indexed256,
+    TrueColor,
+}
+
+impl ColorSupport {
+    /// `NO_COLOR` (any non-empty value, see https://no-color.org) always wins,
+    /// then `COLORTERM` and `TERM` are used to pick the richest palette.
+    pub fn detect() -> Self {
+        if env::var("NO_COLOR").map(|v| !v.is_empty()).unwrap_or(false) {
+            return ColorSupport::NoColor;
+        }
+
+        let term = env::var("TERM").unwrap_or_default();
+        if term == "dumb" {
+            return ColorSupport::NoColor;
+        }
+
+        let colorterm = env::var("COLORTERM").unwrap_or_default().to_lowercase();
+        if colorterm == "truecolor" || colorterm == "24bit" {
+            ColorSupport::TrueColor
+        } else if term.contains("256color") {
+            ColorSupport::Indexed256
+        } else {
+            ColorSupport::Basic16
+        }
+    }
+}
+
+/// Colors for every semantic role used by the `render_*` functions.
+#[derive(Debug, Clone)]
+pub struct Theme {
+    pub name: String,
+    pub support: ColorSupport,
+    /// Regular list and paragraph text.
+    pub text: Color,
+    /// Hints, placeholders and secondary information.
+    pub muted: Color,
+    pub border: Color,
+    pub title: Color,
+    /// The selected row in a list.
+    pub highlight: Color,
+    /// The track that is currently playing, wherever it shows up.
+    pub playing: Color,
+    pub liked: Color,
+    /// Preview and detail panes.
+    pub accent: Color,
+    /// Active text input, e.g. the search box while typing.
+    pub input: Color,
+    pub success: Color,
+    pub warning: Color,
+    pub error: Color,
+    pub progress: Color,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::builtin("default").expect("default theme exists")
+    }
+}
+
+impl Theme {
+    pub const BUILTIN_NAMES: [&'static str; 4] = ["default", "nord", "gruvbox", "mono"];
+
+    pub fn builtin(name: &str) -> Option<Self> {
+        let theme = match name {
+            "default" => Self {
+                name: "default".to_string(),
+                support: ColorSupport::TrueColor,
+                text: Color::White,
+                muted: Color::DarkGray,
+                border: Color::Reset,
+                title: Color::Reset,
+                highlight: Color::Yellow,
+                playing: Color::Green,
+                liked: Color::Red,
+                accent: Color::Cyan,
+                input: Color::Yellow,
+                success: Color::Green,
+                warning: Color::Yellow,
+                error: Color::Red,
+                progress: Color::Green,
+            },
+            "nord" => Self {
+                name: "nord".to_string(),
+                support: ColorSupport::TrueColor,
+                text: Color::Rgb(0xd8, 0xde, 0xe9),
+                muted: Color::Rgb(0x61, 0x6e, 0x88),
+                border: Color::Rgb(0x4c, 0x56, 0x6a),
+                title: Color::Rgb(0x88, 0xc0, 0xd0),
+                highlight: Color::Rgb(0xeb, 0xcb, 0x8b),
+                playing: Color::Rgb(0xa3, 0xbe, 0x8c),
+                liked: Color::Rgb(0xbf, 0x61, 0x6a),
+                accent: Color::Rgb(0x8f, 0xbc, 0xbb),
+                input: Color::Rgb(0xeb, 0xcb, 0x8b),
+                success: Color::Rgb(0xa3, 0xbe, 0x8c),
+                warning: Color::Rgb(0xd0, 0x87, 0x70),
+                error: Color::Rgb(0xbf, 0x61, 0x6a),
+                progress: Color::Rgb(0x81, 0xa1, 0xc1),
+            },
+            "gruvbox" => Self {
+                name: "gruvbox".to_string(),
+                support: ColorSupport::TrueColor,
+                text: Color::Rgb(0xeb, 0xdb, 0xb2),
+                muted: Color::Rgb(0x92, 0x83, 0x74),
+                border: Color::Rgb(0x66, 0x5c, 0x54),
+                title: Color::Rgb(0xfa, 0xbd, 0x2f),
+                highlight: Color::Rgb(0xfe, 0x80, 0x19),
+                playing: Color::Rgb(0xb8, 0xbb, 0x26),
+                liked: Color::Rgb(0xfb, 0x49, 0x34),
+                accent: Color::Rgb(0x83, 0xa5, 0x98),
+                input: Color::Rgb(0xfa, 0xbd, 0x2f),
+                success: Color::Rgb(0xb8, 0xbb, 0x26),
+                warning: Color::Rgb(0xfa, 0xbd, 0x2f),
+                error: Color::Rgb(0xfb, 0x49, 0x34),
+                progress: Color::Rgb(0x8e, 0xc0, 0x7c),
+            },
+            "mono" => Self {
+                name: "mono".to_string(),
+                support: ColorSupport::TrueColor,
+                text: Color::Gray,
+                muted: Color::DarkGray,
+                border: Color::DarkGray,
+                title: Color::White,
+                highlight: Color::White,
+                playing: Color::White,
+                liked: Color::Gray,
+                accent: Color::Gray,
+                input: Color::White,
+                success: Color::Gray,
+                warning: Color::White,
+                error: Color::White,
+                progress: Color::Gray,
+            },
+            _ => return None,
+        };
+        Some(theme)
+    }
+
+    /// Builds the theme selected in the config and adapts it to what the
+    /// terminal supports. Unknown names and unparsable colors fall back to
+    /// the default theme and are reported in the returned warnings.
+    pub fn from_config(config: &Config, support: ColorSupport) -> (Self, Vec<String>) {
+        let mut warnings = Vec::new();
+        let name = config.theme.clone().unwrap_or_else(|| "default".to_string());
+
+        let theme = if let Some(user_theme) = config.themes.get(&name) {
+            Self::from_user_theme(&name, user_theme, &mut warnings)
+        } else if let Some(theme) = Self::builtin(&name) {
+            theme
+        } else {
+            warnings.push(format!(
+                "Unknown theme '{}', using default (built-in: {})",
+                name,
+                Self::BUILTIN_NAMES.join(", ")
+            ));
+            Self::default()
+        };
+
+        (theme.adapt(support), warnings)
+    }
+
+    fn from_user_theme(name: &str, user_theme: &ThemeConfig, warnings: &mut Vec<String>) -> Self {
+        let base_name = user_theme.base.as_deref().unwrap_or("default");
+        let mut theme = Self::builtin(base_name).unwrap_or_else(|| {
+            warnings.push(format!("Theme '{}': unknown base '{}', using default", name, base_name));
+            Self::default()
+        });
+        theme.name = name.to_string();
+
+        let roles: [(&str, &Option<String>, &mut Color); 13] = [
+            ("text", &user_theme.text, &mut theme.text),
+            ("muted", &user_theme.muted, &mut theme.muted),
+            ("border", &user_theme.border, &mut theme.border),
+            ("title", &user_theme.title, &mut theme.title),
+            ("highlight", &user_theme.highlight, &mut theme.highlight),
+            ("playing", &user_theme.playing, &mut theme.playing),
+            ("liked", &user_theme.liked, &mut theme.liked),
+            ("accent", &user_theme.accent, &mut theme.accent),
+            ("input", &user_theme.input, &mut theme.input),
+            ("success", &user_theme.success, &mut theme.success),
+            ("warning", &user_theme.warning, &mut theme.warning),
+            ("error", &user_theme.error, &mut theme.error),
+            ("progress", &user_theme.progress, &mut theme.progress),
+        ];
+
+        for (role, value, slot) in roles {
+            if let Some(value) = value {
+                match Color::from_str(value) {
+                    Ok(color) => *slot = color,
+                    Err(_) => warnings.push(format!("Theme '{}': invalid {} color '{}'", name, role, value)),
+                }
+            }
+        }
+
+        theme
+    }
+
+    /// Downgrades every color to the given palette.
+    pub fn adapt(mut self, support: ColorSupport) -> Self {
+        self.support = support;
+        for color in [
+            &mut self.text,
+            &mut self.muted,
+            &mut self.border,
+            &mut self.title,
+            &mut self.highlight,
+            &mut self.playing,
+            &mut self.liked,
+            &mut self.accent,
+            &mut self.input,
+            &mut self.success,
+            &mut self.warning,
+            &mut self.error,
+            &mut self.progress,
+        ] {
+            *color = downgrade(*color, support);
+        }
+        self
+    }
+
+    /// A bordered block using the theme's border and title colors.
+    pub fn block<'a, T: Into<Title<'a>>>(&self, title: T) -> Block<'a> {
+        Block::default()
+            .title(title)
+            .borders(Borders::ALL)
+            .border_style(self.border_style())
+            .title_style(self.title_style())
+    }
+
+    fn fg(&self, color: Color) -> Style {
+        if self.support == ColorSupport::NoColor {
+            Style::default()
+        } else {
+            Style::default().fg(color)
+        }
+    }
+
+    pub fn text_style(&self) -> Style {
+        self.fg(self.text)
+    }
+
+    pub fn muted_style(&self) -> Style {
+        if self.support == ColorSupport::NoColor {
+            Style::default().add_modifier(Modifier::DIM)
+        } else {
+            self.fg(self.muted)
+        }
+    }
+
+    pub fn border_style(&self) -> Style {
+        self.fg(self.border)
+    }
+
+    /// Border of the pane that has keyboard focus.
+    pub fn focused_border_style(&self) -> Style {
+        self.fg(self.accent).add_modifier(Modifier::BOLD)
+    }
+
+    pub fn title_style(&self) -> Style {
+        self.fg(self.title)
+    }
+
+    /// Selected rows stay visible without colors by using reverse video.
+    pub fn highlight_style(&self) -> Style {
+        if self.support == ColorSupport::NoColor {
+            Style::default().add_modifier(Modifier::REVERSED | Modifier::BOLD)
+        } else {
+            self.fg(self.highlight).add_modifier(Modifier::BOLD)
+        }
+    }
+
+    pub fn playing_style(&self) -> Style {
+        self.fg(self.playing).add_modifier(Modifier::BOLD)
+    }
+
+    /// Rows marked for a bulk action.
+    pub fn marked_style(&self) -> Style {
+        if self.support == ColorSupport::NoColor {
+            Style::default().add_modifier(Modifier::UNDERLINED)
+        } else {
+            self.fg(self.accent)
+        }
+    }
+
+    pub fn liked_highlight_style(&self) -> Style {
+        if self.support == ColorSupport::NoColor {
+            self.highlight_style()
+        } else {
+            self.fg(self.liked).add_modifier(Modifier::BOLD)
+        }
+    }
+
+    pub fn accent_style(&self) -> Style {
+        self.fg(self.accent)
+    }
+
+    pub fn input_style(&self) -> Style {
+        if self.support == ColorSupport::NoColor {
+            Style::default().add_modifier(Modifier::UNDERLINED)
+        } else {
+            self.fg(self.input)
+        }
+    }
+
+    pub fn success_style(&self) -> Style {
+        self.fg(self.success)
+    }
+
+    pub fn warning_style(&self) -> Style {
+        self.fg(self.warning)
+    }
+
+    pub fn error_style(&self) -> Style {
+        if self.support == ColorSupport::NoColor {
+            Style::default().add_modifier(Modifier::BOLD)
+        } else {
+            self.fg(self.error)
+        }
+    }
+
+    pub fn progress_style(&self) -> Style {
+        self.fg(self.progress)
+    }
+
+    /// Picks the style for a status or log message from its leading marker.
+    pub fn message_style(&self, message: &str) -> Style {
+        if message.contains('❌') || message.contains("ERROR") {
+            self.error_style()
+        } else if message.contains('⚠') {
+            self.warning_style()
+        } else if message.contains('✅') {
+            self.success_style()
+        } else {
+            self.text_style()
+        }
+    }
+}
+
+/// The 16 ANSI colors with the RGB values most terminals use for them.
+const ANSI_16: [(Color, (u8, u8, u8)); 16] = [
+    (Color::Black, (0, 0, 0)),
+    (Color::Red, (205, 0, 0)),
+    (Color::Green, (0, 205, 0)),
+    (Color::Yellow, (205, 205, 0)),
+    (Color::Blue, (0, 0, 238)),
+    (Color::Magenta, (205, 0, 205)),
+    (Color::Cyan, (0, 205, 205)),
+    (Color::Gray, (229, 229, 229)),
+    (Color::DarkGray, (127, 127, 127)),
+    (Color::LightRed, (255, 0, 0)),
+    (Color::LightGreen, (0, 255, 0)),
+    (Color::LightYellow, (255, 255, 0)),
+    (Color::LightBlue, (92, 92, 255)),
+    (Color::LightMagenta, (255, 0, 255)),
+    (Color::LightCyan, (0, 255, 255)),
+    (Color::White, (255, 255, 255)),
+];
+
+fn downgrade(color: Color, support: ColorSupport) -> Color {
+    match (support, color) {
+        (ColorSupport::NoColor, _) => Color::Reset,
+        (ColorSupport::TrueColor, _) => color,
+        (ColorSupport::Indexed256, Color::Rgb(r, g, b)) => Color::Indexed(rgb_to_256(r, g, b)),
+        (ColorSupport::Basic16, Color::Rgb(r, g, b)) => nearest_ansi16(r, g, b),
+        (ColorSupport::Basic16, Color::Indexed(index)) => {
+            let (r, g, b) = indexed_to_rgb(index);
+            nearest_ansi16(r, g, b)
+        }
+        _ => color,
+    }
+}
+
+fn nearest_ansi16(r: u8, g: u8, b: u8) -> Color {
+    ANSI_16
+        .iter()
+        .min_by_key(|(_, (ar, ag, ab))| {
+            let dr = r as i32 - *ar as i32;
+            let dg = g as i32 - *ag as i32;
+            let db = b as i32 - *ab as i32;
+            dr * dr + dg * dg + db * db
+        })
+        .map(|(color, _)| *color)
+        .unwrap_or(Color::Reset)
+}
+
+/// Maps to the xterm 6x6x6 color cube or the grayscale ramp, whichever is closer.
+fn rgb_to_256(r: u8, g: u8, b: u8) -> u8 {
+    let to_cube = |v: u8| -> u8 {
+        if v < 48 {
+            0
+        } else if v < 115 {
+            1
+        } else {
+            (v - 35) / 40
+        }
+    };
+    let (cr, cg, cb) = (to_cube(r), to_cube(g), to_cube(b));
+    let cube_index = 16 + 36 * cr + 6 * cg + cb;
+
+    let average = (r as u16 + g as u16 + b as u16) / 3;
+    let gray_index = if average > 238 { 23 } else { (average.saturating_sub(3) / 10) as u8 };
+    let gray_value = 8 + 10 * gray_index as i32;
+
+    let cube_rgb = indexed_to_rgb(cube_index);
+    let distance = |(ar, ag, ab): (u8, u8, u8)| {
+        let dr = r as i32 - ar as i32;
+        let dg = g as i32 - ag as i32;
+        let db = b as i32 - ab as i32;
+        dr * dr + dg * dg + db * db
+    };
+    let gray_distance = distance((gray_value as u8, gray_value as u8, gray_value as u8));
+
+    if gray_distance < distance(cube_rgb) {
+        232 + gray_index
+    } else {
+        cube_index
+    }
+}
+
+fn indexed_to_rgb(index: u8) -> (u8, u8, u8) {
+    match index {
+        0..=15 => ANSI_16[index as usize].1,
+        16..=231 => {
+            let i = index - 16;
+            let level = |v: u8| if v == 0 { 0 } else { 55 + v * 40 };
+            (level(i / 36), level((i / 6) % 6), level(i % 6))
+        }
+        _ => {
+            let v = 8 + 10 * (index - 232);
+            (v, v, v)
+        }
+    }
+}
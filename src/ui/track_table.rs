@@ -0,0 +1,229 @@
+use std::collections::HashSet;
+
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::Style,
+    widgets::{Block, Cell, ListState, Row, Table, TableState},
+    Frame,
+};
+
+use super::glyphs::Glyphs;
+use super::text::{pad_left_to_width, truncate_to_width};
+use super::theme::Theme;
+use super::track_view::SortDirection;
+use crate::config::TrackColumn;
+use crate::models::Track;
+
+/// Shared column-based track list used by every view that shows tracks.
+///
+/// Selection is kept in the caller's `ListState` so the rest of the app can
+/// treat tables and plain lists the same way.
+pub struct TrackTable<'a> {
+    tracks: &'a [Track],
+    columns: &'a [TrackColumn],
+    theme: &'a Theme,
+    glyphs: &'a Glyphs,
+    block: Option<Block<'a>>,
+    playing_id: Option<&'a str>,
+    highlight_style: Option<Style>,
+    rows: Option<Vec<usize>>,
+    sort: Option<(TrackColumn, SortDirection)>,
+    marked: HashSet<usize>,
+}
+
+impl<'a> TrackTable<'a> {
+    pub fn new(tracks: &'a [Track], columns: &'a [TrackColumn], theme: &'a Theme, glyphs: &'a Glyphs) -> Self {
+        Self {
+            tracks,
+            columns,
+            theme,
+            glyphs,
+            block: None,
+            playing_id: None,
+            highlight_style: None,
+            rows: None,
+            sort: None,
+            marked: HashSet::new(),
+        }
+    }
+
+    pub fn block(mut self, block: Block<'a>) -> Self {
+        self.block = Some(block);
+        self
+    }
+
+    /// Rows whose track has this ID are drawn in the theme's playing style.
+    pub fn playing(mut self, track_id: Option<&'a str>) -> Self {
+        self.playing_id = track_id;
+        self
+    }
+
+    pub fn highlight_style(mut self, style: Style) -> Self {
+        self.highlight_style = Some(style);
+        self
+    }
+
+    /// Shows only these positions of `tracks`, in this order. The `#` column
+    /// keeps each track's original position.
+    pub fn rows(mut self, rows: Vec<usize>) -> Self {
+        self.rows = Some(rows);
+        self
+    }
+
+    /// Marks the sorted column in the header.
+    pub fn sort(mut self, sort: Option<(TrackColumn, SortDirection)>) -> Self {
+        self.sort = sort;
+        self
+    }
+
+    /// Positions of `tracks` marked for a bulk action; their titles get a
+    /// marker and the rows the theme's marked style.
+    pub fn marked(mut self, marked: Vec<usize>) -> Self {
+        self.marked = marked.into_iter().collect();
+        self
+    }
+
+    pub fn render(self, f: &mut Frame, area: Rect, state: &mut ListState) {
+        let inner = match self.block {
+            Some(ref block) => block.inner(area),
+            None => area,
+        };
+
+        // Date/owner columns stay hidden for lists that carry no such data (search, queue)
+        let columns: Vec<TrackColumn> = self
+            .columns
+            .iter()
+            .copied()
+            .filter(|column| match column {
+                TrackColumn::AddedAt => self.tracks.iter().any(|t| t.added_at.is_some()),
+                TrackColumn::AddedBy => self.tracks.iter().any(|t| t.added_by.is_some()),
+                _ => true,
+            })
+            .collect();
+
+        let constraints: Vec<Constraint> = columns.iter().map(|c| self.constraint(*c)).collect();
+        let widths: Vec<usize> = Layout::horizontal(constraints.clone())
+            .spacing(1)
+            .split(inner)
+            .iter()
+            .map(|rect| rect.width as usize)
+            .collect();
+
+        let ellipsis = self.glyphs.ellipsis();
+
+        let header = Row::new(
+            columns
+                .iter()
+                .zip(&widths)
+                .map(|(column, width)| {
+                    let header = match self.sort {
+                        Some((sorted, direction)) if sorted == *column => {
+                            let arrow = match direction {
+                                SortDirection::Ascending => "↑",
+                                SortDirection::Descending => "↓",
+                            };
+                            format!("{}{}", column.header(), self.glyphs.text(arrow))
+                        }
+                        _ => column.header().to_string(),
+                    };
+                    Cell::from(truncate_to_width(&header, *width, ellipsis))
+                }),
+        )
+        .style(self.theme.title_style().add_modifier(ratatui::style::Modifier::BOLD));
+
+        let order: Vec<usize> = match self.rows {
+            Some(ref rows) => rows.clone(),
+            None => (0..self.tracks.len()).collect(),
+        };
+
+        let rows: Vec<Row> = order
+            .iter()
+            .filter_map(|i| self.tracks.get(*i).map(|track| (*i, track)))
+            .map(|(i, track)| {
+                let marked = self.marked.contains(&i);
+                let cells = columns.iter().zip(&widths).map(|(column, width)| {
+                    // Spotify data is shown as it is; only the mark is ours
+                    let mut value = cell_text(*column, i, track);
+                    if marked && *column == TrackColumn::Title {
+                        value = format!("{} {}", self.glyphs.text("●"), value);
+                    }
+                    let text = if column.is_numeric() {
+                        pad_left_to_width(&value, *width, ellipsis)
+                    } else {
+                        truncate_to_width(&value, *width, ellipsis)
+                    };
+                    Cell::from(text)
+                });
+
+                let row = Row::new(cells);
+                if self.playing_id.is_some() && self.playing_id == Some(track.id.as_str()) {
+                    row.style(self.theme.playing_style())
+                } else if marked {
+                    row.style(self.theme.marked_style())
+                } else {
+                    row
+                }
+            })
+            .collect();
+
+        let mut table = Table::new(rows, constraints)
+            .header(header)
+            .column_spacing(1)
+            .style(self.theme.text_style())
+            .highlight_style(self.highlight_style.unwrap_or_else(|| self.theme.highlight_style()));
+        if let Some(block) = self.block {
+            table = table.block(block);
+        }
+
+        let mut table_state = TableState::default()
+            .with_offset(state.offset())
+            .with_selected(state.selected());
+        f.render_stateful_widget(table, area, &mut table_state);
+        *state.offset_mut() = table_state.offset();
+    }
+
+    fn constraint(&self, column: TrackColumn) -> Constraint {
+        match column {
+            TrackColumn::Index => {
+                let digits = self.tracks.len().max(1).to_string().len() as u16;
+                // Leave room for the sort arrow next to "#"
+                let sorted = matches!(self.sort, Some((TrackColumn::Index, _)));
+                Constraint::Length(if sorted { digits.max(2) } else { digits })
+            }
+            TrackColumn::Title => Constraint::Fill(3),
+            TrackColumn::Artists => Constraint::Fill(2),
+            TrackColumn::Album => Constraint::Fill(2),
+            TrackColumn::Duration => Constraint::Length(5),
+            TrackColumn::Popularity => Constraint::Length(3),
+            TrackColumn::AddedAt => Constraint::Length(10),
+            TrackColumn::AddedBy => Constraint::Length(12),
+        }
+    }
+}
+
+/// Plain text for one cell. `index` is the row's position in the list.
+pub fn cell_text(column: TrackColumn, index: usize, track: &Track) -> String {
+    match column {
+        TrackColumn::Index => (index + 1).to_string(),
+        TrackColumn::Title => track.name.clone(),
+        TrackColumn::Artists => track
+            .artists
+            .iter()
+            .map(|a| a.name.clone())
+            .collect::<Vec<_>>()
+            .join(", "),
+        TrackColumn::Album => track.album.as_ref().map(|a| a.name.clone()).unwrap_or_default(),
+        TrackColumn::Duration => {
+            let seconds = track.duration_ms / 1000;
+            format!("{}:{:02}", seconds / 60, seconds % 60)
+        }
+        TrackColumn::Popularity => track.popularity.to_string(),
+        // Spotify timestamps are RFC 3339; the date part is enough for a column
+        TrackColumn::AddedAt => track
+            .added_at
+            .as_deref()
+            .map(|added| added.chars().take(10).collect())
+            .unwrap_or_default(),
+        TrackColumn::AddedBy => track.added_by.clone().unwrap_or_default(),
+    }
+}
@@ -0,0 +1,164 @@
+use std::cell::RefCell;
+use std::cmp::Reverse;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+use crate::config::TrackColumn;
+use crate::models::Track;
+
+/// Columns that `o` cycles through, in order.
+const SORT_COLUMNS: [TrackColumn; 6] = [
+    TrackColumn::Title,
+    TrackColumn::Artists,
+    TrackColumn::Album,
+    TrackColumn::Duration,
+    TrackColumn::Popularity,
+    TrackColumn::AddedAt,
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortDirection {
+    Ascending,
+    Descending,
+}
+
+/// Sort order and filter applied on top of a loaded track list.
+///
+/// The list in `AppState` is never reordered; `indices` maps display rows
+/// back to positions in it, so play and queue actions hit the right track.
+#[derive(Debug, Clone, Default)]
+pub struct TrackView {
+    pub sort: Option<(TrackColumn, SortDirection)>,
+    pub filter: String,
+    /// The last `indices`, kept until the sort, filter or list changes.
+    cached: RefCell<Option<(CacheKey, Vec<usize>)>>,
+}
+
+/// What `indices` depends on.
+#[derive(Debug, Clone, PartialEq)]
+struct CacheKey {
+    sort: Option<(TrackColumn, SortDirection)>,
+    filter: String,
+    /// Hash of the track ids, so a reloaded or edited list counts as new.
+    tracks: u64,
+}
+
+/// A track's value in the sort column, worked out once per sort.
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+enum SortKey {
+    Number(u64),
+    Text(String),
+    Optional(Option<String>),
+}
+
+impl TrackView {
+    /// Moves to the next sort column, wrapping back to the original order.
+    pub fn cycle_sort(&mut self) {
+        self.sort = match self.sort {
+            None => Some((SORT_COLUMNS[0], SortDirection::Ascending)),
+            // The reversed original order, from `reverse_sort`
+            Some((TrackColumn::Index, direction)) => Some((SORT_COLUMNS[0], direction)),
+            Some((column, direction)) => {
+                let position = SORT_COLUMNS.iter().position(|c| *c == column).unwrap_or(0);
+                SORT_COLUMNS.get(position + 1).map(|next| (*next, direction))
+            }
+        };
+    }
+
+    /// Flips the sort direction. Without a sort column this reverses the original order.
+    pub fn reverse_sort(&mut self) {
+        self.sort = match self.sort {
+            None => Some((TrackColumn::Index, SortDirection::Descending)),
+            Some((TrackColumn::Index, SortDirection::Descending)) => None,
+            Some((column, SortDirection::Ascending)) => Some((column, SortDirection::Descending)),
+            Some((column, SortDirection::Descending)) => Some((column, SortDirection::Ascending)),
+        };
+    }
+
+    /// Positions in `tracks` that pass the filter, in display order.
+    pub fn indices(&self, tracks: &[Track]) -> Vec<usize> {
+        let mut hasher = DefaultHasher::new();
+        tracks.iter().for_each(|track| track.id.hash(&mut hasher));
+        let key = CacheKey { sort: self.sort, filter: self.filter.clone(), tracks: hasher.finish() };
+        if let Some((cached_key, indices)) = self.cached.borrow().as_ref() {
+            if *cached_key == key {
+                return indices.clone();
+            }
+        }
+        let indices = self.compute_indices(tracks);
+        *self.cached.borrow_mut() = Some((key, indices.clone()));
+        indices
+    }
+
+    fn compute_indices(&self, tracks: &[Track]) -> Vec<usize> {
+        let terms: Vec<String> = self.filter.split_whitespace().map(|t| t.to_lowercase()).collect();
+        let mut indices: Vec<usize> = tracks
+            .iter()
+            .enumerate()
+            .filter(|(_, track)| terms.is_empty() || fuzzy_match(&terms, track))
+            .map(|(i, _)| i)
+            .collect();
+
+        if let Some((column, direction)) = self.sort {
+            // Stable sort, so equal keys keep their original order
+            match direction {
+                SortDirection::Ascending => indices.sort_by_cached_key(|i| sort_key(column, *i, &tracks[*i])),
+                SortDirection::Descending => indices.sort_by_cached_key(|i| Reverse(sort_key(column, *i, &tracks[*i]))),
+            }
+        }
+        indices
+    }
+
+    /// Short description for pane titles, e.g. `sort: Title ↑, filter: "beat"`.
+    pub fn describe(&self) -> Option<String> {
+        let mut parts = Vec::new();
+        if let Some((column, direction)) = self.sort {
+            let arrow = match direction {
+                SortDirection::Ascending => "↑",
+                SortDirection::Descending => "↓",
+            };
+            parts.push(format!("sort: {} {}", column.header(), arrow));
+        }
+        if !self.filter.is_empty() {
+            parts.push(format!("filter: \"{}\"", self.filter));
+        }
+        if parts.is_empty() {
+            None
+        } else {
+            Some(parts.join(", "))
+        }
+    }
+}
+
+/// Every term must appear in title, artists or album as an in-order
+/// (not necessarily contiguous) run of characters.
+fn fuzzy_match(terms: &[String], track: &Track) -> bool {
+    let mut haystack = track.name.to_lowercase();
+    for artist in &track.artists {
+        haystack.push(' ');
+        haystack.push_str(&artist.name.to_lowercase());
+    }
+    if let Some(ref album) = track.album {
+        haystack.push(' ');
+        haystack.push_str(&album.name.to_lowercase());
+    }
+
+    terms.iter().all(|term| {
+        let mut chars = haystack.chars();
+        term.chars().all(|needle| chars.any(|c| c == needle))
+    })
+}
+
+fn sort_key(column: TrackColumn, index: usize, track: &Track) -> SortKey {
+    match column {
+        TrackColumn::Index => SortKey::Number(index as u64),
+        TrackColumn::Title => SortKey::Text(track.name.to_lowercase()),
+        TrackColumn::Artists => SortKey::Text(track.artists.first().map(|a| a.name.to_lowercase()).unwrap_or_default()),
+        TrackColumn::Album => SortKey::Text(track.album.as_ref().map(|a| a.name.to_lowercase()).unwrap_or_default()),
+        TrackColumn::Duration => SortKey::Number(track.duration_ms.into()),
+        TrackColumn::Popularity => SortKey::Number(track.popularity.into()),
+        // RFC 3339 timestamps sort correctly as strings
+        TrackColumn::AddedAt => SortKey::Optional(track.added_at.clone()),
+        TrackColumn::AddedBy => SortKey::Optional(track.added_by.clone()),
+    }
+}
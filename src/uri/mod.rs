@@ -0,0 +1,173 @@
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// Kinds of Spotify item that have their own URI.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ItemKind {
+    Track,
+    Album,
+    Artist,
+    Playlist,
+    Show,
+    Episode,
+}
+
+impl ItemKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ItemKind::Track => "track",
+            ItemKind::Album => "album",
+            ItemKind::Artist => "artist",
+            ItemKind::Playlist => "playlist",
+            ItemKind::Show => "show",
+            ItemKind::Episode => "episode",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "track" => Some(ItemKind::Track),
+            "album" => Some(ItemKind::Album),
+            "artist" => Some(ItemKind::Artist),
+            "playlist" => Some(ItemKind::Playlist),
+            "show" => Some(ItemKind::Show),
+            "episode" => Some(ItemKind::Episode),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for ItemKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A base-62 Spotify ID such as `4uLU6hMCjMI75M1A2tKUQC`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SpotifyId(String);
+
+impl SpotifyId {
+    pub fn parse(id: &str) -> Result<Self> {
+        if !id.is_empty() && id.chars().all(|c| c.is_ascii_alphanumeric()) {
+            Ok(Self(id.to_string()))
+        } else {
+            Err(anyhow!("'{}' is not a Spotify ID", id))
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for SpotifyId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// A typed reference to a Spotify item, written `spotify:<kind>:<id>`.
+///
+/// Parses `spotify:` URIs (including the old `spotify:user:<name>:playlist:<id>`
+/// form) and open.spotify.com links, which may carry a locale segment such
+/// as `/intl-de/` and a `?si=` share token.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SpotifyUri {
+    kind: ItemKind,
+    id: SpotifyId,
+}
+
+impl SpotifyUri {
+    pub fn new(kind: ItemKind, id: SpotifyId) -> Self {
+        Self { kind, id }
+    }
+
+    pub fn kind(&self) -> ItemKind {
+        self.kind
+    }
+
+    pub fn id(&self) -> &SpotifyId {
+        &self.id
+    }
+
+    /// Parses a `spotify:` URI or an open.spotify.com link.
+    pub fn parse(input: &str) -> Result<Self> {
+        let input = input.trim();
+        if let Some(rest) = input.strip_prefix("spotify:") {
+            let parts: Vec<&str> = rest.split(':').collect();
+            return Self::from_segments(&parts).ok_or_else(|| anyhow!("'{}' is not a Spotify URI", input));
+        }
+
+        let without_scheme = input
+            .strip_prefix("https://")
+            .or_else(|| input.strip_prefix("http://"))
+            .unwrap_or(input);
+        if let Some(path) = without_scheme.strip_prefix("open.spotify.com/") {
+            // Drop the share token and anything else after the path
+            let path = path.split(['?', '#']).next().unwrap_or("");
+            let segments: Vec<&str> = path
+                .split('/')
+                .filter(|s| !s.is_empty())
+                .skip_while(|s| s.starts_with("intl-") || *s == "embed")
+                .collect();
+            return Self::from_segments(&segments).ok_or_else(|| anyhow!("'{}' is not a Spotify link", input));
+        }
+
+        Err(anyhow!("'{}' is not a Spotify URI or link", input))
+    }
+
+    /// Like `parse`, but also takes a bare ID and insists on `kind`.
+    pub fn parse_as(kind: ItemKind, input: &str) -> Result<Self> {
+        let input = input.trim();
+        if input.starts_with("spotify:") || input.contains("open.spotify.com/") {
+            let uri = Self::parse(input)?;
+            if uri.kind != kind {
+                return Err(anyhow!("Expected a {} but got a {}", kind, uri.kind));
+            }
+            Ok(uri)
+        } else {
+            SpotifyId::parse(input).map(|id| Self::new(kind, id))
+        }
+    }
+
+    /// `kind`/`id` segments, optionally behind `user/<name>/`.
+    fn from_segments(segments: &[&str]) -> Option<Self> {
+        let segments = match segments {
+            ["user", _, rest @ ..] if !rest.is_empty() => rest,
+            _ => segments,
+        };
+        match segments {
+            [kind, id] => Some(Self::new(ItemKind::from_name(kind)?, SpotifyId::parse(id).ok()?)),
+            _ => None,
+        }
+    }
+
+    /// Albums, artists, playlists and shows play as a context; tracks and
+    /// episodes play on their own.
+    pub fn is_context(&self) -> bool {
+        matches!(self.kind, ItemKind::Album | ItemKind::Artist | ItemKind::Playlist | ItemKind::Show)
+    }
+
+    /// Shareable open.spotify.com link.
+    pub fn url(&self) -> String {
+        format!("https://open.spotify.com/{}/{}", self.kind, self.id)
+    }
+}
+
+impl fmt::Display for SpotifyUri {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "spotify:{}:{}", self.kind, self.id)
+    }
+}
+
+impl FromStr for SpotifyUri {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::parse(s)
+    }
+}
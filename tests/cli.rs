@@ -0,0 +1,76 @@
+//! Parsing `spotycli` subcommands and rendering `spotycli status`.
+
+use spotycli::cli::{Status, StatusOptions, Subcommand};
+use spotycli::commands::RepeatMode;
+use spotycli::daemon::Adjust;
+use spotycli::models::ShuffleMode;
+use spotycli::uri::ItemKind;
+
+fn parse(line: &str) -> Result<Subcommand, i32> {
+    let args: Vec<String> = line.split_whitespace().map(String::from).collect();
+    Subcommand::parse(&args).map_err(|failure| failure.exit_code())
+}
+
+#[test]
+fn subcommands_parse() {
+    assert_eq!(parse(""), Ok(Subcommand::Help));
+    assert_eq!(parse("play"), Ok(Subcommand::Play(None)));
+    assert_eq!(parse("play daft punk"), Ok(Subcommand::Play(Some("daft punk".to_string()))));
+    assert_eq!(parse("prev"), Ok(Subcommand::Previous));
+    assert_eq!(parse("shuffle"), Ok(Subcommand::Shuffle(None)));
+    assert_eq!(parse("shuffle smart"), Ok(Subcommand::Shuffle(Some(ShuffleMode::SmartShuffle))));
+    assert_eq!(parse("repeat playlist"), Ok(Subcommand::Repeat(RepeatMode::Context)));
+    assert_eq!(parse("transfer Living Room"), Ok(Subcommand::Transfer("Living Room".to_string())));
+    assert_eq!(parse("search -t album -n 5 discovery"), Ok(Subcommand::Search { query: "discovery".to_string(), kind: ItemKind::Album, limit: 5 }));
+    assert_eq!(
+        parse("status --format {title} --follow"),
+        Ok(Subcommand::Status(StatusOptions { format: Some("{title}".to_string()), follow: true }))
+    );
+}
+
+#[test]
+fn volume_and_seek_take_absolute_or_relative_values() {
+    assert_eq!(parse("vol 40"), Ok(Subcommand::Volume(Adjust::To(40))));
+    assert_eq!(parse("vol +5"), Ok(Subcommand::Volume(Adjust::Up(5))));
+    assert_eq!(parse("vol -5"), Ok(Subcommand::Volume(Adjust::Down(5))));
+    assert_eq!(parse("seek 1:23"), Ok(Subcommand::Seek(Adjust::To(83_000))));
+    assert_eq!(parse("seek +10"), Ok(Subcommand::Seek(Adjust::Up(10_000))));
+    assert_eq!(parse("seek -10"), Ok(Subcommand::Seek(Adjust::Down(10_000))));
+}
+
+#[test]
+fn bad_arguments_are_usage_errors() {
+    for line in [
+        "vol 101",
+        "vol",
+        "seek soon",
+        "seek +18446744073709552",
+        "pause now",
+        "shuffle sideways",
+        "queue spotify:album:4aawyAB9vmqN3uQ7FjRGTy",
+        "search --limit 99 x",
+        "search",
+        "status --yaml",
+        "frobnicate",
+    ] {
+        assert_eq!(parse(line), Err(2), "{}", line);
+    }
+}
+
+#[test]
+fn status_templates_fill_each_placeholder_once() {
+    let status = Status {
+        state: "playing".to_string(),
+        title: Some("{artist} & {progress}".to_string()),
+        artists: vec!["Daft Punk".to_string(), "{title}".to_string()],
+        progress_ms: Some(83_000),
+        duration_ms: Some(332_000),
+        shuffle: Some(true),
+        ..Status::default()
+    };
+    assert_eq!(
+        status.render("{artist} - {title} [{progress}/{duration} {percent}%] shuffle {shuffle}"),
+        "Daft Punk, {title} - {artist} & {progress} [1:23/5:32 25%] shuffle on"
+    );
+    assert_eq!(status.render("{album}|{liked}|{nope}|{{state}}|{state"), "||{nope}|{playing}|{state");
+}
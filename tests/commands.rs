@@ -0,0 +1,64 @@
+//! The `:` command line: splitting, parsing and positions.
+
+use spotycli::commands::{parse_position, split_args, Command, RepeatMode};
+use spotycli::models::ShuffleMode;
+use spotycli::uri::SpotifyUri;
+
+#[test]
+fn quoted_words_stay_together() {
+    assert_eq!(split_args(r#"playlist new "Gym  Mix" now"#).unwrap(), ["playlist", "new", "Gym  Mix", "now"]);
+    assert_eq!(split_args(r#"search """#).unwrap(), ["search", ""]);
+    assert!(split_args(r#"playlist open "Gym"#).is_err());
+}
+
+#[test]
+fn commands_parse_with_or_without_a_colon() {
+    assert_eq!(Command::parse(":vol 40").unwrap(), Command::Volume(40));
+    assert_eq!(Command::parse("  seek 1:23 ").unwrap(), Command::Seek(83_000));
+    assert_eq!(Command::parse("play").unwrap(), Command::Play(None));
+    assert_eq!(
+        Command::parse("play spotify:track:4uLU6hMCjMI75M1A2tKUQC").unwrap(),
+        Command::Play(Some(SpotifyUri::parse("spotify:track:4uLU6hMCjMI75M1A2tKUQC").unwrap()))
+    );
+    assert_eq!(Command::parse("device Living Room").unwrap(), Command::Device("Living Room".to_string()));
+    assert_eq!(Command::parse("shuffle smart").unwrap(), Command::Shuffle(ShuffleMode::SmartShuffle));
+    assert_eq!(Command::parse("repeat album").unwrap(), Command::Repeat(RepeatMode::Context));
+    assert_eq!(Command::parse(r#"playlist add "Road Trip""#).unwrap(), Command::PlaylistAdd("Road Trip".to_string()));
+    assert_eq!(Command::parse("queue clear-local").unwrap(), Command::QueueClearLocal);
+    assert_eq!(Command::parse("party stop").unwrap(), Command::PartyStop);
+    assert_eq!(Command::parse("radio stop").unwrap(), Command::RadioStop);
+    assert_eq!(Command::parse("q").unwrap(), Command::Quit);
+}
+
+#[test]
+fn bad_commands_explain_themselves() {
+    for (line, error) in [
+        ("", "Type a command, e.g. vol 40"),
+        ("vol 101", "Volume must be 0-100"),
+        ("vol", "Usage: vol <value>"),
+        ("search", "Usage: search <name>"),
+        ("playlist new", "Usage: playlist new|open|add <name>"),
+        ("shuffle sideways", "Unknown shuffle mode 'sideways' (on, off, smart)"),
+        ("frobnicate", "Unknown command 'frobnicate'"),
+    ] {
+        assert_eq!(Command::parse(line).unwrap_err().to_string(), error, "{}", line);
+    }
+}
+
+#[test]
+fn positions_take_seconds_minutes_and_hours() {
+    assert_eq!(parse_position("83").unwrap(), 83_000);
+    assert_eq!(parse_position("1:23").unwrap(), 83_000);
+    assert_eq!(parse_position("1:02:03").unwrap(), 3_723_000);
+    // The leading part may run long
+    assert_eq!(parse_position("90:00").unwrap(), 5_400_000);
+}
+
+#[test]
+fn bad_positions_are_refused() {
+    for text in ["", "1:", "x", "1:60", "1:00:75", "1:2:3:4", "-5"] {
+        assert!(parse_position(text).is_err(), "{}", text);
+    }
+    assert!(parse_position(&u64::MAX.to_string()).is_err());
+    assert!(parse_position(&format!("{}:00", u64::MAX / 60)).is_err());
+}
@@ -0,0 +1,116 @@
+// Shared by the integration tests; not every test uses every helper
+#![allow(dead_code)]
+
+use serde_json::{json, Value};
+use std::sync::{Arc, Mutex};
+
+use spotycli::cli::Failure;
+use spotycli::daemon::{Control, Engine, Player, SearchHit, SharedEngine};
+use spotycli::models::{CurrentPlayback, DeviceList, Track};
+use spotycli::uri::ItemKind;
+
+/// What the fake Spotify reports, and every command it was sent.
+#[derive(Default)]
+pub struct FakeState {
+    pub playback: Option<CurrentPlayback>,
+    pub controls: Vec<Control>,
+    /// Tracks after the current one.
+    pub queue: Vec<Track>,
+    /// Every query searched for.
+    pub searches: Vec<String>,
+    /// Reject commands the way Spotify does with no device open.
+    pub no_device: bool,
+    /// Searches wait for a permit here, to stand in for a slow Spotify.
+    pub search_gate: Option<Arc<tokio::sync::Semaphore>>,
+}
+
+#[derive(Clone, Default)]
+pub struct FakePlayer(pub Arc<Mutex<FakeState>>);
+
+impl Player for FakePlayer {
+    async fn playback(&mut self) -> Result<Option<CurrentPlayback>, Failure> {
+        Ok(self.0.lock().unwrap().playback.clone())
+    }
+
+    async fn devices(&mut self) -> Result<DeviceList, Failure> {
+        Ok(serde_json::from_value(json!({ "devices": [device("d1", "Kitchen Speaker", 50), device("d2", "Laptop", 20)] })).unwrap())
+    }
+
+    async fn search(&mut self, query: &str, _kind: ItemKind, _limit: u32) -> Result<Vec<SearchHit>, Failure> {
+        let gate = self.0.lock().unwrap().search_gate.clone();
+        if let Some(gate) = gate {
+            gate.acquire().await.unwrap().forget();
+        }
+        self.0.lock().unwrap().searches.push(query.to_string());
+        Ok(vec![SearchHit { uri: "spotify:track:found1".to_string(), name: format!("{}\tSomeone", query) }])
+    }
+
+    async fn library(&mut self, kind: ItemKind, _limit: u32) -> Result<Vec<SearchHit>, Failure> {
+        Ok(vec![SearchHit { uri: format!("spotify:{}:saved1", kind.as_str()), name: "Saved".to_string() }])
+    }
+
+    async fn is_liked(&mut self, track_id: &str) -> Result<bool, Failure> {
+        Ok(track_id == "t1")
+    }
+
+    async fn queue(&mut self) -> Result<Vec<Track>, Failure> {
+        Ok(self.0.lock().unwrap().queue.clone())
+    }
+
+    async fn control(&mut self, control: Control) -> Result<(), Failure> {
+        let mut state = self.0.lock().unwrap();
+        if state.no_device {
+            return Err(Failure::NoActiveDevice);
+        }
+        if let Some(playback) = state.playback.as_mut() {
+            match control {
+                Control::Pause => playback.is_playing = false,
+                Control::Resume => playback.is_playing = true,
+                Control::Volume(volume) => playback.device.volume_percent = Some(volume),
+                _ => {}
+            }
+        }
+        state.controls.push(control);
+        Ok(())
+    }
+}
+
+pub fn device(id: &str, name: &str, volume: u8) -> Value {
+    json!({
+        "id": id,
+        "is_active": id == "d1",
+        "is_private_session": false,
+        "is_restricted": false,
+        "name": name,
+        "type": "Speaker",
+        "volume_percent": volume,
+    })
+}
+
+pub fn playback(track_id: &str, playing: bool, volume: u8) -> CurrentPlayback {
+    serde_json::from_value(json!({
+        "device": device("d1", "Kitchen Speaker", volume),
+        "shuffle_state": false,
+        "repeat_state": "off",
+        "timestamp": 0,
+        "context": null,
+        "progress_ms": 30000,
+        "item": {
+            "id": track_id,
+            "name": format!("Song {}", track_id),
+            "uri": format!("spotify:track:{}", track_id),
+            "artists": [{ "id": "a1", "name": "The Band", "genres": null, "popularity": null }],
+            "album": null,
+            "duration_ms": 200000,
+            "preview_url": null,
+        },
+        "currently_playing_type": "track",
+        "is_playing": playing,
+    }))
+    .unwrap()
+}
+
+/// An engine over `player`, ready to serve.
+pub fn shared(player: &FakePlayer) -> SharedEngine<FakePlayer> {
+    Arc::new(Engine::new(player.clone()))
+}
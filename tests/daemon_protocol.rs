@@ -0,0 +1,213 @@
+use serde_json::{json, Value};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{UnixListener, UnixStream};
+use tokio::sync::Semaphore;
+
+use spotycli::cli::Failure;
+use spotycli::commands::RepeatMode;
+use spotycli::daemon::{serve, Adjust, Control, DaemonClient, Request};
+use spotycli::events::PlaybackEvent;
+use spotycli::models::CurrentPlayback;
+use spotycli::uri::ItemKind;
+
+mod common;
+
+use common::{playback, shared, FakePlayer};
+
+/// Starts a daemon on a fresh socket, returning its path and the fake's state.
+async fn start(playback: Option<CurrentPlayback>) -> (PathBuf, FakePlayer) {
+    static NEXT: AtomicUsize = AtomicUsize::new(0);
+    let path = std::env::temp_dir().join(format!("spotycli-test-{}-{}.sock", std::process::id(), NEXT.fetch_add(1, Ordering::SeqCst)));
+    let _ = std::fs::remove_file(&path);
+    let player = FakePlayer::default();
+    player.0.lock().unwrap().playback = playback;
+    let listener = UnixListener::bind(&path).unwrap();
+    tokio::spawn(serve(listener, shared(&player)));
+    (path, player)
+}
+
+/// Sends raw lines and reads one reply line per request.
+async fn exchange(path: &Path, lines: &[&str]) -> Vec<Value> {
+    let (reader, mut writer) = UnixStream::connect(path).await.unwrap().into_split();
+    let mut replies = BufReader::new(reader).lines();
+    let mut values = Vec::new();
+    for line in lines {
+        writer.write_all(format!("{}\n", line).as_bytes()).await.unwrap();
+        let reply = replies.next_line().await.unwrap().unwrap();
+        values.push(serde_json::from_str(&reply).unwrap());
+    }
+    values
+}
+
+async fn client(path: &Path) -> DaemonClient {
+    DaemonClient::connect(path).await.unwrap()
+}
+
+fn exit_code(result: Result<Value, Failure>) -> i32 {
+    result.unwrap_err().exit_code()
+}
+
+#[tokio::test]
+async fn ping_echoes_the_request_id() {
+    let (path, _) = start(None).await;
+    let replies = exchange(&path, &[r#"{"id":7,"cmd":"ping"}"#, r#"{"cmd":"ping"}"#]).await;
+    assert_eq!(replies[0], json!({ "id": 7, "ok": true, "data": "pong" }));
+    assert_eq!(replies[1], json!({ "ok": true, "data": "pong" }));
+}
+
+#[tokio::test]
+async fn malformed_and_unknown_requests_are_usage_errors() {
+    let (path, _) = start(None).await;
+    let replies = exchange(
+        &path,
+        &[
+            "not json",
+            r#"{"id":3,"cmd":"dance"}"#,
+            r#"{"id":4,"cmd":"volume","change":{"sideways":5}}"#,
+        ],
+    )
+    .await;
+    for reply in &replies {
+        assert_eq!(reply["ok"], json!(false));
+        assert_eq!(reply["code"], json!(2));
+        assert!(reply["error"].is_string());
+    }
+    assert_eq!(replies[0].get("id"), None);
+    assert_eq!(replies[1]["id"], json!(3));
+    assert_eq!(replies[2]["id"], json!(4));
+
+    // The connection survives bad lines
+    let replies = exchange(&path, &["{}", r#"{"id":5,"cmd":"ping"}"#]).await;
+    assert_eq!(replies[1]["ok"], json!(true));
+}
+
+#[tokio::test]
+async fn commands_reach_the_player() {
+    let (path, player) = start(Some(playback("t1", true, 50))).await;
+    let mut client = client(&path).await;
+    client.request(Request::Pause).await.unwrap();
+    client.request(Request::Repeat { mode: RepeatMode::Track }).await.unwrap();
+    client.request(Request::Play { target: Some("https://open.spotify.com/track/abc123?si=x".to_string()) }).await.unwrap();
+    client.request(Request::Play { target: Some("some song".to_string()) }).await.unwrap();
+
+    let controls = player.0.lock().unwrap().controls.clone();
+    assert_eq!(controls[0], Control::Pause);
+    assert_eq!(controls[1], Control::Repeat(RepeatMode::Track));
+    assert!(matches!(&controls[2], Control::Play(uri) if uri.to_string() == "spotify:track:abc123"));
+    assert!(matches!(&controls[3], Control::Play(uri) if uri.to_string() == "spotify:track:found1"));
+}
+
+#[tokio::test]
+async fn toggle_and_relative_changes_read_the_current_state() {
+    let (path, player) = start(Some(playback("t1", true, 50))).await;
+    let mut client = client(&path).await;
+    client.request(Request::Toggle).await.unwrap();
+    // The pause is applied to the cached state, so this resumes
+    client.request(Request::Toggle).await.unwrap();
+    client.request(Request::Volume { change: Adjust::Up(5) }).await.unwrap();
+    client.request(Request::Volume { change: Adjust::Up(5) }).await.unwrap();
+    client.request(Request::Volume { change: Adjust::Up(200) }).await.unwrap();
+    client.request(Request::Volume { change: Adjust::Down(250) }).await.unwrap();
+
+    let controls = player.0.lock().unwrap().controls.clone();
+    assert_eq!(
+        controls,
+        vec![Control::Pause, Control::Resume, Control::Volume(55), Control::Volume(60), Control::Volume(100), Control::Volume(0)]
+    );
+}
+
+#[tokio::test]
+async fn status_reports_the_track_and_liked_state() {
+    let (path, _) = start(Some(playback("t1", false, 50))).await;
+    let mut client = client(&path).await;
+    let status = client.request(Request::Status).await.unwrap();
+    assert_eq!(status["state"], json!("paused"));
+    assert_eq!(status["title"], json!("Song t1"));
+    assert_eq!(status["artists"], json!(["The Band"]));
+    assert_eq!(status["progress_ms"], json!(30000));
+    assert_eq!(status["liked"], json!(true));
+
+    let (path, _) = start(None).await;
+    let status = DaemonClient::connect(&path).await.unwrap().request(Request::Status).await.unwrap();
+    assert_eq!(status["state"], json!("stopped"));
+    assert_eq!(status["title"], Value::Null);
+}
+
+#[tokio::test]
+async fn transfer_finds_devices_by_name() {
+    let (path, player) = start(Some(playback("t1", true, 50))).await;
+    let mut client = client(&path).await;
+    client.request(Request::Transfer { device: "lap".to_string() }).await.unwrap();
+    assert_eq!(player.0.lock().unwrap().controls, vec![Control::Transfer("d2".to_string())]);
+
+    let missing = client.request(Request::Transfer { device: "Toaster".to_string() }).await;
+    assert_eq!(exit_code(missing), 5);
+}
+
+#[tokio::test]
+async fn failures_keep_their_exit_status() {
+    let (path, player) = start(None).await;
+    let mut client = client(&path).await;
+
+    // Nothing playing: relative changes have nothing to start from
+    assert_eq!(exit_code(client.request(Request::Volume { change: Adjust::Up(5) }).await), 4);
+    assert_eq!(exit_code(client.request(Request::Queue { uri: "spotify:album:xyz".to_string() }).await), 2);
+    assert_eq!(exit_code(client.request(Request::Queue { uri: "nonsense".to_string() }).await), 2);
+    assert_eq!(exit_code(client.request(Request::Like).await), 4);
+
+    player.0.lock().unwrap().no_device = true;
+    assert_eq!(exit_code(client.request(Request::Next).await), 4);
+}
+
+#[tokio::test]
+async fn cached_reads_do_not_wait_for_a_slow_search() {
+    let player = FakePlayer::default();
+    player.0.lock().unwrap().playback = Some(playback("t1", true, 50));
+    let gate = Arc::new(Semaphore::new(0));
+    player.0.lock().unwrap().search_gate = Some(gate.clone());
+    let engine = shared(&player);
+    engine.poll().await.unwrap();
+
+    let searching = tokio::spawn({
+        let engine = engine.clone();
+        async move { engine.handle(Request::Search { query: "daft punk".to_string(), kind: ItemKind::Track, limit: 5 }).await }
+    });
+    tokio::task::yield_now().await;
+    let current = tokio::time::timeout(Duration::from_secs(1), engine.handle(Request::Playback)).await.unwrap().unwrap();
+    assert_eq!(current["item"]["id"], "t1");
+
+    gate.add_permits(1);
+    searching.await.unwrap().unwrap();
+    assert_eq!(player.0.lock().unwrap().searches, ["daft punk"]);
+}
+
+#[tokio::test]
+async fn subscribers_get_playback_events() {
+    let (path, player) = start(Some(playback("t1", true, 50))).await;
+    let mut listener = client(&path).await;
+    // Make sure the first poll has happened before subscribing
+    listener.request(Request::Playback).await.unwrap();
+    listener.subscribe().await.unwrap();
+
+    player.0.lock().unwrap().playback = Some(playback("t2", true, 40));
+    client(&path).await.request(Request::Sync).await.unwrap();
+
+    let event = tokio::time::timeout(Duration::from_secs(5), listener.next_event()).await.unwrap().unwrap();
+    match event {
+        PlaybackEvent::TrackChanged { previous, current } => {
+            assert_eq!(previous.unwrap().id, "t1");
+            assert_eq!(current.unwrap().id, "t2");
+        }
+        other => panic!("expected a track change, got {:?}", other),
+    }
+    let event = tokio::time::timeout(Duration::from_secs(5), listener.next_event()).await.unwrap().unwrap();
+    assert!(matches!(event, PlaybackEvent::VolumeChanged { volume: 40 }));
+
+    // Event lines are tagged, so raw clients can tell them from replies
+    let line = serde_json::to_value(PlaybackEvent::VolumeChanged { volume: 40 }).unwrap();
+    assert_eq!(line, json!({ "event": "volume_changed", "volume": 40 }));
+}
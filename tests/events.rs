@@ -0,0 +1,106 @@
+//! Turning consecutive playback snapshots into change events.
+
+use serde_json::{json, Value};
+use std::time::Duration;
+
+use spotycli::events::{diff, PlaybackEvent, PlaybackEvents};
+use spotycli::models::{CurrentPlayback, PlaybackContext};
+
+mod common;
+
+use common::{device, playback};
+
+/// Each event as it goes out over the wire.
+fn wire(events: &[PlaybackEvent]) -> Vec<Value> {
+    events.iter().map(|event| serde_json::to_value(event).unwrap()).collect()
+}
+
+fn names(events: &[PlaybackEvent]) -> Vec<String> {
+    wire(events).iter().map(|event| event["event"].as_str().unwrap().to_string()).collect()
+}
+
+fn at(mut playback: CurrentPlayback, progress_ms: u64) -> CurrentPlayback {
+    playback.progress_ms = Some(progress_ms);
+    playback
+}
+
+#[test]
+fn starting_and_stopping() {
+    let playing = playback("t1", true, 50);
+    assert_eq!(names(&diff(None, Some(&playing), Duration::ZERO)), ["track_changed", "resumed", "device_changed"]);
+
+    let stopped = diff(Some(&playing), None, Duration::from_secs(1));
+    assert_eq!(names(&stopped), ["track_changed", "paused", "device_changed"]);
+    assert_eq!(wire(&stopped)[0]["current"], Value::Null);
+    assert_eq!(wire(&stopped)[2]["device"], Value::Null);
+    assert!(diff(None, None, Duration::ZERO).is_empty());
+}
+
+#[test]
+fn playing_on_is_not_a_seek() {
+    let before = playback("t1", true, 50);
+    assert!(diff(Some(&before), Some(&at(before.clone(), 35_000)), Duration::from_secs(5)).is_empty());
+    // Polling jitter stays under the tolerance
+    assert!(diff(Some(&before), Some(&at(before.clone(), 37_000)), Duration::from_secs(5)).is_empty());
+
+    let jumped = diff(Some(&before), Some(&at(before.clone(), 90_000)), Duration::from_secs(5));
+    assert_eq!(wire(&jumped), [json!({ "event": "seeked", "position_ms": 90_000 })]);
+
+    // Paused, the position shouldn't move at all
+    let paused = playback("t1", false, 50);
+    let moved = diff(Some(&paused), Some(&at(paused.clone(), 40_000)), Duration::from_secs(10));
+    assert_eq!(names(&moved), ["seeked"]);
+}
+
+#[test]
+fn settings_changes() {
+    let before = playback("t1", true, 50);
+    let mut after = playback("t1", false, 30);
+    after.shuffle_state = true;
+    after.repeat_state = "context".to_string();
+    after.context = Some(PlaybackContext {
+        external_urls: None,
+        href: String::new(),
+        context_type: "album".to_string(),
+        uri: "spotify:album:a1".to_string(),
+    });
+    assert_eq!(
+        wire(&diff(Some(&before), Some(&after), Duration::ZERO)),
+        [
+            json!({ "event": "paused" }),
+            json!({ "event": "volume_changed", "volume": 30 }),
+            json!({ "event": "shuffle_changed", "shuffle": true }),
+            json!({ "event": "repeat_changed", "repeat": "context" }),
+            json!({ "event": "context_changed", "uri": "spotify:album:a1" }),
+        ]
+    );
+
+    let mut moved = before.clone();
+    moved.device = serde_json::from_value(device("d2", "Phone", 50)).unwrap();
+    assert_eq!(names(&diff(Some(&before), Some(&moved), Duration::ZERO)), ["device_changed"]);
+}
+
+#[test]
+fn a_new_track_is_not_also_a_seek() {
+    let before = playback("t1", true, 50);
+    let after = at(playback("t2", true, 50), 0);
+    let events = diff(Some(&before), Some(&after), Duration::from_secs(1));
+    assert_eq!(names(&events), ["track_changed"]);
+    assert_eq!(wire(&events)[0]["previous"]["id"], "t1");
+    assert_eq!(wire(&events)[0]["current"]["id"], "t2");
+}
+
+#[tokio::test]
+async fn updates_are_broadcast() {
+    let mut events = PlaybackEvents::new();
+    let mut receiver = events.subscribe();
+    assert_eq!(names(&events.update(Some(&playback("t1", true, 50)))).len(), 3);
+    assert!(events.update(Some(&playback("t1", true, 50))).is_empty());
+    events.update(Some(&playback("t1", false, 50)));
+
+    let mut received = Vec::new();
+    while let Ok(event) = receiver.try_recv() {
+        received.push(event);
+    }
+    assert_eq!(names(&received), ["track_changed", "resumed", "device_changed", "paused"]);
+}
@@ -0,0 +1,138 @@
+//! The HTTP API and event WebSocket, driven through warp's test client.
+
+use serde_json::{json, Value};
+use std::time::Duration;
+
+use spotycli::daemon::{Control, SharedEngine};
+use spotycli::models::CurrentPlayback;
+
+mod common;
+
+use common::{playback, shared, FakePlayer};
+
+const TOKEN: &str = "s3cret";
+
+fn engine(playback: Option<CurrentPlayback>) -> (FakePlayer, SharedEngine<FakePlayer>) {
+    let player = FakePlayer::default();
+    player.0.lock().unwrap().playback = playback;
+    let engine = shared(&player);
+    (player, engine)
+}
+
+/// Sends a request with the token and returns the status and JSON body.
+async fn call(engine: &SharedEngine<FakePlayer>, method: &str, path: &str, body: &str) -> (u16, Value) {
+    let response = warp::test::request()
+        .method(method)
+        .path(path)
+        .header("authorization", format!("Bearer {}", TOKEN))
+        .body(body)
+        .reply(&spotycli::http::routes(TOKEN, engine.clone()))
+        .await;
+    (response.status().as_u16(), serde_json::from_slice(response.body()).unwrap())
+}
+
+#[tokio::test]
+async fn requests_need_the_token() {
+    let (player, engine) = engine(Some(playback("t1", true, 50)));
+    let routes = spotycli::http::routes(TOKEN, engine);
+    let missing = warp::test::request().method("POST").path("/api/pause").reply(&routes).await;
+    assert_eq!(missing.status(), 401);
+    let wrong = warp::test::request().method("POST").path("/api/pause").header("authorization", "Bearer nope").reply(&routes).await;
+    assert_eq!(wrong.status(), 401);
+    assert!(player.0.lock().unwrap().controls.is_empty());
+
+    let query = warp::test::request().path(&format!("/api/playback?token={}", TOKEN)).reply(&routes).await;
+    assert_eq!(query.status(), 200);
+}
+
+#[tokio::test]
+async fn reads_return_daemon_responses() {
+    let (player, engine) = engine(Some(playback("t1", true, 50)));
+    player.0.lock().unwrap().queue = vec![playback("t2", false, 0).item.unwrap()];
+
+    let (status, body) = call(&engine, "GET", "/api/status", "").await;
+    assert_eq!(status, 200);
+    assert_eq!(body["ok"], true);
+    assert_eq!(body["data"]["title"], "Song t1");
+
+    let (_, body) = call(&engine, "GET", "/api/queue", "").await;
+    assert_eq!(body["data"][0]["uri"], "spotify:track:t2");
+
+    let (_, body) = call(&engine, "GET", "/api/devices", "").await;
+    assert_eq!(body["data"].as_array().unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn search_and_library_take_query_parameters() {
+    let (player, engine) = engine(None);
+    let (status, body) = call(&engine, "GET", "/api/search?query=daft%20punk&kind=track&limit=5", "").await;
+    assert_eq!(status, 200);
+    assert_eq!(body["data"][0]["uri"], "spotify:track:found1");
+    assert_eq!(player.0.lock().unwrap().searches, vec!["daft punk".to_string()]);
+
+    let (_, body) = call(&engine, "GET", "/api/library?kind=playlist", "").await;
+    assert_eq!(body["data"], json!([{ "uri": "spotify:playlist:saved1", "name": "Saved" }]));
+
+    let (status, _) = call(&engine, "GET", "/api/search", "").await;
+    assert_eq!(status, 400);
+}
+
+#[tokio::test]
+async fn commands_are_posted_with_their_fields() {
+    let (player, engine) = engine(Some(playback("t1", true, 50)));
+    engine.poll().await.unwrap();
+    assert_eq!(call(&engine, "POST", "/api/pause", "").await.0, 200);
+    assert_eq!(call(&engine, "POST", "/api/volume", r#"{"change":{"up":5}}"#).await.0, 200);
+    assert_eq!(call(&engine, "POST", "/api/queue", r#"{"uri":"spotify:track:abc"}"#).await.0, 200);
+    let controls = player.0.lock().unwrap().controls.clone();
+    assert_eq!(controls[..2], [Control::Pause, Control::Volume(55)]);
+    assert!(matches!(&controls[2], Control::Queue(uri) if uri.to_string() == "spotify:track:abc"));
+}
+
+#[tokio::test]
+async fn failures_map_to_http_statuses() {
+    let (player, engine) = engine(Some(playback("t1", true, 50)));
+    let (status, body) = call(&engine, "POST", "/api/volume", "{not json").await;
+    assert_eq!(status, 400);
+    assert_eq!(body["code"], 2);
+    assert_eq!(call(&engine, "POST", "/api/frobnicate", "").await.0, 400);
+    assert_eq!(call(&engine, "GET", "/api/nothing", "").await.0, 404);
+    let (status, _) = call(&engine, "POST", "/api/queue", &format!(r#"{{"uri":"{}"}}"#, "x".repeat(70_000))).await;
+    assert_eq!(status, 413);
+
+    player.0.lock().unwrap().no_device = true;
+    let (status, body) = call(&engine, "POST", "/api/next", "").await;
+    assert_eq!(status, 409);
+    assert_eq!(body["code"], 4);
+}
+
+#[tokio::test]
+async fn websocket_streams_playback_events() {
+    let (player, engine) = engine(Some(playback("t1", true, 50)));
+    engine.poll().await.unwrap();
+    let mut socket = warp::test::ws()
+        .path(&format!("/api/events?token={}", TOKEN))
+        .handshake(spotycli::http::routes(TOKEN, engine.clone()))
+        .await
+        .unwrap();
+
+    // The subscription happens once the upgrade is handled; poll until it shows
+    let message = loop {
+        player.0.lock().unwrap().playback = Some(playback("t1", true, 10));
+        engine.poll().await.unwrap();
+        player.0.lock().unwrap().playback = Some(playback("t1", true, 70));
+        engine.poll().await.unwrap();
+        if let Ok(message) = tokio::time::timeout(Duration::from_millis(100), socket.recv()).await {
+            break message.unwrap();
+        }
+    };
+    let event: Value = serde_json::from_str(message.to_str().unwrap()).unwrap();
+    assert_eq!(event["event"], "volume_changed");
+}
+
+#[tokio::test]
+async fn websocket_needs_the_token() {
+    let (_, engine) = engine(None);
+    let refused = warp::test::ws().path("/api/events").handshake(spotycli::http::routes(TOKEN, engine)).await;
+    assert!(refused.is_err());
+}
@@ -0,0 +1,110 @@
+//! The queue the app holds on to: play next, removal, reordering and
+//! feeding Spotify one track at a time.
+
+use spotycli::models::Track;
+use spotycli::ui::ManagedQueue;
+
+mod common;
+
+use common::playback;
+
+fn track(id: &str) -> Track {
+    playback(id, true, 50).item.unwrap()
+}
+
+fn ids(queue: &ManagedQueue) -> Vec<&str> {
+    queue.tracks.iter().map(|t| t.id.as_str()).collect()
+}
+
+/// `later` added with play later, then `next` with play next.
+fn queue(later: &[&str], next: &[&str]) -> ManagedQueue {
+    let mut queue = ManagedQueue::default();
+    for id in later {
+        queue.push_back(track(id));
+    }
+    queue.play_next(next.iter().map(|id| track(id)).collect());
+    queue
+}
+
+#[test]
+fn play_next_goes_after_earlier_play_next_tracks() {
+    let mut queue = queue(&["l1", "l2"], &["n1", "n2"]);
+    queue.play_next(vec![track("n3")]);
+    queue.push_back(track("l3"));
+    assert_eq!(ids(&queue), ["n1", "n2", "n3", "l1", "l2", "l3"]);
+    assert_eq!(queue.next_count(), 3);
+}
+
+#[test]
+fn removing_keeps_the_play_next_count() {
+    let mut queue = queue(&["l1", "l2"], &["n1", "n2"]);
+    assert_eq!(queue.remove(3).unwrap().id, "l2");
+    assert_eq!(queue.next_count(), 2);
+    assert_eq!(queue.remove(0).unwrap().id, "n1");
+    assert_eq!(queue.next_count(), 1);
+    assert!(queue.remove(9).is_none());
+
+    queue.play_next(vec![track("n3")]);
+    assert_eq!(ids(&queue), ["n2", "n3", "l1"]);
+}
+
+#[test]
+fn retaining_and_clearing_keep_the_play_next_count() {
+    let mut queue = queue(&["l1", "l2"], &["n1", "n2"]);
+    queue.retain(|t| t.id != "n2" && t.id != "l1");
+    assert_eq!(ids(&queue), ["n1", "l2"]);
+    assert_eq!(queue.next_count(), 1);
+
+    queue.clear();
+    assert_eq!(queue.next_count(), 0);
+    queue.push_back(track("l3"));
+    queue.play_next(vec![track("n3")]);
+    assert_eq!(ids(&queue), ["n3", "l3"]);
+}
+
+#[test]
+fn moving_across_the_play_next_tracks_changes_sides() {
+    let mut queue = queue(&["l1", "l2"], &["n1"]);
+    assert_eq!(queue.move_by(1, true), Some(0));
+    assert_eq!(ids(&queue), ["l1", "n1", "l2"]);
+    assert_eq!(queue.next_count(), 2);
+    queue.play_next(vec![track("n2")]);
+    assert_eq!(ids(&queue), ["l1", "n1", "n2", "l2"]);
+
+    assert_eq!(queue.move_by(2, false), Some(3));
+    assert_eq!(ids(&queue), ["l1", "n1", "l2", "n2"]);
+    assert_eq!(queue.next_count(), 2);
+    assert_eq!(queue.move_by(1, false), Some(2));
+    assert_eq!(queue.next_count(), 1);
+
+    assert_eq!(queue.move_by(0, true), None);
+    assert_eq!(queue.move_by(3, false), None);
+}
+
+#[test]
+fn each_track_feeds_once() {
+    let mut queue = queue(&["l1", "l2"], &[]);
+    assert!(!queue.is_due("p1", 30_000));
+    assert!(queue.is_due("p1", 10_000));
+    assert_eq!(queue.take_for("p1").unwrap().id, "l1");
+    // Still near the end of the same track
+    assert!(!queue.is_due("p1", 5_000));
+    assert!(queue.is_due("l1", 0));
+    assert_eq!(queue.take_for("l1").unwrap().id, "l2");
+    assert!(queue.take_for("l2").is_none());
+}
+
+#[test]
+fn loading_trims_the_play_next_count() {
+    let saved = serde_json::json!({ "tracks": [track("n1"), track("l1")], "next_count": 5 });
+    let mut queue = ManagedQueue::from_json(&saved.to_string()).unwrap();
+    assert_eq!(queue.next_count(), 2);
+    queue.push_back(track("l2"));
+    queue.play_next(vec![track("n2")]);
+    assert_eq!(ids(&queue), ["n1", "l1", "n2", "l2"]);
+
+    // Older files have no count at all
+    let saved = serde_json::json!({ "tracks": [track("l1")] });
+    assert_eq!(ManagedQueue::from_json(&saved.to_string()).unwrap().next_count(), 0);
+    assert!(ManagedQueue::from_json("not json").is_none());
+}
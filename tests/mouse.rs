@@ -0,0 +1,60 @@
+//! Hit-testing clicks against the last draw, and telling double-clicks apart.
+
+use std::time::Duration;
+
+use ratatui::layout::Rect;
+
+use spotycli::ui::{ClickTracker, HitAreas};
+
+fn areas() -> HitAreas {
+    HitAreas {
+        list_rows: Some(Rect::new(20, 3, 40, 10)),
+        sidebar_nav: Some(Rect::new(1, 1, 18, 8)),
+        volume_bar: Some(Rect::new(70, 20, 11, 1)),
+        ..HitAreas::default()
+    }
+}
+
+#[test]
+fn rows_are_counted_from_the_top_of_their_area() {
+    let areas = areas();
+    // Corners are inside, one past the edge is not
+    assert_eq!(areas.list_row(20, 3), Some(0));
+    assert_eq!(areas.list_row(59, 12), Some(9));
+    assert_eq!(areas.list_row(19, 3), None);
+    assert_eq!(areas.list_row(60, 3), None);
+    assert_eq!(areas.list_row(20, 2), None);
+    assert_eq!(areas.list_row(20, 13), None);
+    assert_eq!(areas.sidebar_row(1, 4), Some(3));
+    // Nothing registered, nothing hit
+    assert_eq!(areas.sidebar_playlist_row(1, 4), None);
+    assert_eq!(areas.seek_ratio(70, 20), None);
+}
+
+#[test]
+fn bars_report_where_along_them_the_click_was() {
+    let areas = areas();
+    assert_eq!(areas.volume_ratio(70, 20), Some(0.0));
+    assert_eq!(areas.volume_ratio(75, 20), Some(0.5));
+    assert_eq!(areas.volume_ratio(80, 20), Some(1.0));
+    assert_eq!(areas.volume_ratio(81, 20), None);
+    assert_eq!(areas.volume_ratio(75, 21), None);
+    // Dragging past either end clamps
+    assert_eq!(areas.volume_drag_ratio(0), Some(0.0));
+    assert_eq!(areas.volume_drag_ratio(200), Some(1.0));
+}
+
+#[test]
+fn two_quick_clicks_on_one_row_are_a_double_click() {
+    let mut clicks = ClickTracker::default();
+    assert!(!clicks.click(4));
+    assert!(clicks.click(4));
+    // A third click starts over
+    assert!(!clicks.click(4));
+
+    assert!(!clicks.click(5));
+    assert!(!clicks.click(6));
+
+    std::thread::sleep(Duration::from_millis(450));
+    assert!(!clicks.click(6));
+}
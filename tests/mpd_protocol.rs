@@ -0,0 +1,220 @@
+//! The MPD protocol server, spoken to over a local TCP socket.
+
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader, Lines};
+use tokio::net::tcp::{OwnedReadHalf, OwnedWriteHalf};
+use tokio::net::{TcpListener, TcpStream};
+
+use spotycli::commands::RepeatMode;
+use spotycli::daemon::{Control, SharedEngine};
+use spotycli::models::{CurrentPlayback, ShuffleMode, Track};
+
+mod common;
+
+use common::{playback, shared, FakePlayer};
+
+struct Mpd {
+    lines: Lines<BufReader<OwnedReadHalf>>,
+    writer: OwnedWriteHalf,
+    player: FakePlayer,
+    engine: SharedEngine<FakePlayer>,
+    greeting: String,
+}
+
+impl Mpd {
+    async fn send(&mut self, line: &str) {
+        self.writer.write_all(format!("{}\n", line).as_bytes()).await.unwrap();
+    }
+
+    /// Lines up to and including the closing `OK` or `ACK`.
+    async fn reply(&mut self) -> Vec<String> {
+        let mut reply = Vec::new();
+        loop {
+            let line = tokio::time::timeout(Duration::from_secs(5), self.lines.next_line()).await.unwrap().unwrap().unwrap();
+            let done = line == "OK" || line.starts_with("ACK ");
+            reply.push(line);
+            if done {
+                return reply;
+            }
+        }
+    }
+
+    async fn command(&mut self, line: &str) -> Vec<String> {
+        self.send(line).await;
+        self.reply().await
+    }
+
+    fn controls(&self) -> Vec<Control> {
+        self.player.0.lock().unwrap().controls.clone()
+    }
+}
+
+async fn connect(playback: Option<CurrentPlayback>) -> Mpd {
+    let player = FakePlayer::default();
+    player.0.lock().unwrap().playback = playback;
+    let engine = shared(&player);
+    // Settle the first poll's events before the client subscribes
+    engine.poll().await.unwrap();
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap();
+    tokio::spawn(spotycli::mpd::serve(listener, engine.clone()));
+    let (reader, writer) = TcpStream::connect(address).await.unwrap().into_split();
+    let mut lines = BufReader::new(reader).lines();
+    let greeting = lines.next_line().await.unwrap().unwrap();
+    Mpd { lines, writer, player, engine, greeting }
+}
+
+fn track(id: &str) -> Track {
+    playback(id, false, 0).item.unwrap()
+}
+
+fn has(reply: &[String], line: &str) -> bool {
+    reply.iter().any(|l| l == line)
+}
+
+#[tokio::test]
+async fn greets_with_a_protocol_version() {
+    let mpd = connect(None).await;
+    assert!(mpd.greeting.starts_with("OK MPD 0."), "{}", mpd.greeting);
+}
+
+#[tokio::test]
+async fn status_reports_playback() {
+    let mut mpd = connect(Some(playback("t1", true, 40))).await;
+    mpd.player.0.lock().unwrap().queue = vec![track("t2")];
+    let status = mpd.command("status").await;
+    for line in ["volume: 40", "repeat: 0", "random: 0", "state: play", "song: 0", "songid: 1", "playlistlength: 2", "nextsong: 1", "duration: 200.000", "OK"] {
+        assert!(has(&status, line), "{} missing from {:?}", line, status);
+    }
+    let elapsed = status.iter().find_map(|l| l.strip_prefix("elapsed: ")).unwrap();
+    assert!(elapsed.parse::<f64>().unwrap() >= 30.0);
+}
+
+#[tokio::test]
+async fn status_without_playback_is_stopped() {
+    let mut mpd = connect(None).await;
+    let status = mpd.command("status").await;
+    assert!(has(&status, "state: stop"));
+    assert!(has(&status, "playlistlength: 0"));
+    assert!(!status.iter().any(|l| l.starts_with("song:")));
+    assert_eq!(mpd.command("currentsong").await, vec!["OK"]);
+}
+
+#[tokio::test]
+async fn currentsong_describes_the_track() {
+    let mut mpd = connect(Some(playback("t1", false, 50))).await;
+    let song = mpd.command("currentsong").await;
+    assert_eq!(
+        song,
+        vec!["file: spotify:track:t1", "Artist: The Band", "Title: Song t1", "Time: 200", "duration: 200.000", "Pos: 0", "Id: 1", "OK"]
+    );
+}
+
+#[tokio::test]
+async fn commands_control_playback() {
+    let mut mpd = connect(Some(playback("t1", false, 50))).await;
+    for command in ["play", "pause 1", "pause", "next", "previous", "setvol 30", "volume -10", "seekcur +5", "seekcur 12.5", "random 1", "repeat 1", "single 1", "stop"] {
+        assert_eq!(mpd.command(command).await, vec!["OK"], "{}", command);
+    }
+    let controls = mpd.controls();
+    assert_eq!(controls[..5], [Control::Resume, Control::Pause, Control::Resume, Control::Next, Control::Previous]);
+    assert_eq!(controls[5..7], [Control::Volume(30), Control::Volume(20)]);
+    assert!(matches!(controls[7], Control::Seek(ms) if (35_000..36_000).contains(&ms)), "{:?}", controls[7]);
+    assert_eq!(
+        controls[8..],
+        [
+            Control::Seek(12_500),
+            Control::Shuffle(ShuffleMode::On),
+            Control::Repeat(RepeatMode::Context),
+            Control::Repeat(RepeatMode::Track),
+            Control::Pause,
+        ]
+    );
+}
+
+#[tokio::test]
+async fn seeking_forward_stops_at_the_end_of_the_track() {
+    let mut mpd = connect(Some(playback("t1", true, 50))).await;
+    assert_eq!(mpd.command("seekcur +1e300").await, vec!["OK"]);
+    assert_eq!(mpd.controls(), vec![Control::Seek(200_000)]);
+}
+
+#[tokio::test]
+async fn playlistinfo_lists_the_queue_after_the_current_track() {
+    let mut mpd = connect(Some(playback("t1", true, 50))).await;
+    mpd.player.0.lock().unwrap().queue = vec![track("t2"), track("t3")];
+    let reply = mpd.command("playlistinfo").await;
+    let files: Vec<&str> = reply.iter().filter_map(|l| l.strip_prefix("file: ")).collect();
+    assert_eq!(files, ["spotify:track:t1", "spotify:track:t2", "spotify:track:t3"]);
+    assert!(has(&reply, "Pos: 2") && has(&reply, "Id: 3"));
+
+    let one = mpd.command("playlistid 2").await;
+    assert_eq!(one[0], "file: spotify:track:t2");
+    assert_eq!(mpd.command("playlistinfo 9").await, vec!["ACK [50@0] {playlistinfo} No such song"]);
+    assert_eq!(mpd.command("play 3").await, vec!["ACK [50@0] {play} Bad song index"]);
+    assert!(mpd.controls().is_empty());
+
+    assert_eq!(mpd.command("playid 3").await, vec!["OK"]);
+    assert_eq!(mpd.controls(), vec![Control::Next, Control::Next]);
+}
+
+#[tokio::test]
+async fn search_builds_a_spotify_query() {
+    let mut mpd = connect(None).await;
+    let reply = mpd.command(r#"search artist "Daft Punk" title "One \"More\" Time""#).await;
+    let query = r#"artist:"Daft Punk" track:"One "More" Time""#;
+    assert_eq!(mpd.player.0.lock().unwrap().searches, vec![query.to_string()]);
+    assert_eq!(reply, vec!["file: spotify:track:found1".to_string(), format!("Title: {}", query), "Artist: Someone".to_string(), "OK".to_string()]);
+
+    assert_eq!(mpd.command("find any").await, vec!["ACK [2@0] {find} incorrect arguments"]);
+    assert!(mpd.command("search \"(artist == 'x')\"").await[0].starts_with("ACK [2@0] {search}"));
+}
+
+#[tokio::test]
+async fn errors_are_acked() {
+    let mut mpd = connect(Some(playback("t1", true, 50))).await;
+    assert_eq!(mpd.command("frobnicate").await, vec![r#"ACK [5@0] {} unknown command "frobnicate""#]);
+    assert_eq!(mpd.command("setvol 101").await, vec!["ACK [2@0] {setvol} Invalid volume value"]);
+    assert_eq!(mpd.command("random maybe").await, vec!["ACK [2@0] {random} Boolean (0/1) expected: maybe"]);
+    assert_eq!(mpd.command("seekid 4 10").await[0], "ACK [50@0] {seekid} Only the current song can be seeked");
+    assert!(mpd.controls().is_empty());
+
+    mpd.player.0.lock().unwrap().no_device = true;
+    assert!(mpd.command("next").await[0].starts_with("ACK [55@0] {next}"));
+}
+
+#[tokio::test]
+async fn command_lists_run_in_order_and_stop_at_a_failure() {
+    let mut mpd = connect(Some(playback("t1", true, 50))).await;
+    for line in ["command_list_ok_begin", "ping", "next", "command_list_end"] {
+        mpd.send(line).await;
+    }
+    assert_eq!(mpd.reply().await, vec!["list_OK", "list_OK", "OK"]);
+
+    for line in ["command_list_begin", "previous", "setvol nope", "next", "command_list_end"] {
+        mpd.send(line).await;
+    }
+    assert_eq!(mpd.reply().await, vec!["ACK [2@1] {setvol} Number expected: nope"]);
+    assert_eq!(mpd.controls(), vec![Control::Next, Control::Previous]);
+}
+
+#[tokio::test]
+async fn idle_reports_changed_subsystems() {
+    let mut mpd = connect(Some(playback("t1", true, 50))).await;
+    mpd.send("idle").await;
+    // Nothing has changed, so idle waits
+    assert!(tokio::time::timeout(Duration::from_millis(200), mpd.lines.next_line()).await.is_err());
+
+    mpd.player.0.lock().unwrap().playback = Some(playback("t2", true, 70));
+    mpd.engine.poll().await.unwrap();
+    let reply = mpd.reply().await;
+    assert!(has(&reply, "changed: player") && has(&reply, "changed: mixer") && has(&reply, "changed: playlist"), "{:?}", reply);
+
+    // Only the subsystems asked for, and noidle ends the wait
+    mpd.send("idle options").await;
+    mpd.player.0.lock().unwrap().playback = Some(playback("t2", false, 70));
+    mpd.engine.poll().await.unwrap();
+    mpd.send("noidle").await;
+    assert_eq!(mpd.reply().await, vec!["OK"]);
+    assert_eq!(mpd.command("ping").await, vec!["OK"]);
+}
@@ -0,0 +1,193 @@
+//! MPRIS against a private `dbus-daemon`; run with `--features mpris`.
+//! Skipped when `dbus-daemon` isn't installed.
+#![cfg(feature = "mpris")]
+
+use futures_util::StreamExt;
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader};
+use std::process::{Child, Command, Stdio};
+use std::time::Duration;
+use zbus::proxy::CacheProperties;
+use zbus::zvariant::{OwnedObjectPath, OwnedValue};
+use zbus::{connection, Connection, Proxy};
+
+use spotycli::commands::RepeatMode;
+use spotycli::daemon::{Control, SharedEngine};
+use spotycli::models::{CurrentPlayback, ShuffleMode};
+use spotycli::mpris::{BUS_NAME, OBJECT_PATH};
+
+mod common;
+
+use common::{playback, shared, FakePlayer};
+
+const PLAYER: &str = "org.mpris.MediaPlayer2.Player";
+
+/// A session bus of our own, killed on drop.
+struct Bus {
+    daemon: Child,
+    address: String,
+}
+
+impl Drop for Bus {
+    fn drop(&mut self) {
+        let _ = self.daemon.kill();
+        let _ = self.daemon.wait();
+    }
+}
+
+fn private_bus() -> Option<Bus> {
+    let mut daemon = Command::new("dbus-daemon")
+        .args(["--session", "--nofork", "--print-address"])
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .ok()?;
+    let mut address = String::new();
+    BufReader::new(daemon.stdout.take()?).read_line(&mut address).ok()?;
+    Some(Bus { daemon, address: address.trim().to_string() })
+}
+
+/// Everything a test needs: keep the bus and server alive while it runs.
+struct Published {
+    _bus: Bus,
+    _server: Connection,
+    client: Connection,
+    player: FakePlayer,
+    engine: SharedEngine<FakePlayer>,
+}
+
+impl Published {
+    async fn proxy(&self, interface: &'static str) -> Proxy<'static> {
+        zbus::proxy::Builder::new(&self.client)
+            .destination(BUS_NAME)
+            .unwrap()
+            .path(OBJECT_PATH)
+            .unwrap()
+            .interface(interface)
+            .unwrap()
+            .cache_properties(CacheProperties::No)
+            .build()
+            .await
+            .unwrap()
+    }
+
+    fn controls(&self) -> Vec<Control> {
+        self.player.0.lock().unwrap().controls.clone()
+    }
+}
+
+async fn publish(playback: Option<CurrentPlayback>) -> Option<Published> {
+    let Some(bus) = private_bus() else {
+        eprintln!("dbus-daemon not found; skipping");
+        return None;
+    };
+    let player = FakePlayer::default();
+    player.0.lock().unwrap().playback = playback;
+    let engine = shared(&player);
+    engine.poll().await.unwrap();
+    let server = spotycli::mpris::serve(connection::Builder::address(bus.address.as_str()).unwrap(), engine.clone())
+        .await
+        .unwrap();
+    let client = connection::Builder::address(bus.address.as_str()).unwrap().build().await.unwrap();
+    Some(Published { _bus: bus, _server: server, client, player, engine })
+}
+
+fn entry<T: TryFrom<OwnedValue>>(metadata: &HashMap<String, OwnedValue>, key: &str) -> T {
+    match T::try_from(metadata[key].try_clone().unwrap()) {
+        Ok(value) => value,
+        Err(_) => panic!("{} has an unexpected type", key),
+    }
+}
+
+#[tokio::test]
+async fn identifies_itself() {
+    let Some(published) = publish(None).await else { return };
+    let root = published.proxy("org.mpris.MediaPlayer2").await;
+    assert_eq!(root.get_property::<String>("Identity").await.unwrap(), "SpotyCli");
+    assert!(!root.get_property::<bool>("CanRaise").await.unwrap());
+    assert!(root.get_property::<Vec<String>>("SupportedUriSchemes").await.unwrap().contains(&"spotify".to_string()));
+}
+
+#[tokio::test]
+async fn publishes_playback_and_metadata() {
+    let Some(published) = publish(Some(playback("t1", false, 50))).await else { return };
+    let player = published.proxy(PLAYER).await;
+    assert_eq!(player.get_property::<String>("PlaybackStatus").await.unwrap(), "Paused");
+    assert_eq!(player.get_property::<String>("LoopStatus").await.unwrap(), "None");
+    assert_eq!(player.get_property::<f64>("Volume").await.unwrap(), 0.5);
+    assert_eq!(player.get_property::<i64>("Position").await.unwrap(), 30_000_000);
+    assert!(player.get_property::<bool>("CanControl").await.unwrap());
+
+    let metadata: HashMap<String, OwnedValue> = player.get_property("Metadata").await.unwrap();
+    assert_eq!(entry::<String>(&metadata, "xesam:title"), "Song t1");
+    assert_eq!(entry::<Vec<String>>(&metadata, "xesam:artist"), vec!["The Band".to_string()]);
+    assert_eq!(entry::<i64>(&metadata, "mpris:length"), 200_000_000);
+    assert_eq!(entry::<String>(&metadata, "xesam:url"), "https://open.spotify.com/track/t1");
+    assert_eq!(entry::<OwnedObjectPath>(&metadata, "mpris:trackid").as_str(), "/org/spotycli/track/t1");
+}
+
+#[tokio::test]
+async fn stopped_without_playback() {
+    let Some(published) = publish(None).await else { return };
+    let player = published.proxy(PLAYER).await;
+    assert_eq!(player.get_property::<String>("PlaybackStatus").await.unwrap(), "Stopped");
+    let metadata: HashMap<String, OwnedValue> = player.get_property("Metadata").await.unwrap();
+    assert_eq!(entry::<OwnedObjectPath>(&metadata, "mpris:trackid").as_str(), "/org/mpris/MediaPlayer2/TrackList/NoTrack");
+    assert_eq!(metadata.len(), 1);
+}
+
+#[tokio::test]
+async fn methods_and_setters_control_playback() {
+    let Some(published) = publish(Some(playback("t1", false, 50))).await else { return };
+    let player = published.proxy(PLAYER).await;
+    player.call_method("PlayPause", &()).await.unwrap();
+    player.call_method("Next", &()).await.unwrap();
+    player.call_method("Seek", &(-10_000_000i64)).await.unwrap();
+    player.call_method("OpenUri", &("spotify:album:abc",)).await.unwrap();
+    player.set_property("Volume", 0.25f64).await.unwrap();
+    player.set_property("LoopStatus", "Playlist").await.unwrap();
+    player.set_property("Shuffle", true).await.unwrap();
+
+    let controls = published.controls();
+    assert_eq!(controls[0], Control::Resume);
+    assert_eq!(controls[1], Control::Next);
+    assert!(matches!(controls[2], Control::Seek(ms) if (20_000..21_000).contains(&ms)), "{:?}", controls[2]);
+    assert!(matches!(&controls[3], Control::Play(uri) if uri.to_string() == "spotify:album:abc"));
+    assert_eq!(controls[4..], [Control::Volume(25), Control::Repeat(RepeatMode::Context), Control::Shuffle(ShuffleMode::On)]);
+}
+
+#[tokio::test]
+async fn set_position_ignores_stale_track_ids() {
+    let Some(published) = publish(Some(playback("t1", false, 50))).await else { return };
+    let player = published.proxy(PLAYER).await;
+    let stale = OwnedObjectPath::try_from("/org/spotycli/track/t0").unwrap();
+    let current = OwnedObjectPath::try_from("/org/spotycli/track/t1").unwrap();
+    player.call_method("SetPosition", &(stale, 5_000_000i64)).await.unwrap();
+    player.call_method("SetPosition", &(current, 5_000_000i64)).await.unwrap();
+    assert_eq!(published.controls(), vec![Control::Seek(5000)]);
+}
+
+#[tokio::test]
+async fn playback_events_become_signals() {
+    let Some(published) = publish(Some(playback("t1", true, 50))).await else { return };
+    let properties = zbus::fdo::PropertiesProxy::builder(&published.client)
+        .destination(BUS_NAME)
+        .unwrap()
+        .path(OBJECT_PATH)
+        .unwrap()
+        .build()
+        .await
+        .unwrap();
+    let mut changes = properties.receive_properties_changed().await.unwrap();
+
+    published.player.0.lock().unwrap().playback = Some(playback("t2", true, 70));
+    published.engine.poll().await.unwrap();
+
+    let mut changed = Vec::new();
+    while !(changed.contains(&"Metadata".to_string()) && changed.contains(&"Volume".to_string())) {
+        let signal = tokio::time::timeout(Duration::from_secs(5), changes.next()).await.unwrap().unwrap();
+        let args = signal.args().unwrap();
+        assert_eq!(args.interface_name().as_str(), PLAYER);
+        changed.extend(args.changed_properties().keys().map(|name| name.to_string()));
+    }
+}
@@ -0,0 +1,104 @@
+//! Vim-style list motions: counts, prefixes and where they land.
+
+use std::time::Duration;
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+use spotycli::ui::{find_next, target, ListNavigator, Motion, NavResult};
+
+fn key(code: KeyCode) -> KeyEvent {
+    KeyEvent::new(code, KeyModifiers::NONE)
+}
+
+/// Feeds `keys` one character at a time and returns the last result.
+fn typed(navigator: &mut ListNavigator, keys: &str) -> NavResult {
+    keys.chars().map(|c| navigator.handle_key(&key(KeyCode::Char(c)))).last().unwrap()
+}
+
+fn labels(names: &[&str]) -> Vec<String> {
+    names.iter().map(|name| name.to_string()).collect()
+}
+
+#[test]
+fn counts_repeat_motions() {
+    let mut navigator = ListNavigator::default();
+    assert_eq!(typed(&mut navigator, "5"), NavResult::Pending);
+    assert_eq!(navigator.pending().as_deref(), Some("5"));
+    assert_eq!(typed(&mut navigator, "j"), NavResult::Move { motion: Motion::Down, count: Some(5) });
+    assert_eq!(navigator.pending(), None);
+    assert_eq!(target(Motion::Down, Some(5), 2, 100, 20), 7);
+
+    assert_eq!(typed(&mut navigator, "12G"), NavResult::Move { motion: Motion::Bottom, count: Some(12) });
+    assert_eq!(target(Motion::Bottom, Some(12), 50, 100, 20), 11);
+    assert_eq!(target(Motion::Bottom, None, 50, 100, 20), 99);
+}
+
+#[test]
+fn g_prefixes_wait_for_the_second_key() {
+    let mut navigator = ListNavigator::default();
+    assert_eq!(typed(&mut navigator, "g"), NavResult::Pending);
+    assert_eq!(navigator.pending().as_deref(), Some("g"));
+    assert_eq!(typed(&mut navigator, "g"), NavResult::Move { motion: Motion::Top, count: None });
+    assert_eq!(target(Motion::Top, None, 50, 100, 20), 0);
+    assert_eq!(typed(&mut navigator, "gp"), NavResult::Move { motion: Motion::Playing, count: None });
+}
+
+#[test]
+fn escape_cancels_a_half_typed_motion() {
+    let mut navigator = ListNavigator::default();
+    typed(&mut navigator, "3f");
+    assert_eq!(navigator.pending().as_deref(), Some("3f"));
+    assert_eq!(navigator.handle_key(&key(KeyCode::Esc)), NavResult::Pending);
+    assert_eq!(navigator.pending(), None);
+    // The count went with it
+    assert_eq!(typed(&mut navigator, "j"), NavResult::Move { motion: Motion::Down, count: None });
+}
+
+#[test]
+fn find_wraps_around_and_ignores_case() {
+    let mut navigator = ListNavigator::default();
+    assert_eq!(typed(&mut navigator, "fx"), NavResult::Move { motion: Motion::Find('x'), count: None });
+
+    let names = labels(&["Xanadu", "Abba", "  xtc", "Beatles"]);
+    assert_eq!(find_next(&names, 0, 'x'), Some(2));
+    assert_eq!(find_next(&names, 2, 'x'), Some(0));
+    assert_eq!(find_next(&names, 1, 'B'), Some(3));
+    assert_eq!(find_next(&names, 0, 'z'), None);
+}
+
+#[test]
+fn a_lone_digit_is_handed_back() {
+    let mut navigator = ListNavigator::default();
+    typed(&mut navigator, "3");
+    assert_eq!(navigator.handle_key(&key(KeyCode::Enter)), NavResult::Unhandled(Some('3')));
+
+    // Two digits are a count, not a view shortcut
+    typed(&mut navigator, "34");
+    assert_eq!(navigator.handle_key(&key(KeyCode::Enter)), NavResult::Unhandled(None));
+
+    typed(&mut navigator, "4");
+    assert_eq!(navigator.take_expired_digit(), None);
+    std::thread::sleep(Duration::from_millis(550));
+    assert_eq!(navigator.take_expired_digit(), Some('4'));
+    assert_eq!(navigator.take_expired_digit(), None);
+    assert_eq!(navigator.pending(), None);
+}
+
+#[test]
+fn counts_are_capped() {
+    let mut navigator = ListNavigator::default();
+    let result = typed(&mut navigator, "99999999999999999999j");
+    assert_eq!(result, NavResult::Move { motion: Motion::Down, count: Some(99_999) });
+    assert_eq!(target(Motion::Down, Some(99_999), 0, 10, 5), 9);
+    assert_eq!(target(Motion::PageDown, Some(usize::MAX), 3, 10, 5), 9);
+}
+
+#[test]
+fn motions_stay_inside_the_list() {
+    assert_eq!(target(Motion::Down, None, 0, 0, 20), 0);
+    assert_eq!(target(Motion::Bottom, Some(12), 0, 0, 20), 0);
+    assert_eq!(target(Motion::Up, Some(5), 2, 10, 4), 0);
+    assert_eq!(target(Motion::HalfPageDown, None, 0, 10, 4), 2);
+    assert_eq!(target(Motion::PageUp, None, 9, 10, 4), 5);
+    assert_eq!(target(Motion::Find('a'), None, 4, 10, 4), 4);
+}
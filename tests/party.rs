@@ -0,0 +1,105 @@
+//! Party mode's rules: rate limits, blocklists, votes and approval.
+
+use std::net::IpAddr;
+
+use spotycli::config::PartyConfig;
+use spotycli::models::Track;
+use spotycli::party::{Party, Refusal};
+
+mod common;
+
+use common::playback;
+
+fn guest(n: u8) -> IpAddr {
+    IpAddr::from([192, 168, 1, n])
+}
+
+fn track(id: &str) -> Track {
+    playback(id, true, 50).item.unwrap()
+}
+
+fn party(config: PartyConfig) -> Party {
+    Party::new(PartyConfig { submissions_per_window: 2, ..config })
+}
+
+#[test]
+fn guests_are_rate_limited() {
+    let mut party = party(PartyConfig::default());
+    party.submit(guest(1), None, track("t1")).unwrap();
+    party.submit(guest(1), None, track("t2")).unwrap();
+    assert!(matches!(party.submit(guest(1), None, track("t3")), Err(Refusal::TooSoon(_))));
+    // Other guests have their own allowance
+    party.submit(guest(2), Some("Sam".to_string()), track("t3")).unwrap();
+    assert_eq!(party.submit(guest(3), None, track("t3")), Err(Refusal::AlreadyPending));
+}
+
+#[test]
+fn blocklists_refuse_tracks_and_artists() {
+    let mut party = party(PartyConfig {
+        blocked_artists: vec!["the band".to_string()],
+        ..PartyConfig::default()
+    });
+    assert_eq!(party.submit(guest(1), None, track("t1")), Err(Refusal::NotAllowed));
+
+    let mut party = Party::new(PartyConfig {
+        blocked_tracks: vec!["spotify:track:bad".to_string()],
+        ..PartyConfig::default()
+    });
+    assert_eq!(party.submit(guest(1), None, track("bad")), Err(Refusal::NotAllowed));
+    party.submit(guest(1), None, track("good")).unwrap();
+}
+
+#[test]
+fn votes_reorder_and_toggle() {
+    let mut party = party(PartyConfig::default());
+    let first = party.submit(guest(1), None, track("t1")).unwrap();
+    let second = party.submit(guest(2), None, track("t2")).unwrap();
+    party.vote(guest(3), second).unwrap();
+    assert_eq!(party.pending().iter().map(|s| s.id).collect::<Vec<_>>(), vec![second, first]);
+
+    party.vote(guest(3), second).unwrap();
+    assert_eq!(party.pending()[0].id, first);
+    assert_eq!(party.vote(guest(3), 99), Err(Refusal::NoSuchSubmission));
+}
+
+#[test]
+fn only_approved_submissions_play() {
+    let mut party = party(PartyConfig::default());
+    let first = party.submit(guest(1), None, track("t1")).unwrap();
+    let second = party.submit(guest(2), None, track("t2")).unwrap();
+    assert!(party.take_next().is_none());
+    assert_eq!(party.awaiting_approval(), 2);
+
+    party.approve(second);
+    assert_eq!(party.take_next().unwrap().id, "t2");
+    assert_eq!(party.pending().iter().map(|s| s.id).collect::<Vec<_>>(), vec![first]);
+
+    let mut open = Party::new(PartyConfig { require_approval: false, ..PartyConfig::default() });
+    open.submit(guest(1), None, track("t1")).unwrap();
+    assert_eq!(open.take_next().unwrap().id, "t1");
+}
+
+#[test]
+fn blocking_a_guest_drops_their_submissions() {
+    let mut party = party(PartyConfig::default());
+    let id = party.submit(guest(1), Some("Pat".to_string()), track("t1")).unwrap();
+    party.submit(guest(1), None, track("t2")).unwrap();
+    let kept = party.submit(guest(2), None, track("t3")).unwrap();
+
+    assert_eq!(party.block_submitter(id).as_deref(), Some("Pat"));
+    assert_eq!(party.pending().iter().map(|s| s.id).collect::<Vec<_>>(), vec![kept]);
+    assert_eq!(party.submit(guest(1), None, track("t4")), Err(Refusal::Blocked));
+    assert_eq!(party.vote(guest(1), kept), Err(Refusal::Blocked));
+}
+
+#[test]
+fn searches_are_rate_limited() {
+    let mut party = party(PartyConfig::default());
+    while party.check_search(guest(1)).is_ok() {}
+    assert!(matches!(party.check_search(guest(1)), Err(Refusal::TooSoon(_))));
+    party.check_search(guest(2)).unwrap();
+
+    let id = party.submit(guest(3), None, track("t1")).unwrap();
+    party.block_submitter(id);
+    assert_eq!(party.check_search(guest(3)), Err(Refusal::Blocked));
+}
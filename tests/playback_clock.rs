@@ -0,0 +1,62 @@
+//! The local playback clock and how often it asks Spotify again.
+
+use std::time::Duration;
+
+use spotycli::models::CurrentPlayback;
+use spotycli::ui::PlaybackClock;
+
+mod common;
+
+use common::playback;
+
+fn at(progress_ms: u64, playing: bool) -> CurrentPlayback {
+    let mut playback = playback("t1", playing, 50);
+    playback.progress_ms = Some(progress_ms);
+    playback
+}
+
+fn synced(playback: Option<CurrentPlayback>) -> PlaybackClock {
+    let mut clock = PlaybackClock::default();
+    clock.sync(playback.as_ref());
+    clock
+}
+
+#[test]
+fn polls_slowly_when_nothing_is_playing() {
+    assert_eq!(PlaybackClock::default().next_poll(), Duration::from_secs(30));
+    assert_eq!(synced(None).next_poll(), Duration::from_secs(30));
+    assert_eq!(synced(Some(at(30_000, false))).next_poll(), Duration::from_secs(10));
+}
+
+#[test]
+fn polls_faster_near_the_end_of_a_track() {
+    assert_eq!(synced(Some(at(30_000, true))).next_poll(), Duration::from_secs(5));
+    assert_eq!(synced(Some(at(190_000, true))).next_poll(), Duration::from_secs(2));
+    // Not long after the track should have ended
+    let last_second = synced(Some(at(199_000, true))).next_poll();
+    assert!(last_second <= Duration::from_millis(1500), "{:?}", last_second);
+    assert!(last_second > Duration::from_millis(1000), "{:?}", last_second);
+    let over = synced(Some(at(250_000, true))).next_poll();
+    assert!(over <= Duration::from_millis(500), "{:?}", over);
+}
+
+#[test]
+fn the_position_moves_only_while_playing() {
+    let paused = synced(Some(at(30_000, false)));
+    std::thread::sleep(Duration::from_millis(20));
+    assert_eq!(paused.position_ms(), 30_000);
+
+    let mut playing = synced(Some(at(30_000, true)));
+    std::thread::sleep(Duration::from_millis(20));
+    assert!(playing.position_ms() >= 30_020);
+
+    playing.set_playing(false);
+    let frozen = playing.position_ms();
+    std::thread::sleep(Duration::from_millis(20));
+    assert_eq!(playing.position_ms(), frozen);
+
+    playing.seek(60_000);
+    assert_eq!(playing.position_ms(), 60_000);
+    // Never past the end of the track
+    assert_eq!(synced(Some(at(250_000, true))).position_ms(), 200_000);
+}
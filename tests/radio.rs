@@ -0,0 +1,214 @@
+//! The radio engine, picking from the catalog in `fixtures/radio_catalog.json`.
+//! Track ids start with their artist's id, e.g. `a2-top1` is by `a2`.
+
+use anyhow::{anyhow, Result};
+use serde::Deserialize;
+use serde_json::json;
+use std::collections::HashMap;
+use std::time::Duration;
+
+use spotycli::config::{RadioConfig, RadioSource};
+use spotycli::models::{Album, Artist, Track};
+use spotycli::radio::{Catalog, Radio, Station, StationCommand, StationEvent};
+
+#[derive(Deserialize)]
+struct Fixture {
+    artists: HashMap<String, String>,
+    related: HashMap<String, Vec<String>>,
+    top_tracks: HashMap<String, Vec<String>>,
+    albums: HashMap<String, Vec<String>>,
+    liked: Vec<String>,
+    history: Vec<String>,
+}
+
+struct FakeCatalog {
+    fixture: Fixture,
+    /// Answer related artists with an error, like an app without access.
+    related_fails: bool,
+}
+
+impl FakeCatalog {
+    fn new() -> Self {
+        let fixture = serde_json::from_str(include_str!("fixtures/radio_catalog.json")).unwrap();
+        Self { fixture, related_fails: false }
+    }
+
+    fn artist(&self, id: &str) -> Artist {
+        let name = &self.fixture.artists[id];
+        serde_json::from_value(json!({ "id": id, "name": name, "genres": null, "popularity": null })).unwrap()
+    }
+
+    fn track(&self, id: &str) -> Track {
+        let artist = id.split('-').next().unwrap();
+        let album = self.fixture.albums.iter().find(|(_, tracks)| tracks.iter().any(|t| t == id)).map(|(album, _)| {
+            json!({ "id": album, "name": album, "artists": [], "release_date": null, "total_tracks": 5, "images": null })
+        });
+        serde_json::from_value(json!({
+            "id": id,
+            "name": id,
+            "uri": format!("spotify:track:{}", id),
+            "artists": [self.artist(artist)],
+            "album": album,
+            "duration_ms": 200000,
+            "preview_url": null,
+        }))
+        .unwrap()
+    }
+
+    fn tracks(&self, ids: &[String]) -> Vec<Track> {
+        ids.iter().map(|id| self.track(id)).collect()
+    }
+}
+
+impl Catalog for FakeCatalog {
+    async fn related_artists(&self, artist_id: &str) -> Result<Vec<Artist>> {
+        if self.related_fails {
+            return Err(anyhow!("403 Forbidden"));
+        }
+        Ok(self.fixture.related.get(artist_id).into_iter().flatten().map(|id| self.artist(id)).collect())
+    }
+
+    async fn top_tracks(&self, artist_id: &str) -> Result<Vec<Track>> {
+        Ok(self.tracks(self.fixture.top_tracks.get(artist_id).map(Vec::as_slice).unwrap_or_default()))
+    }
+
+    async fn album_tracks(&self, album: &Album) -> Result<Vec<Track>> {
+        self.fixture.albums.get(&album.id).map(|ids| self.tracks(ids)).ok_or_else(|| anyhow!("no album {}", album.id))
+    }
+
+    async fn liked_tracks(&self) -> Result<Vec<Track>> {
+        Ok(self.tracks(&self.fixture.liked))
+    }
+
+    async fn history(&self) -> Result<Vec<Track>> {
+        Ok(self.tracks(&self.fixture.history))
+    }
+}
+
+fn config(sources: &[RadioSource], artist_gap: usize) -> RadioConfig {
+    RadioConfig { sources: sources.to_vec(), artist_gap, avoid_recent: 2, ..RadioConfig::default() }
+}
+
+fn ids(tracks: &[Track]) -> Vec<&str> {
+    tracks.iter().map(|t| t.id.as_str()).collect()
+}
+
+#[tokio::test]
+async fn mixes_every_source_in_turn() {
+    let catalog = FakeCatalog::new();
+    let mut radio = Radio::new(&config(&RadioConfig::default().sources, 0));
+    let mut logs = Vec::new();
+    let picks = radio.next_batch(&catalog, &catalog.track("a1-seed"), 6, &mut logs).await;
+    // Related artists, the seed's top tracks, the album, liked songs and
+    // history each get a turn; a1-top1 was played recently
+    assert_eq!(ids(&picks), ["a2-top1", "a1-top2", "a1-side4", "a2-liked", "a2-old", "a2-top2"]);
+    assert!(logs.iter().any(|line| line.contains("Picked 6")));
+}
+
+#[tokio::test]
+async fn artists_wait_their_turn() {
+    let catalog = FakeCatalog::new();
+    let mut radio = Radio::new(&config(&RadioConfig::default().sources, 2));
+    let picks = radio.next_batch(&catalog, &catalog.track("a1-seed"), 20, &mut Vec::new()).await;
+    for window in picks.windows(3) {
+        let artists: Vec<&str> = window.iter().map(|t| t.artists[0].id.as_str()).collect();
+        assert!(artists[0] != artists[1] && artists[1] != artists[2] && artists[0] != artists[2], "{:?}", ids(&picks));
+    }
+    // The seed's artist counts as just played
+    assert_ne!(picks[0].artists[0].id, "a1");
+    assert_ne!(picks[1].artists[0].id, "a1");
+}
+
+#[tokio::test]
+async fn skips_the_seed_recent_plays_and_strangers() {
+    let catalog = FakeCatalog::new();
+    let mut radio = Radio::new(&config(&RadioConfig::default().sources, 0));
+    let picks = radio.next_batch(&catalog, &catalog.track("a1-seed"), 50, &mut Vec::new()).await;
+    let picked = ids(&picks);
+    for skipped in ["a1-seed", "a1-top1", "a4-recent", "a4-liked", "a4-old"] {
+        assert!(!picked.contains(&skipped), "{} in {:?}", skipped, picked);
+    }
+    // Everything else, once each
+    assert_eq!(picks.len(), 12);
+}
+
+#[tokio::test]
+async fn album_neighbours_come_nearest_first() {
+    let catalog = FakeCatalog::new();
+    let mut radio = Radio::new(&config(&[RadioSource::AlbumNeighbors], 0));
+    let picks = radio.next_batch(&catalog, &catalog.track("a1-seed"), 4, &mut Vec::new()).await;
+    assert_eq!(ids(&picks), ["a1-side4", "a1-side2", "a1-side5", "a1-side1"]);
+}
+
+#[tokio::test]
+async fn failing_sources_are_logged_and_skipped() {
+    let mut catalog = FakeCatalog::new();
+    catalog.related_fails = true;
+    let mut radio = Radio::new(&config(&[RadioSource::RelatedArtists, RadioSource::ArtistTopTracks], 0));
+    let mut logs = Vec::new();
+    let picks = radio.next_batch(&catalog, &catalog.track("a1-seed"), 5, &mut logs).await;
+    assert_eq!(ids(&picks), ["a1-top2", "a1-top3"]);
+    assert!(logs.iter().any(|line| line.contains("403 Forbidden")));
+}
+
+#[tokio::test]
+async fn later_batches_pick_new_tracks() {
+    let catalog = FakeCatalog::new();
+    let mut radio = Radio::new(&config(&RadioConfig::default().sources, 1));
+    let seed = catalog.track("a1-seed");
+    let first = radio.next_batch(&catalog, &seed, 4, &mut Vec::new()).await;
+    let second = radio.next_batch(&catalog, &seed, 4, &mut Vec::new()).await;
+    assert_eq!(second.len(), 4);
+    assert!(second.iter().all(|t| !ids(&first).contains(&t.id.as_str())));
+}
+
+#[test]
+fn the_same_song_on_another_release_is_a_repeat() {
+    let catalog = FakeCatalog::new();
+    let mut radio = Radio::new(&config(&[], 0));
+    let mut single = catalog.track("a2-top1");
+    single.id = "a2-single".to_string();
+    let picks = radio.pick(vec![vec![catalog.track("a2-top1"), single, catalog.track("a3-top1")]], 3);
+    assert_eq!(ids(&picks), ["a2-top1", "a3-top1"]);
+}
+
+#[tokio::test]
+async fn thumbs_up_picks_around_the_track_next() {
+    let catalog = FakeCatalog::new();
+    let mut station = Station::new(&config(&RadioConfig::default().sources, 0), catalog.track("a1-seed"));
+    station.thumbs_up(catalog.track("a3-top1"));
+    let picks = station.next_batch(&catalog, 2, &mut Vec::new()).await;
+    assert_eq!(ids(&picks), ["a5-top1", "a3-top2"]);
+}
+
+#[tokio::test]
+async fn thumbs_down_drops_the_artist() {
+    let catalog = FakeCatalog::new();
+    let mut station = Station::new(&config(&RadioConfig::default().sources, 0), catalog.track("a1-seed"));
+    station.thumbs_down(&catalog.track("a2-top1"));
+    let picks = station.next_batch(&catalog, 50, &mut Vec::new()).await;
+    assert!(!picks.is_empty());
+    assert!(picks.iter().all(|t| t.artists[0].id != "a2"), "{:?}", ids(&picks));
+}
+
+#[tokio::test]
+async fn a_running_station_answers_top_ups() {
+    let catalog = FakeCatalog::new();
+    let seed = catalog.track("a1-seed");
+    let mut handle = spotycli::radio::spawn(Station::new(&RadioConfig::default(), seed), catalog);
+    handle.send(StationCommand::TopUp(2));
+    assert!(handle.topping_up);
+
+    let mut logged = false;
+    let picks = loop {
+        match handle.try_recv() {
+            Some(StationEvent::Log(_)) => logged = true,
+            Some(StationEvent::Picked(picks)) => break picks,
+            None => tokio::time::sleep(Duration::from_millis(10)).await,
+        }
+    };
+    assert!(logged);
+    assert_eq!(picks.len(), 2);
+    assert!(!handle.topping_up);
+    assert!(picks.iter().all(|t| handle.picked.contains(&t.id)));
+}
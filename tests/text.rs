@@ -0,0 +1,41 @@
+//! Fitting text into terminal columns when characters have different widths.
+
+use spotycli::ui::{display_width, pad_left_to_width, truncate_to_width};
+
+#[test]
+fn wide_characters_take_two_columns() {
+    assert_eq!(display_width("abc"), 3);
+    assert_eq!(display_width("日本語"), 6);
+    assert_eq!(display_width("🎵"), 2);
+    // A combining accent adds nothing
+    assert_eq!(display_width("e\u{301}"), 1);
+}
+
+#[test]
+fn text_that_fits_is_left_alone() {
+    assert_eq!(truncate_to_width("日本語", 6, "…"), "日本語");
+    assert_eq!(truncate_to_width("", 0, "…"), "");
+}
+
+#[test]
+fn truncation_never_splits_a_wide_character() {
+    assert_eq!(truncate_to_width("日本語の曲", 7, "…"), "日本語…");
+    // One column short of the next character: leave the gap
+    assert_eq!(truncate_to_width("日本語の曲", 6, "…"), "日本…");
+    assert_eq!(display_width(&truncate_to_width("日本語の曲", 6, "…")), 5);
+    assert_eq!(truncate_to_width("Song 🎵🎵", 7, "..."), "Song...");
+    assert_eq!(truncate_to_width("Long title", 4, "..."), "L...");
+}
+
+#[test]
+fn tiny_widths_keep_what_fits_of_the_ellipsis() {
+    assert_eq!(truncate_to_width("Long title", 2, "..."), "..");
+    assert_eq!(truncate_to_width("Long title", 0, "…"), "");
+}
+
+#[test]
+fn padding_lines_up_wide_text() {
+    assert_eq!(pad_left_to_width("日本", 6, "…"), "  日本");
+    assert_eq!(pad_left_to_width("日本語の曲", 6, "…"), " 日本…");
+    assert_eq!(display_width(&pad_left_to_width("日本語の曲", 6, "…")), 6);
+}
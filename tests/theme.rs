@@ -0,0 +1,63 @@
+//! Fitting theme colors to what the terminal can show.
+
+use ratatui::style::Color;
+
+use spotycli::ui::{ColorSupport, Theme};
+
+fn theme(color: Color) -> Theme {
+    Theme { text: color, ..Theme::default() }
+}
+
+fn adapted(color: Color, support: ColorSupport) -> Color {
+    theme(color).adapt(support).text
+}
+
+#[test]
+fn rgb_maps_to_the_nearest_of_256() {
+    let indexed = |r, g, b| adapted(Color::Rgb(r, g, b), ColorSupport::Indexed256);
+    assert_eq!(indexed(255, 0, 0), Color::Indexed(196));
+    assert_eq!(indexed(0, 0, 0), Color::Indexed(16));
+    assert_eq!(indexed(255, 255, 255), Color::Indexed(231));
+    // Grays land on the grayscale ramp rather than the cube
+    assert_eq!(indexed(128, 128, 128), Color::Indexed(244));
+    // Named colors are already in the palette
+    assert_eq!(adapted(Color::Yellow, ColorSupport::Indexed256), Color::Yellow);
+}
+
+#[test]
+fn basic_terminals_get_the_nearest_named_color() {
+    assert_eq!(adapted(Color::Rgb(200, 10, 10), ColorSupport::Basic16), Color::Red);
+    assert_eq!(adapted(Color::Indexed(196), ColorSupport::Basic16), Color::LightRed);
+    assert_eq!(adapted(Color::Indexed(244), ColorSupport::Basic16), Color::DarkGray);
+    assert_eq!(adapted(Color::Cyan, ColorSupport::Basic16), Color::Cyan);
+}
+
+#[test]
+fn true_color_keeps_everything_and_no_color_drops_it() {
+    assert_eq!(adapted(Color::Rgb(1, 2, 3), ColorSupport::TrueColor), Color::Rgb(1, 2, 3));
+    let plain = theme(Color::Rgb(1, 2, 3)).adapt(ColorSupport::NoColor);
+    assert_eq!(plain.text, Color::Reset);
+    assert_eq!(plain.text_style().fg, None);
+}
+
+#[test]
+fn color_support_comes_from_the_environment() {
+    // Every case in one test: the environment is shared between threads
+    let detect = |no_color: Option<&str>, term: &str, colorterm: &str| {
+        match no_color {
+            Some(value) => std::env::set_var("NO_COLOR", value),
+            None => std::env::remove_var("NO_COLOR"),
+        }
+        std::env::set_var("TERM", term);
+        std::env::set_var("COLORTERM", colorterm);
+        ColorSupport::detect()
+    };
+    assert_eq!(detect(None, "xterm-256color", "truecolor"), ColorSupport::TrueColor);
+    assert_eq!(detect(None, "xterm", "24BIT"), ColorSupport::TrueColor);
+    assert_eq!(detect(None, "screen-256color", ""), ColorSupport::Indexed256);
+    assert_eq!(detect(None, "xterm", ""), ColorSupport::Basic16);
+    assert_eq!(detect(None, "dumb", "truecolor"), ColorSupport::NoColor);
+    assert_eq!(detect(Some("1"), "xterm-256color", "truecolor"), ColorSupport::NoColor);
+    // An empty NO_COLOR doesn't count
+    assert_eq!(detect(Some(""), "xterm-256color", ""), ColorSupport::Indexed256);
+}
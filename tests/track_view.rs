@@ -0,0 +1,91 @@
+//! Sorting and filtering a loaded track list without reordering it.
+
+use serde_json::json;
+
+use spotycli::config::TrackColumn;
+use spotycli::models::Track;
+use spotycli::ui::{SortDirection, TrackView};
+
+fn track(id: &str, name: &str, artist: &str, album: &str, duration_ms: u32) -> Track {
+    serde_json::from_value(json!({
+        "id": id,
+        "name": name,
+        "uri": format!("spotify:track:{}", id),
+        "artists": [{ "id": artist, "name": artist, "genres": null, "popularity": null }],
+        "album": { "id": album, "name": album, "artists": [], "release_date": null, "total_tracks": 10, "images": null },
+        "duration_ms": duration_ms,
+        "preview_url": null,
+    }))
+    .unwrap()
+}
+
+fn tracks() -> Vec<Track> {
+    vec![
+        track("t0", "one more time", "Daft Punk", "Discovery", 320_000),
+        track("t1", "Around the World", "Daft Punk", "Homework", 429_000),
+        track("t2", "Breathe", "Télépopmusik", "Genetic World", 279_000),
+        track("t3", "Around the World", "Red Hot Chili Peppers", "Californication", 238_000),
+    ]
+}
+
+#[test]
+fn without_sort_or_filter_rows_follow_the_list() {
+    assert_eq!(TrackView::default().indices(&tracks()), [0, 1, 2, 3]);
+}
+
+#[test]
+fn filter_terms_match_in_order_across_fields() {
+    let mut view = TrackView::default();
+    view.filter = "wrld".to_string();
+    assert_eq!(view.indices(&tracks()), [1, 2, 3]);
+    // Every term has to match, in any field
+    view.filter = "around PEPPERS".to_string();
+    assert_eq!(view.indices(&tracks()), [3]);
+    view.filter = "dlrow".to_string();
+    assert!(view.indices(&tracks()).is_empty());
+}
+
+#[test]
+fn sorting_ignores_case_and_keeps_ties_in_order() {
+    let mut view = TrackView::default();
+    view.sort = Some((TrackColumn::Title, SortDirection::Ascending));
+    assert_eq!(view.indices(&tracks()), [1, 3, 2, 0]);
+    view.sort = Some((TrackColumn::Title, SortDirection::Descending));
+    assert_eq!(view.indices(&tracks()), [0, 2, 1, 3]);
+    view.sort = Some((TrackColumn::Duration, SortDirection::Ascending));
+    assert_eq!(view.indices(&tracks()), [3, 2, 0, 1]);
+    view.sort = Some((TrackColumn::Artists, SortDirection::Ascending));
+    view.filter = "world".to_string();
+    assert_eq!(view.indices(&tracks()), [1, 3, 2]);
+}
+
+#[test]
+fn sort_keys_cycle_and_reverse() {
+    let mut view = TrackView::default();
+    view.reverse_sort();
+    assert_eq!(view.indices(&tracks()), [3, 2, 1, 0]);
+    // From the reversed original order, cycling starts at Title
+    view.cycle_sort();
+    assert_eq!(view.sort, Some((TrackColumn::Title, SortDirection::Descending)));
+    view.cycle_sort();
+    assert_eq!(view.sort, Some((TrackColumn::Artists, SortDirection::Descending)));
+    for _ in 0..5 {
+        view.cycle_sort();
+    }
+    assert_eq!(view.sort, None);
+    view.reverse_sort();
+    view.reverse_sort();
+    assert_eq!(view.sort, None);
+}
+
+#[test]
+fn a_changed_list_is_sorted_afresh() {
+    let mut view = TrackView::default();
+    view.sort = Some((TrackColumn::Duration, SortDirection::Ascending));
+    let mut list = tracks();
+    assert_eq!(view.indices(&list), [3, 2, 0, 1]);
+    list.remove(3);
+    assert_eq!(view.indices(&list), [2, 0, 1]);
+    list.push(track("t4", "Short", "Someone", "Single", 1_000));
+    assert_eq!(view.indices(&list), [3, 2, 0, 1]);
+}
@@ -0,0 +1,62 @@
+//! Spotify URIs and open.spotify.com links.
+
+use spotycli::uri::{ItemKind, SpotifyUri};
+
+const ID: &str = "4uLU6hMCjMI75M1A2tKUQC";
+
+fn parsed(input: &str) -> (ItemKind, String) {
+    let uri = SpotifyUri::parse(input).unwrap_or_else(|e| panic!("{}: {}", input, e));
+    (uri.kind(), uri.id().to_string())
+}
+
+#[test]
+fn uris_and_links_parse() {
+    for (input, kind) in [
+        (format!("spotify:track:{}", ID), ItemKind::Track),
+        (format!("  spotify:album:{}\n", ID), ItemKind::Album),
+        (format!("spotify:user:someone:playlist:{}", ID), ItemKind::Playlist),
+        (format!("https://open.spotify.com/artist/{}", ID), ItemKind::Artist),
+        (format!("http://open.spotify.com/show/{}", ID), ItemKind::Show),
+        (format!("open.spotify.com/episode/{}", ID), ItemKind::Episode),
+        (format!("https://open.spotify.com/intl-de/track/{}?si=abc123&context=x", ID), ItemKind::Track),
+        (format!("https://open.spotify.com/embed/playlist/{}#top", ID), ItemKind::Playlist),
+        (format!("https://open.spotify.com/user/someone/playlist/{}/", ID), ItemKind::Playlist),
+    ] {
+        assert_eq!(parsed(&input), (kind, ID.to_string()), "{}", input);
+    }
+}
+
+#[test]
+fn uris_print_and_link_back() {
+    let uri: SpotifyUri = format!("https://open.spotify.com/intl-fr/album/{}?si=x", ID).parse().unwrap();
+    assert_eq!(uri.to_string(), format!("spotify:album:{}", ID));
+    assert_eq!(uri.url(), format!("https://open.spotify.com/album/{}", ID));
+    assert!(uri.is_context());
+    assert!(!SpotifyUri::parse(&format!("spotify:episode:{}", ID)).unwrap().is_context());
+}
+
+#[test]
+fn anything_else_is_refused() {
+    for input in [
+        "",
+        "daft punk",
+        "spotify:track",
+        "spotify:song:abc",
+        "spotify:track:not-an-id",
+        "spotify:track:abc:extra",
+        "https://open.spotify.com/",
+        "https://open.spotify.com/genre/abc",
+        "https://example.com/track/abc",
+    ] {
+        assert!(SpotifyUri::parse(input).is_err(), "{}", input);
+    }
+}
+
+#[test]
+fn parse_as_takes_bare_ids_and_checks_the_kind() {
+    assert_eq!(SpotifyUri::parse_as(ItemKind::Playlist, ID).unwrap().to_string(), format!("spotify:playlist:{}", ID));
+    assert_eq!(SpotifyUri::parse_as(ItemKind::Track, &format!("https://open.spotify.com/track/{}?si=x", ID)).unwrap().kind(), ItemKind::Track);
+    let wrong = SpotifyUri::parse_as(ItemKind::Track, &format!("spotify:album:{}", ID)).unwrap_err();
+    assert!(wrong.to_string().starts_with("Expected a track"), "{}", wrong);
+    assert!(SpotifyUri::parse_as(ItemKind::Track, "not an id").is_err());
+}